@@ -73,12 +73,16 @@ async function execGateway(args) {
 
 async function setAuthorities(chainSpec, chainConfig, opts) {
   let registry = new types.TypeRegistry();
-  let validators = chainConfig.validators.map(({substrate_id, eth_address}) => {
+  let validators = chainConfig.validators.map(({substrate_id, eth_address, dot_id, sol_id}) => {
     let substrateId = (new types.GenericAccountId(registry, substrate_id));
+    let dotId = (new types.GenericAccountId(registry, dot_id));
+    let solId = (new types.GenericAccountId(registry, sol_id));
 
     return {
       substrate_id: [...substrateId.toU8a()],
-      eth_address
+      eth_address,
+      dot_id: [...dotId.toU8a()],
+      sol_id: [...solId.toU8a()]
     };
   });
 
@@ -154,7 +158,9 @@ async function setAssetInfo(chainSpec, chainConfig, opts) {
     }
     let asset = `ETH:${tokenAddress}`;
     let decimals = must(tokenInfo, 'decimals', (d) => typeof(d) === 'number');
-    let liquidity_factor = must(info, 'liquidity_factor', (d) => typeof(d) === 'number' && d > 0 && d <= 1) * 1e18;
+    let liquidity_factor = must(info, 'liquidity_factor', (d) => typeof(d) === 'number' && d > 0 && d <= 1);
+    let collateral_factor = must(info, 'collateral_factor', (d) => typeof(d) === 'undefined' || (typeof(d) === 'number' && d > 0 && d <= 1)) || liquidity_factor;
+    let borrow_factor = must(info, 'borrow_factor', (d) => typeof(d) === 'undefined' || (typeof(d) === 'number' && d > 0 && d <= 1)) || liquidity_factor;
     let rate_model;
     let rateModelRaw = must(info, 'rate_model');
     if (typeof(rateModelRaw) === 'string') {
@@ -167,15 +173,18 @@ async function setAssetInfo(chainSpec, chainConfig, opts) {
     }
     let miner_shares = must(info, 'miner_shares', (d) => typeof(d) === 'number' && d >= 0 && d <= 1) * 1e18;
     let supply_cap = must(info, 'supply_cap', (d) => typeof(d) === 'undefined' || typeof(d) === 'number') || 0;
+    let borrow_cap = must(info, 'borrow_cap', (d) => typeof(d) === 'undefined' || typeof(d) === 'number') || 0;
     let ticker = must(info, 'ticker', (d) => typeof(d) === 'undefined' || typeof(d) === 'string') || symbol;
 
     return {
       asset,
       decimals,
-      liquidity_factor,
+      collateral_factor: collateral_factor * 1e18,
+      borrow_factor: borrow_factor * 1e18,
       rate_model,
       miner_shares,
       supply_cap,
+      borrow_cap,
       symbol,
       ticker
     };
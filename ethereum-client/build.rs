@@ -0,0 +1,92 @@
+use std::{env, fs, path::Path};
+
+/// The events of the compiled starport ABI, which `yarn abi` exports from the contract build.
+/// Unlike the ABIs saved when deploying to a network, this always matches the contract source.
+const STARPORT_EVENTS_ABI_PATH: &str = "../ethereum/.build/Starport-events.json";
+
+/// Generate the `ethabi::Event` definitions of every event in the starport ABI,
+///  as an expression which `events.rs` includes.
+fn main() {
+    println!("cargo:rerun-if-changed={}", STARPORT_EVENTS_ABI_PATH);
+
+    let abi_json =
+        fs::read_to_string(STARPORT_EVENTS_ABI_PATH).expect("Unable to read starport ABI");
+    let abi: serde_json::Value = serde_json::from_str(&abi_json).expect("Invalid starport ABI");
+    let events: Vec<String> = abi
+        .as_array()
+        .expect("Starport ABI must be an array")
+        .iter()
+        .filter(|item| item["type"] == "event")
+        .map(event_source)
+        .collect();
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("starport_events.rs");
+    fs::write(out_path, format!("vec![\n{}\n]\n", events.join(",\n")))
+        .expect("Unable to write starport events");
+}
+
+fn event_source(event: &serde_json::Value) -> String {
+    let inputs: Vec<String> = event["inputs"]
+        .as_array()
+        .expect("Event inputs must be an array")
+        .iter()
+        .map(|input| {
+            format!(
+                "ethabi::EventParam {{ name: String::from({:?}), kind: {}, indexed: {} }}",
+                input["name"]
+                    .as_str()
+                    .expect("Event input must have a name"),
+                param_type_source(
+                    input["type"]
+                        .as_str()
+                        .expect("Event input must have a type")
+                ),
+                input["indexed"].as_bool().unwrap_or(false)
+            )
+        })
+        .collect();
+    format!(
+        "ethabi::Event {{ name: String::from({:?}), inputs: vec![{}], anonymous: {} }}",
+        event["name"].as_str().expect("Event must have a name"),
+        inputs.join(", "),
+        event["anonymous"].as_bool().unwrap_or(false)
+    )
+}
+
+/// The `ParamType` expression for a solidity type in the ABI.
+fn param_type_source(kind: &str) -> String {
+    const PARAM_TYPE: &str = "ethabi::param_type::ParamType";
+    if kind.ends_with(']') {
+        let open = kind.rfind('[').expect("Unbalanced array type");
+        let inner = param_type_source(&kind[..open]);
+        return match &kind[open + 1..kind.len() - 1] {
+            "" => format!("{}::Array(Box::new({}))", PARAM_TYPE, inner),
+            len => format!(
+                "{}::FixedArray(Box::new({}), {})",
+                PARAM_TYPE,
+                inner,
+                len.parse::<usize>().expect("Invalid array length")
+            ),
+        };
+    }
+    match kind {
+        "address" => format!("{}::Address", PARAM_TYPE),
+        "bool" => format!("{}::Bool", PARAM_TYPE),
+        "string" => format!("{}::String", PARAM_TYPE),
+        "bytes" => format!("{}::Bytes", PARAM_TYPE),
+        _ if kind.starts_with("bytes") => {
+            format!("{}::FixedBytes({})", PARAM_TYPE, bits(&kind[5..], 0))
+        }
+        _ if kind.starts_with("uint") => format!("{}::Uint({})", PARAM_TYPE, bits(&kind[4..], 256)),
+        _ if kind.starts_with("int") => format!("{}::Int({})", PARAM_TYPE, bits(&kind[3..], 256)),
+        _ => panic!("Unsupported type in starport ABI: {}", kind),
+    }
+}
+
+/// The size suffix of a solidity type, `uint` and `int` alone being 256 bits.
+fn bits(suffix: &str, default: usize) -> usize {
+    match suffix {
+        "" if default > 0 => default,
+        _ => suffix.parse().expect("Invalid type size"),
+    }
+}
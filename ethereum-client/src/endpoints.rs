@@ -0,0 +1,305 @@
+use crate::{
+    fetch_and_decode_logs_paged, fetch_block_hash, EthereumClientError, HttpOptions, LogsPage,
+    LogsPaging,
+};
+use codec::{Decode, Encode};
+use frame_support::debug;
+use our_std::{cell::Cell, RuntimeDebug};
+use sp_runtime::offchain::storage::StorageValueRef;
+
+/// How long an endpoint is skipped after its first failure, doubling with each failure after.
+pub const BACKOFF_BASE_MILLIS: u64 = 6_000;
+
+/// The longest an endpoint is ever skipped for.
+pub const BACKOFF_MAX_MILLIS: u64 = 10 * 60 * 1_000;
+
+const ENDPOINT_HEALTH_PREFIX: &[u8] = b"ethereum_client::endpoint_health::";
+
+/// An RPC endpoint, along with the options for requests to it.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Endpoint {
+    pub url: String,
+    pub options: HttpOptions,
+}
+
+impl Endpoint {
+    pub fn new(url: &str, options: HttpOptions) -> Endpoint {
+        Endpoint {
+            url: String::from(url),
+            options,
+        }
+    }
+}
+
+impl From<&str> for Endpoint {
+    fn from(url: &str) -> Endpoint {
+        Endpoint::new(url, HttpOptions::default())
+    }
+}
+
+/// The health of an RPC endpoint, kept in offchain storage so it carries over between runs.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct EndpointHealth {
+    /// The number of requests to the endpoint which have failed in a row.
+    pub failures: u32,
+    /// The offchain timestamp until which the endpoint is only tried as a last resort.
+    pub retry_at: u64,
+}
+
+impl EndpointHealth {
+    pub fn is_healthy(&self, now: u64) -> bool {
+        now >= self.retry_at
+    }
+
+    /// The health of the endpoint after another request to it fails at `now`.
+    pub fn failed(self, now: u64) -> Self {
+        let failures = self.failures.saturating_add(1);
+        let backoff = BACKOFF_BASE_MILLIS
+            .saturating_mul(2u64.saturating_pow(failures - 1))
+            .min(BACKOFF_MAX_MILLIS);
+        EndpointHealth {
+            failures,
+            retry_at: now.saturating_add(backoff),
+        }
+    }
+}
+
+/// The order to try endpoints in: healthy endpoints in the order they are configured,
+///  followed by the rest in the order they may be retried.
+pub fn endpoint_order(healths: &[EndpointHealth], now: u64) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..healths.len()).collect();
+    order.sort_by_key(|&i| match healths[i] {
+        health if health.is_healthy(now) => (false, 0),
+        health => (true, health.retry_at),
+    });
+    order
+}
+
+/// Whether an error means the endpoint itself is failing, rather than the request.
+/// A node which refuses the logs of a single block counts as failing, so other nodes get a turn.
+fn is_endpoint_failure(err: &EthereumClientError) -> bool {
+    match err {
+        EthereumClientError::TooManyResults | EthereumClientError::ProvidersDisagree => false,
+        _ => true,
+    }
+}
+
+fn health_key(url: &str) -> Vec<u8> {
+    [ENDPOINT_HEALTH_PREFIX, url.as_bytes()].concat()
+}
+
+fn get_health(url: &str) -> EndpointHealth {
+    let key = health_key(url);
+    StorageValueRef::persistent(&key)
+        .get::<EndpointHealth>()
+        .flatten()
+        .unwrap_or_default()
+}
+
+fn set_health(url: &str, health: EndpointHealth) {
+    let key = health_key(url);
+    StorageValueRef::persistent(&key).set(&health);
+}
+
+fn ordered_endpoints(endpoints: &[Endpoint]) -> Vec<(&Endpoint, EndpointHealth)> {
+    let now = sp_io::offchain::timestamp().unix_millis();
+    let healths: Vec<EndpointHealth> = endpoints
+        .iter()
+        .map(|endpoint| get_health(&endpoint.url))
+        .collect();
+    endpoint_order(&healths, now)
+        .into_iter()
+        .map(|i| (&endpoints[i], healths[i]))
+        .collect()
+}
+
+/// Make a request to a single endpoint, recording whether the endpoint answered it.
+fn try_endpoint<T>(
+    endpoint: &Endpoint,
+    health: EndpointHealth,
+    request: impl FnOnce(&Endpoint) -> Result<T, EthereumClientError>,
+) -> Result<T, EthereumClientError> {
+    let url = &endpoint.url;
+    let result = request(endpoint);
+    match &result {
+        Ok(_) if health != EndpointHealth::default() => set_health(url, EndpointHealth::default()),
+        Err(err) if is_endpoint_failure(err) => {
+            let now = sp_io::offchain::timestamp().unix_millis();
+            let health = health.failed(now);
+            debug::warn!(
+                "RPC endpoint {} failed ({:?}), backing off until {}",
+                url,
+                err,
+                health.retry_at
+            );
+            set_health(url, health)
+        }
+        _ => (),
+    }
+    result
+}
+
+/// Make a request to the first endpoint which answers it.
+///
+/// Endpoints which failed recently are only tried once the healthy ones have failed too,
+///  so a node which is down is skipped until its backoff is over.
+pub fn with_failover<T>(
+    endpoints: &[Endpoint],
+    request: impl Fn(&Endpoint) -> Result<T, EthereumClientError>,
+) -> Result<T, EthereumClientError> {
+    let mut last_err = EthereumClientError::NoEndpoints;
+    for (endpoint, health) in ordered_endpoints(endpoints) {
+        match try_endpoint(endpoint, health, &request) {
+            Ok(value) => return Ok(value),
+            Err(err) if is_endpoint_failure(&err) => last_err = err,
+            Err(err) => return Err(err),
+        }
+    }
+    Err(last_err)
+}
+
+/// Make a request to endpoints until `quorum` of them return identical answers, and accept that answer.
+///
+/// Endpoints are tried in the same order as `with_failover`, and a quorum of 1 or less is just failover.
+/// Whichever answer `quorum` endpoints return is accepted, no matter which endpoint answered first.
+pub fn with_quorum<T: PartialEq>(
+    endpoints: &[Endpoint],
+    quorum: usize,
+    request: impl Fn(&Endpoint) -> Result<T, EthereumClientError>,
+) -> Result<T, EthereumClientError> {
+    if quorum <= 1 {
+        return with_failover(endpoints, request);
+    }
+
+    let mut candidates: Vec<(T, usize)> = vec![];
+    let mut last_err = EthereumClientError::NoEndpoints;
+    for (endpoint, health) in ordered_endpoints(endpoints) {
+        let fetched = match try_endpoint(endpoint, health, &request) {
+            Ok(fetched) => fetched,
+            Err(err) => {
+                last_err = err;
+                continue;
+            }
+        };
+        match candidates.iter_mut().find(|(answer, _)| *answer == fetched) {
+            Some((_, agreeing)) => *agreeing += 1,
+            None => {
+                if !candidates.is_empty() {
+                    debug::warn!("RPC endpoint {} disagrees with the others", endpoint.url);
+                }
+                candidates.push((fetched, 1));
+            }
+        }
+        if let Some(i) = candidates
+            .iter()
+            .position(|(_, agreeing)| *agreeing >= quorum)
+        {
+            return Ok(candidates.swap_remove(i).0);
+        }
+    }
+    if candidates.len() > 1 {
+        Err(EthereumClientError::ProvidersDisagree)
+    } else {
+        Err(last_err)
+    }
+}
+
+/// Fetch and decode the logs of a contract like `fetch_and_decode_logs_paged`, failing over
+///  between endpoints, but only once `quorum` endpoints return identical logs.
+///
+/// Logs must match exactly, down to their block hash and log index, for endpoints to agree.
+/// The first endpoint to answer decides how far the range is paged, and the rest fetch the same range.
+pub fn fetch_and_decode_logs_agreed(
+    endpoints: &[Endpoint],
+    address: &str,
+    from_block: u64,
+    to_block: u64,
+    paging: LogsPaging,
+    quorum: usize,
+) -> Result<LogsPage, EthereumClientError> {
+    let paged_to_block: Cell<Option<u64>> = Cell::new(None);
+    with_quorum(endpoints, quorum, |endpoint| {
+        let fetched = match paged_to_block.get() {
+            None => fetch_and_decode_logs_paged(endpoint, address, from_block, to_block, paging)?,
+            Some(paged_to_block) => {
+                let paging = LogsPaging {
+                    max_batches: usize::MAX,
+                    ..paging
+                };
+                fetch_and_decode_logs_paged(endpoint, address, from_block, paged_to_block, paging)?
+            }
+        };
+        paged_to_block.set(Some(paged_to_block.get().unwrap_or(fetched.to_block)));
+        Ok(fetched)
+    })
+}
+
+/// Fetch the hash of the block at the given height, only once `quorum` endpoints return the same hash.
+pub fn fetch_block_hash_agreed(
+    endpoints: &[Endpoint],
+    block_number: u64,
+    quorum: usize,
+) -> Result<[u8; 32], EthereumClientError> {
+    with_quorum(endpoints, quorum, |endpoint| {
+        fetch_block_hash(endpoint, block_number)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::endpoints::*;
+
+    #[test]
+    fn test_endpoint_backoff() {
+        let health = EndpointHealth::default().failed(1_000);
+        assert_eq!(
+            health,
+            EndpointHealth {
+                failures: 1,
+                retry_at: 1_000 + BACKOFF_BASE_MILLIS
+            }
+        );
+        assert!(!health.is_healthy(1_000));
+        assert!(health.is_healthy(1_000 + BACKOFF_BASE_MILLIS));
+
+        let health = health.failed(2_000);
+        assert_eq!(health.failures, 2);
+        assert_eq!(health.retry_at, 2_000 + 2 * BACKOFF_BASE_MILLIS);
+
+        let health = EndpointHealth {
+            failures: u32::MAX,
+            retry_at: 0,
+        }
+        .failed(2_000);
+        assert_eq!(health.failures, u32::MAX);
+        assert_eq!(health.retry_at, 2_000 + BACKOFF_MAX_MILLIS);
+    }
+
+    #[test]
+    fn test_endpoint_order() {
+        let healthy = EndpointHealth::default();
+        let backing_off = |retry_at| EndpointHealth {
+            failures: 1,
+            retry_at,
+        };
+        assert_eq!(endpoint_order(&[], 100), Vec::<usize>::new());
+        assert_eq!(
+            endpoint_order(&[healthy, backing_off(50), healthy], 100),
+            vec![0, 1, 2]
+        );
+        assert_eq!(
+            endpoint_order(&[backing_off(300), healthy, backing_off(200), healthy], 100),
+            vec![1, 3, 2, 0]
+        );
+    }
+
+    #[test]
+    fn test_is_endpoint_failure() {
+        assert!(is_endpoint_failure(&EthereumClientError::HttpTimeout));
+        assert!(is_endpoint_failure(&EthereumClientError::JsonParseError));
+        assert!(!is_endpoint_failure(&EthereumClientError::TooManyResults));
+        assert!(is_endpoint_failure(
+            &EthereumClientError::BlockTooManyResults(1)
+        ));
+    }
+}
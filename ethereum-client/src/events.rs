@@ -1,6 +1,6 @@
 use crate::hex::{decode_hex, decode_topic};
 use codec::{Decode, Encode};
-use our_std::convert::TryInto;
+use our_std::convert::{TryFrom, TryInto};
 use our_std::RuntimeDebug;
 
 use types_derive::Types;
@@ -35,332 +35,161 @@ pub enum EthereumEvent {
         notice_hash: [u8; 32],
         result: Vec<u8>,
     },
+    /// A log from the starport which could not be decoded, kept as it was returned by the node.
+    Unknown {
+        topics: Vec<String>,
+        data: String,
+        error: EventError,
+    },
 }
 
 lazy_static! {
-    static ref LOCK_EVENT: ethabi::Event = ethabi::Event {
-        name: String::from("Lock"),
-        inputs: vec![
-            ethabi::EventParam {
-                name: String::from("asset"),
-                kind: ethabi::param_type::ParamType::Address,
-                indexed: true
-            },
-            ethabi::EventParam {
-                name: String::from("sender"),
-                kind: ethabi::param_type::ParamType::Address,
-                indexed: true
-            },
-            ethabi::EventParam {
-                name: String::from("chain"),
-                kind: ethabi::param_type::ParamType::String,
-                indexed: false
-            },
-            ethabi::EventParam {
-                name: String::from("recipient"),
-                kind: ethabi::param_type::ParamType::FixedBytes(32),
-                indexed: true
-            },
-            ethabi::EventParam {
-                name: String::from("amount"),
-                kind: ethabi::param_type::ParamType::Uint(256),
-                indexed: false
-            }
-        ],
-        anonymous: false
-    };
-    static ref LOCK_EVENT_TOPIC: ethabi::Hash = LOCK_EVENT.signature();
-    static ref LOCK_CASH_EVENT: ethabi::Event = ethabi::Event {
-        name: String::from("LockCash"),
-        inputs: vec![
-            ethabi::EventParam {
-                name: String::from("sender"),
-                kind: ethabi::param_type::ParamType::Address,
-                indexed: true
-            },
-            ethabi::EventParam {
-                name: String::from("chain"),
-                kind: ethabi::param_type::ParamType::String,
-                indexed: false
-            },
-            ethabi::EventParam {
-                name: String::from("recipient"),
-                kind: ethabi::param_type::ParamType::FixedBytes(32),
-                indexed: true
-            },
-            ethabi::EventParam {
-                name: String::from("amount"),
-                kind: ethabi::param_type::ParamType::Uint(256),
-                indexed: false
-            },
-            ethabi::EventParam {
-                name: String::from("principal"),
-                kind: ethabi::param_type::ParamType::Uint(128),
-                indexed: false
-            },
-        ],
-        anonymous: false
+    /// The events of the starport by their topic, generated from its ABI by the build script.
+    static ref STARPORT_EVENTS: Vec<(ethabi::Hash, ethabi::Event)> = {
+        let events: Vec<ethabi::Event> =
+            include!(concat!(env!("OUT_DIR"), "/starport_events.rs"));
+        events
+            .into_iter()
+            .map(|event| (event.signature(), event))
+            .collect()
     };
-    static ref LOCK_CASH_EVENT_TOPIC: ethabi::Hash = LOCK_CASH_EVENT.signature();
-    static ref EXEC_TRX_REQUEST_EVENT: ethabi::Event = ethabi::Event {
-        name: String::from("ExecTrxRequest"),
-        inputs: vec![
-            ethabi::EventParam {
-                name: String::from("account"),
-                kind: ethabi::param_type::ParamType::Address,
-                indexed: true
-            },
-            ethabi::EventParam {
-                name: String::from("title"),
-                kind: ethabi::param_type::ParamType::String,
-                indexed: false
-            },
-        ],
-        anonymous: false
-    };
-    static ref EXEC_TRX_REQUEST_EVENT_TOPIC: ethabi::Hash = EXEC_TRX_REQUEST_EVENT.signature();
-    static ref EXECUTE_PROPOSAL_EVENT: ethabi::Event = ethabi::Event {
-        name: String::from("ExecuteProposal"),
-        inputs: vec![
-            ethabi::EventParam {
-                name: String::from("title"),
-                kind: ethabi::param_type::ParamType::String,
-                indexed: false
-            },
-            ethabi::EventParam {
-                name: String::from("extrinsics"),
-                kind: ethabi::param_type::ParamType::Array(Box::new(
-                    ethabi::param_type::ParamType::Bytes
-                )),
-                indexed: false
-            }
-        ],
-        anonymous: false
-    };
-    static ref EXECUTE_PROPOSAL_EVENT_TOPIC: ethabi::Hash = EXECUTE_PROPOSAL_EVENT.signature();
-    static ref NOTICE_INVOKED_EVENT: ethabi::Event = ethabi::Event {
-        name: String::from("NoticeInvoked"),
-        inputs: vec![
-            ethabi::EventParam {
-                name: String::from("eraId"),
-                kind: ethabi::param_type::ParamType::Uint(32),
-                indexed: true
-            },
-            ethabi::EventParam {
-                name: String::from("eraIndex"),
-                kind: ethabi::param_type::ParamType::Uint(32),
-                indexed: true
-            },
-            ethabi::EventParam {
-                name: String::from("noticeHash"),
-                kind: ethabi::param_type::ParamType::FixedBytes(32),
-                indexed: true
-            },
-            ethabi::EventParam {
-                name: String::from("result"),
-                kind: ethabi::param_type::ParamType::Bytes,
-                indexed: false
-            },
-        ],
-        anonymous: false
-    };
-    static ref NOTICE_INVOKED_EVENT_TOPIC: ethabi::Hash = NOTICE_INVOKED_EVENT.signature();
 }
 
-fn parse_lock_log(log: ethabi::Log) -> Result<EthereumEvent, EventError> {
-    match &log.params[..] {
-        [ethabi::LogParam {
-            value: ethabi::token::Token::Address(asset),
-            ..
-        }, ethabi::LogParam {
-            value: ethabi::token::Token::Address(sender),
-            ..
-        }, ethabi::LogParam {
-            value: ethabi::token::Token::String(chain),
-            ..
-        }, ethabi::LogParam {
-            value: ethabi::token::Token::FixedBytes(recipient),
-            ..
-        }, ethabi::LogParam {
-            value: ethabi::token::Token::Uint(amount),
-            ..
-        }] => Ok(EthereumEvent::Lock {
-            asset: (*asset).into(),
-            sender: (*sender).into(),
-            chain: chain.into(),
-            recipient: recipient[..]
-                .try_into()
-                .map_err(|_| EventError::InvalidRecipient)?,
-            amount: (*amount).try_into().map_err(|_| EventError::Overflow)?,
-        }),
-        _ => Err(EventError::InvalidLogParams),
-    }
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub enum EventError {
+    UnknownEventTopic([u8; 32]),
+    ErrorParsingLog,
+    InvalidHex,
+    InvalidTopic,
+    Overflow,
+    InvalidHash,
+    InvalidLogParams,
+    InvalidRecipient,
+    MissingTopicsOrData,
 }
 
-fn parse_lock_cash_log(log: ethabi::Log) -> Result<EthereumEvent, EventError> {
-    match &log.params[..] {
-        [ethabi::LogParam {
-            value: ethabi::token::Token::Address(sender),
-            ..
-        }, ethabi::LogParam {
-            value: ethabi::token::Token::String(chain),
-            ..
-        }, ethabi::LogParam {
-            value: ethabi::token::Token::FixedBytes(recipient),
-            ..
-        }, ethabi::LogParam {
-            value: ethabi::token::Token::Uint(amount),
-            ..
-        }, ethabi::LogParam {
-            value: ethabi::token::Token::Uint(principal),
-            ..
-        }] => Ok(EthereumEvent::LockCash {
-            sender: (*sender).into(),
-            chain: chain.into(),
-            recipient: recipient[..]
-                .try_into()
-                .map_err(|_| EventError::InvalidRecipient)?,
-            amount: (*amount).try_into().map_err(|_| EventError::Overflow)?,
-            principal: (*principal).try_into().map_err(|_| EventError::Overflow)?,
-        }),
-        _ => Err(EventError::InvalidLogParams),
+/// The params of a parsed log, looked up by their names in the ABI.
+struct LogParams(Vec<ethabi::LogParam>);
+
+impl LogParams {
+    fn get(&self, name: &str) -> Result<&ethabi::Token, EventError> {
+        self.0
+            .iter()
+            .find(|param| param.name == name)
+            .map(|param| &param.value)
+            .ok_or(EventError::InvalidLogParams)
     }
-}
 
-fn parse_exec_trx_request_log(log: ethabi::Log) -> Result<EthereumEvent, EventError> {
-    match &log.params[..] {
-        [ethabi::LogParam {
-            value: ethabi::token::Token::Address(account),
-            ..
-        }, ethabi::LogParam {
-            value: ethabi::token::Token::String(trx_request),
-            ..
-        }] => Ok(EthereumEvent::ExecTrxRequest {
-            account: (*account).into(),
-            trx_request: trx_request.into(),
-        }),
-        _ => Err(EventError::InvalidLogParams),
+    fn address(&self, name: &str) -> Result<[u8; 20], EventError> {
+        match self.get(name)? {
+            ethabi::Token::Address(address) => Ok((*address).into()),
+            _ => Err(EventError::InvalidLogParams),
+        }
     }
-}
 
-fn parse_execute_proposal_log(log: ethabi::Log) -> Result<EthereumEvent, EventError> {
-    match &log.params[..] {
-        [ethabi::LogParam {
-            value: ethabi::token::Token::String(title),
-            ..
-        }, ethabi::LogParam {
-            value: ethabi::token::Token::Array(extrinsics_tokens),
-            ..
-        }] => {
-            let extrinsics = extrinsics_tokens
-                .clone()
-                .into_iter()
-                .map(|extrinsic| match extrinsic {
-                    ethabi::token::Token::Bytes(extrinsic) => Ok(extrinsic),
+    fn string(&self, name: &str) -> Result<String, EventError> {
+        match self.get(name)? {
+            ethabi::Token::String(string) => Ok(string.clone()),
+            _ => Err(EventError::InvalidLogParams),
+        }
+    }
+
+    fn bytes(&self, name: &str) -> Result<Vec<u8>, EventError> {
+        match self.get(name)? {
+            ethabi::Token::Bytes(bytes) => Ok(bytes.clone()),
+            _ => Err(EventError::InvalidLogParams),
+        }
+    }
+
+    fn bytes_array(&self, name: &str) -> Result<Vec<Vec<u8>>, EventError> {
+        match self.get(name)? {
+            ethabi::Token::Array(tokens) => tokens
+                .iter()
+                .map(|token| match token {
+                    ethabi::Token::Bytes(bytes) => Ok(bytes.clone()),
                     _ => Err(EventError::InvalidLogParams),
                 })
-                .collect::<Result<Vec<Vec<u8>>, _>>()?;
-            Ok(EthereumEvent::ExecuteProposal {
-                title: title.into(),
-                extrinsics,
-            })
+                .collect(),
+            _ => Err(EventError::InvalidLogParams),
         }
-        _ => Err(EventError::InvalidLogParams),
     }
-}
 
-fn parse_notice_invoked_log(log: ethabi::Log) -> Result<EthereumEvent, EventError> {
-    match &log.params[..] {
-        [ethabi::LogParam {
-            value: ethabi::token::Token::Uint(era_id),
-            ..
-        }, ethabi::LogParam {
-            value: ethabi::token::Token::Uint(era_index),
-            ..
-        }, ethabi::LogParam {
-            value: ethabi::token::Token::FixedBytes(notice_hash),
-            ..
-        }, ethabi::LogParam {
-            value: ethabi::token::Token::Bytes(result),
-            ..
-        }] => Ok(EthereumEvent::NoticeInvoked {
-            era_id: (*era_id).try_into().map_err(|_| EventError::Overflow)?,
-            era_index: (*era_index).try_into().map_err(|_| EventError::Overflow)?,
-            notice_hash: notice_hash[..]
+    fn bytes32(&self, name: &str) -> Result<[u8; 32], EventError> {
+        match self.get(name)? {
+            ethabi::Token::FixedBytes(bytes) => bytes[..]
                 .try_into()
-                .map_err(|_| EventError::InvalidHash)?,
-            result: result.clone(), // TODO: Why the clones?
-        }),
-        _ => Err(EventError::InvalidLogParams),
+                .map_err(|_| EventError::InvalidLogParams),
+            _ => Err(EventError::InvalidLogParams),
+        }
+    }
+
+    fn uint<T: TryFrom<ethabi::Uint>>(&self, name: &str) -> Result<T, EventError> {
+        match self.get(name)? {
+            ethabi::Token::Uint(uint) => T::try_from(*uint).map_err(|_| EventError::Overflow),
+            _ => Err(EventError::InvalidLogParams),
+        }
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
-pub enum EventError {
-    UnknownEventTopic([u8; 32]),
-    ErrorParsingLog,
-    InvalidHex,
-    InvalidTopic,
-    Overflow,
-    InvalidHash,
-    InvalidLogParams,
-    InvalidRecipient,
+/// Map the params of a starport event onto the matching `EthereumEvent`.
+/// Starport events which the gateway doesn't act on map to nothing.
+fn decode_params(name: &str, params: LogParams) -> Result<Option<EthereumEvent>, EventError> {
+    let event = match name {
+        "Lock" => EthereumEvent::Lock {
+            asset: params.address("asset")?,
+            sender: params.address("sender")?,
+            chain: params.string("chain")?,
+            recipient: params.bytes32("recipient")?,
+            amount: params.uint("amount")?,
+        },
+        "LockCash" => EthereumEvent::LockCash {
+            sender: params.address("sender")?,
+            chain: params.string("chain")?,
+            recipient: params.bytes32("recipient")?,
+            amount: params.uint("amount")?,
+            principal: params.uint("principal")?,
+        },
+        "ExecTrxRequest" => EthereumEvent::ExecTrxRequest {
+            account: params.address("account")?,
+            trx_request: params.string("trxRequest")?,
+        },
+        "ExecuteProposal" => EthereumEvent::ExecuteProposal {
+            title: params.string("title")?,
+            extrinsics: params.bytes_array("extrinsics")?,
+        },
+        "NoticeInvoked" => EthereumEvent::NoticeInvoked {
+            era_id: params.uint("eraId")?,
+            era_index: params.uint("eraIndex")?,
+            notice_hash: params.bytes32("noticeHash")?,
+            result: params.bytes("result")?,
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
 }
 
-pub fn decode_event(topics: Vec<String>, data: String) -> Result<EthereumEvent, EventError> {
+/// Decode a starport log, using the definition of its event in the starport ABI.
+pub fn decode_event(
+    topics: Vec<String>,
+    data: String,
+) -> Result<Option<EthereumEvent>, EventError> {
     let topic_hashes = topics
         .iter()
         .map(|topic| decode_topic(topic).ok_or(EventError::InvalidTopic))
         .collect::<Result<Vec<ethabi::Hash>, _>>()?;
-    let topic_hash = topic_hashes.first().ok_or(EventError::InvalidTopic)?;
-    if *topic_hash == *LOCK_EVENT_TOPIC {
-        let log: ethabi::Log = LOCK_EVENT
-            .parse_log(ethabi::RawLog {
-                topics: topic_hashes,
-                data: decode_hex(&data).ok_or(EventError::InvalidHex)?,
-            })
-            .map_err(|_| EventError::ErrorParsingLog)?;
-
-        parse_lock_log(log)
-    } else if *topic_hash == *LOCK_CASH_EVENT_TOPIC {
-        let log: ethabi::Log = LOCK_CASH_EVENT
-            .parse_log(ethabi::RawLog {
-                topics: topic_hashes,
-                data: decode_hex(&data).ok_or(EventError::InvalidHex)?,
-            })
-            .map_err(|_| EventError::ErrorParsingLog)?;
-
-        parse_lock_cash_log(log)
-    } else if *topic_hash == *EXEC_TRX_REQUEST_EVENT_TOPIC {
-        let log: ethabi::Log = EXEC_TRX_REQUEST_EVENT
-            .parse_log(ethabi::RawLog {
-                topics: topic_hashes,
-                data: decode_hex(&data).ok_or(EventError::InvalidHex)?,
-            })
-            .map_err(|_| EventError::ErrorParsingLog)?;
-
-        parse_exec_trx_request_log(log)
-    } else if *topic_hash == *EXECUTE_PROPOSAL_EVENT_TOPIC {
-        let log: ethabi::Log = EXECUTE_PROPOSAL_EVENT
-            .parse_log(ethabi::RawLog {
-                topics: topic_hashes,
-                data: decode_hex(&data).ok_or(EventError::InvalidHex)?,
-            })
-            .map_err(|_| EventError::ErrorParsingLog)?;
-
-        parse_execute_proposal_log(log)
-    } else if *topic_hash == *NOTICE_INVOKED_EVENT_TOPIC {
-        let log: ethabi::Log = NOTICE_INVOKED_EVENT
-            .parse_log(ethabi::RawLog {
-                topics: topic_hashes,
-                data: decode_hex(&data).ok_or(EventError::InvalidHex)?,
-            })
-            .map_err(|_| EventError::ErrorParsingLog)?;
+    let topic_hash = *topic_hashes.first().ok_or(EventError::InvalidTopic)?;
+    let (_, event) = STARPORT_EVENTS
+        .iter()
+        .find(|(topic, _)| *topic == topic_hash)
+        .ok_or(EventError::UnknownEventTopic(*topic_hash.as_fixed_bytes()))?;
+    let log: ethabi::Log = event
+        .parse_log(ethabi::RawLog {
+            topics: topic_hashes,
+            data: decode_hex(&data).ok_or(EventError::InvalidHex)?,
+        })
+        .map_err(|_| EventError::ErrorParsingLog)?;
 
-        parse_notice_invoked_log(log)
-    } else {
-        Err(EventError::UnknownEventTopic(*topic_hash.as_fixed_bytes()))
-    }
+    decode_params(&event.name, LogParams(log.params))
 }
 
 #[cfg(test)]
@@ -381,7 +210,7 @@ mod tests {
             String::from("0x00000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000de0b6b3a764000000000000000000000000000000000000000000000000000000000000000000034554480000000000000000000000000000000000000000000000000000000000");
         assert_eq!(
             decode_event(topics, data),
-            Ok(EthereumEvent::Lock {
+            Ok(Some(EthereumEvent::Lock {
                 asset: [
                     9, 12, 3, 40, 98, 125, 92, 189, 126, 88, 76, 85, 134, 148, 48, 61, 139, 166,
                     162, 57
@@ -396,7 +225,7 @@ mod tests {
                     238, 225, 201, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
                 ],
                 amount: 1000000000000000000
-            })
+            }))
         )
     }
 
@@ -412,7 +241,7 @@ mod tests {
             String::from("0x000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000f424000000000000000000000000000000000000000000000000000000000000f424000000000000000000000000000000000000000000000000000000000000000034554480000000000000000000000000000000000000000000000000000000000");
         assert_eq!(
             decode_event(topics, data),
-            Ok(EthereumEvent::LockCash {
+            Ok(Some(EthereumEvent::LockCash {
                 sender: [
                     190, 151, 67, 84, 196, 13, 110, 88, 88, 4, 176, 238, 53, 82, 241, 142, 194,
                     238, 225, 201
@@ -424,7 +253,7 @@ mod tests {
                 ],
                 amount: 1000000,
                 principal: 1000000,
-            })
+            }))
         )
     }
 
@@ -438,7 +267,7 @@ mod tests {
             String::from("0x000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000412845787472616374203130302043415348204574683a3078643841314135393131363443463336453964464239463939363539323433323562374539466339612900000000000000000000000000000000000000000000000000000000000000");
         assert_eq!(
             decode_event(topics, data),
-            Ok(EthereumEvent::ExecTrxRequest {
+            Ok(Some(EthereumEvent::ExecTrxRequest {
                 account: [
                     216, 161, 165, 145, 22, 76, 243, 110, 157, 251, 159, 153, 101, 146, 67, 37,
                     183, 233, 252, 154
@@ -446,7 +275,7 @@ mod tests {
                 trx_request: String::from(
                     "(Extract 100 CASH Eth:0xd8A1A591164CF36E9dFB9F9965924325b7E9Fc9a)"
                 ),
-            })
+            }))
         )
     }
 
@@ -459,10 +288,10 @@ mod tests {
             String::from("0x0000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000094d7920416374696f6e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000003010203000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000030405060000000000000000000000000000000000000000000000000000000000");
         assert_eq!(
             decode_event(topics, data),
-            Ok(EthereumEvent::ExecuteProposal {
+            Ok(Some(EthereumEvent::ExecuteProposal {
                 title: String::from("My Action"),
                 extrinsics: vec![vec![1, 2, 3], vec![4, 5, 6]]
-            })
+            }))
         )
     }
 
@@ -478,7 +307,7 @@ mod tests {
             String::from("0x000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000001");
         assert_eq!(
             decode_event(topics, data),
-            Ok(EthereumEvent::NoticeInvoked {
+            Ok(Some(EthereumEvent::NoticeInvoked {
                 era_id: 0,
                 era_index: 3,
                 notice_hash: [
@@ -489,7 +318,88 @@ mod tests {
                     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                     0, 0, 0, 0, 0, 1
                 ],
-            })
+            }))
+        )
+    }
+
+    /// The starport events which decode into an `EthereumEvent`.
+    const DECODED_EVENTS: [&str; 5] = [
+        "Lock",
+        "LockCash",
+        "ExecTrxRequest",
+        "ExecuteProposal",
+        "NoticeInvoked",
+    ];
+
+    /// The starport event each variant is decoded from.
+    /// A new variant won't compile until it's named here and added to `DECODED_EVENTS`.
+    fn event_name(event: &EthereumEvent) -> Option<&'static str> {
+        match event {
+            EthereumEvent::Lock { .. } => Some("Lock"),
+            EthereumEvent::LockCash { .. } => Some("LockCash"),
+            EthereumEvent::ExecTrxRequest { .. } => Some("ExecTrxRequest"),
+            EthereumEvent::ExecuteProposal { .. } => Some("ExecuteProposal"),
+            EthereumEvent::NoticeInvoked { .. } => Some("NoticeInvoked"),
+            EthereumEvent::Unknown { .. } => None,
+        }
+    }
+
+    fn sample_token(kind: &ethabi::ParamType) -> ethabi::Token {
+        match kind {
+            ethabi::ParamType::Address => ethabi::Token::Address([1; 20].into()),
+            ethabi::ParamType::Bytes => ethabi::Token::Bytes(vec![2, 3]),
+            ethabi::ParamType::Int(_) => ethabi::Token::Int(ethabi::Int::from(4u64)),
+            ethabi::ParamType::Uint(_) => ethabi::Token::Uint(ethabi::Uint::from(5u64)),
+            ethabi::ParamType::Bool => ethabi::Token::Bool(true),
+            ethabi::ParamType::String => ethabi::Token::String(String::from("ETH")),
+            ethabi::ParamType::Array(inner) => ethabi::Token::Array(vec![sample_token(inner)]),
+            ethabi::ParamType::FixedBytes(len) => ethabi::Token::FixedBytes(vec![6; *len]),
+            ethabi::ParamType::FixedArray(inner, len) => {
+                ethabi::Token::FixedArray(vec![sample_token(inner); *len])
+            }
+            kind => panic!("No sample value for {:?}", kind),
+        }
+    }
+
+    /// Build the topics and data of a log for the event, the way the starport would emit it.
+    fn sample_log(event: &ethabi::Event) -> (Vec<String>, String) {
+        let mut topics = vec![event.signature()];
+        let mut values = vec![];
+        for input in event.inputs.iter() {
+            let token = sample_token(&input.kind);
+            if input.indexed {
+                topics.push(ethabi::Hash::from_slice(&ethabi::encode(&[token])));
+            } else {
+                values.push(token);
+            }
+        }
+        (
+            topics
+                .iter()
+                .map(|topic| format!("0x{}", hex::encode(topic.as_bytes())))
+                .collect(),
+            format!("0x{}", hex::encode(ethabi::encode(&values))),
         )
     }
+
+    #[test]
+    fn test_starport_abi_matches_ethereum_event() {
+        let mut decoded = vec![];
+        for (_, event) in STARPORT_EVENTS.iter() {
+            let (topics, data) = sample_log(event);
+            match decode_event(topics, data) {
+                Ok(Some(ethereum_event)) => {
+                    assert_eq!(event_name(&ethereum_event), Some(&event.name[..]));
+                    decoded.push(&event.name[..]);
+                }
+                Ok(None) => (),
+                Err(err) => panic!("Starport event {} doesn't decode: {:?}", event.name, err),
+            }
+        }
+        decoded.sort();
+
+        let mut expected = DECODED_EVENTS.to_vec();
+        expected.sort();
+        assert_eq!(decoded, expected);
+    }
 }
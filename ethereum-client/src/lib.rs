@@ -1,17 +1,22 @@
 #[macro_use]
 extern crate lazy_static;
 
+pub mod endpoints;
 pub mod events;
 pub mod hex;
 
-use crate::events::decode_event;
+pub use crate::endpoints::{
+    fetch_and_decode_logs_agreed, fetch_block_hash_agreed, with_failover, with_quorum, Endpoint,
+};
 pub use crate::events::EthereumEvent;
+use crate::events::{decode_event, EventError};
 use crate::hex::{parse_u64, parse_word};
 use codec::{Decode, Encode};
 use frame_support::debug;
 use our_std::RuntimeDebug;
 use serde::Deserialize;
-use sp_runtime::offchain::{http, Duration};
+use sp_runtime::offchain::{http, Duration, Timestamp};
+use sp_std::fmt;
 
 use types_derive::Types;
 
@@ -22,6 +27,13 @@ pub enum EthereumClientError {
     HttpErrorCode(u16),
     InvalidUTF8,
     JsonParseError,
+    TooManyResults,
+    NoEndpoints,
+    ProvidersDisagree,
+    MissingLogField,
+    /// The node refused the logs of this single block for returning too many results,
+    ///  so they can't be fetched from it in smaller windows either.
+    BlockTooManyResults(u64),
 }
 
 #[derive(Deserialize, RuntimeDebug, PartialEq)]
@@ -30,6 +42,38 @@ pub struct ResponseError {
     pub code: Option<i64>,
 }
 
+/// Messages nodes use to refuse an `eth_getLogs` call which matches too many logs,
+///  or which spans more blocks than they are willing to scan.
+const TOO_MANY_RESULTS_MESSAGES: [&str; 5] = [
+    "query returned more than",
+    "too many",
+    "response size",
+    "block range",
+    "limit exceeded",
+];
+
+/// The JSON-RPC error code nodes use when a request exceeds one of their limits.
+const LIMIT_EXCEEDED_CODE: i64 = -32005;
+
+impl ResponseError {
+    /// Whether the node refused the request for returning too many results,
+    ///  in which case the same query over fewer blocks may still succeed.
+    pub fn is_too_many_results(&self) -> bool {
+        if self.code == Some(LIMIT_EXCEEDED_CODE) {
+            return true;
+        }
+        match &self.message {
+            Some(message) => {
+                let message = message.to_lowercase();
+                TOO_MANY_RESULTS_MESSAGES
+                    .iter()
+                    .any(|pattern| message.contains(pattern))
+            }
+            None => false,
+        }
+    }
+}
+
 #[derive(Deserialize, RuntimeDebug, PartialEq)]
 pub struct EventsResponse<T> {
     pub id: Option<u64>,
@@ -67,18 +111,48 @@ pub struct LogObject {
     pub topics: Option<Vec<String>>,
 }
 
+#[derive(Deserialize, RuntimeDebug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockObject {
+    /// the block number. null when its pending block.
+    pub number: Option<String>,
+    /// 32 Bytes - hash of the block. null when its pending block.
+    pub hash: Option<String>,
+    /// 32 Bytes - hash of the parent block.
+    pub parent_hash: Option<String>,
+}
+
+#[derive(Deserialize, RuntimeDebug, PartialEq)]
+pub struct BlockObjectResponse {
+    pub id: Option<u64>,
+    pub result: Option<BlockObject>,
+    pub error: Option<ResponseError>,
+}
+
 fn deserialize_get_logs_response(
     response: &str,
 ) -> serde_json::error::Result<EventsResponse<LogObject>> {
     serde_json::from_str(response)
 }
 
+fn deserialize_get_logs_batch_response(
+    response: &str,
+) -> serde_json::error::Result<Vec<EventsResponse<LogObject>>> {
+    serde_json::from_str(response)
+}
+
 fn deserialize_get_block_number_response(
     response: &str,
 ) -> serde_json::error::Result<BlockResponse> {
     serde_json::from_str(response)
 }
 
+fn deserialize_get_block_by_number_response(
+    response: &str,
+) -> serde_json::error::Result<BlockObjectResponse> {
+    serde_json::from_str(response)
+}
+
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub struct EthereumLogEvent {
     pub block_hash: [u8; 32],
@@ -88,25 +162,192 @@ pub struct EthereumLogEvent {
     pub event: EthereumEvent,
 }
 
+/// Limits on how `eth_getLogs` calls page through a range of blocks.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub struct LogsPaging {
+    /// The most blocks a single call may span, this shrinks whenever a node refuses a call.
+    pub max_block_span: u64,
+    /// The most calls sent together in a single JSON-RPC batch request.
+    pub batch_size: usize,
+    /// The most batch requests which fetch logs before returning what was fetched so far.
+    pub max_batches: usize,
+}
+
+/// The logs fetched from a range of blocks, which may stop short of the end of the range.
+#[derive(RuntimeDebug, PartialEq)]
+pub struct LogsPage {
+    /// The last block whose logs have all been fetched.
+    pub to_block: u64,
+    pub logs: Vec<EthereumLogEvent>,
+}
+
+/// How long a request may take when no timeout is configured for its endpoint.
+pub const DEFAULT_HTTP_TIMEOUT_MILLIS: u64 = 2_000;
+
+/// How long to wait before retrying a failed request the first time, doubling with each retry after.
+pub const HTTP_RETRY_BACKOFF_BASE_MILLIS: u64 = 250;
+
+/// The longest a request may take over all its attempts and the waits between them,
+///  however many retries are configured, so it ends well before the OCW lock it's made under expires.
+pub const MAX_HTTP_REQUEST_MILLIS: u64 = 10_000;
+
+/// The options for HTTP requests to an endpoint.
+#[derive(Clone, Eq, PartialEq)]
+pub struct HttpOptions {
+    /// How long each attempt at a request may take.
+    pub timeout_ms: u64,
+    /// How many more times a request is attempted after failing in a way that may be transient.
+    pub retries: u32,
+    /// Headers sent with every request, such as credentials for the endpoint.
+    pub headers: Vec<(String, String)>,
+}
+
+impl HttpOptions {
+    pub fn new(
+        timeout_ms: Option<u64>,
+        retries: Option<u32>,
+        headers: Vec<(String, String)>,
+    ) -> HttpOptions {
+        HttpOptions {
+            timeout_ms: timeout_ms.unwrap_or(DEFAULT_HTTP_TIMEOUT_MILLIS),
+            retries: retries.unwrap_or(0),
+            headers,
+        }
+    }
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        HttpOptions::new(None, None, vec![])
+    }
+}
+
+/// Header values may be credentials, so only their names are ever logged.
+impl fmt::Debug for HttpOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let header_names: Vec<&str> = self.headers.iter().map(|(name, _)| &name[..]).collect();
+        f.debug_struct("HttpOptions")
+            .field("timeout_ms", &self.timeout_ms)
+            .field("retries", &self.retries)
+            .field("headers", &header_names)
+            .finish()
+    }
+}
+
+fn rpc_call(method: &str, params: Vec<&str>, id: u64) -> String {
+    format!(
+        r#"{{"jsonrpc":"2.0","method":"{}","params":[{}],"id":{}}}"#,
+        method,
+        params.join(","),
+        id
+    )
+}
+
 fn send_rpc(
-    server: &str,
+    server: &Endpoint,
     method: &'static str,
     params: Vec<&str>,
 ) -> Result<String, EthereumClientError> {
-    // TODO - move 2_000 to config???
-    let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(2_000));
-    let data = format!(
-        r#"{{"jsonrpc":"2.0","method":"{}","params":[{}],"id":1}}"#,
-        method,
-        params.join(",")
-    );
-    // debug::native::info!("Data for send_rpc: {}", data.clone());
+    send_request(server, rpc_call(method, params, 1))
+}
+
+/// Send several calls to the same method in a single JSON-RPC batch request.
+/// The calls are given ids counting up from 1, in the order of `batch_params`.
+fn send_rpc_batch(
+    server: &Endpoint,
+    method: &'static str,
+    batch_params: Vec<Vec<&str>>,
+) -> Result<String, EthereumClientError> {
+    let calls: Vec<String> = batch_params
+        .into_iter()
+        .zip(1..)
+        .map(|(params, id)| rpc_call(method, params, id))
+        .collect();
+    send_request(server, format!("[{}]", calls.join(",")))
+}
+
+fn send_request(server: &Endpoint, data: String) -> Result<String, EthereumClientError> {
+    let body = send_http(&server.url, Some(data.as_bytes()), &server.options)?;
+
+    // Create a str slice from the body.
+    let body_str = sp_std::str::from_utf8(&body).map_err(|_| {
+        debug::warn!("No UTF8 body");
+        EthereumClientError::InvalidUTF8
+    })?;
+
+    Ok(String::from(body_str))
+}
+
+/// Send an HTTP request to `url` with the options for its endpoint, POSTing `json_body` if given
+///  and otherwise making a GET request, and return the body of the response.
+///
+/// A request which times out, fails to connect, or is answered with a 429 or 5xx status,
+///  is attempted again up to `options.retries` more times, backing off between attempts.
+/// The request gives up once it has taken `MAX_HTTP_REQUEST_MILLIS`, even with retries left.
+pub fn send_http(
+    url: &str,
+    json_body: Option<&[u8]>,
+    options: &HttpOptions,
+) -> Result<Vec<u8>, EthereumClientError> {
+    let give_up_at =
+        sp_io::offchain::timestamp().add(Duration::from_millis(MAX_HTTP_REQUEST_MILLIS));
+    let mut attempts = 0;
+    loop {
+        match send_http_once(url, json_body, options, give_up_at) {
+            Err(err) if attempts < options.retries && is_transient(&err) => {
+                attempts += 1;
+                let backoff = retry_backoff_millis(attempts);
+                let retry_at = sp_io::offchain::timestamp().add(Duration::from_millis(backoff));
+                if retry_at >= give_up_at {
+                    debug::warn!(
+                        "HTTP request to {} failed ({:?}), out of time to retry",
+                        url,
+                        err
+                    );
+                    return Err(err);
+                }
+                debug::warn!(
+                    "HTTP request to {} failed ({:?}), retrying in {}ms ({}/{})",
+                    url,
+                    err,
+                    backoff,
+                    attempts,
+                    options.retries
+                );
+                sp_io::offchain::sleep_until(retry_at);
+            }
+            result => return result,
+        }
+    }
+}
 
-    let request = http::Request::post(server, vec![data]);
+/// How long to wait before the given retry of a request, counting from 1.
+fn retry_backoff_millis(retry: u32) -> u64 {
+    HTTP_RETRY_BACKOFF_BASE_MILLIS.saturating_mul(2u64.saturating_pow(retry.saturating_sub(1)))
+}
+
+fn send_http_once(
+    url: &str,
+    json_body: Option<&[u8]>,
+    options: &HttpOptions,
+    give_up_at: Timestamp,
+) -> Result<Vec<u8>, EthereumClientError> {
+    let deadline = sp_io::offchain::timestamp()
+        .add(Duration::from_millis(options.timeout_ms))
+        .min(give_up_at);
+
+    let mut request = match json_body {
+        Some(body) => {
+            http::Request::post(url, vec![body]).add_header("Content-Type", "application/json")
+        }
+        None => http::Request::get(url),
+    };
+    for (name, value) in &options.headers {
+        request = request.add_header(name, value);
+    }
 
     let pending = request
         .deadline(deadline)
-        .add_header("Content-Type", "application/json")
         .send()
         .map_err(|_| EthereumClientError::HttpIoError)?;
 
@@ -120,74 +361,239 @@ fn send_rpc(
         return Err(EthereumClientError::HttpErrorCode(response.code));
     }
 
-    let body = response.body().collect::<Vec<u8>>();
-
-    // Create a str slice from the body.
-    let body_str = sp_std::str::from_utf8(&body).map_err(|_| {
-        debug::warn!("No UTF8 body");
-        EthereumClientError::InvalidUTF8
-    })?;
+    Ok(response.body().collect::<Vec<u8>>())
+}
 
-    Ok(String::from(body_str))
+/// Whether a failed request may succeed if it is simply attempted again.
+fn is_transient(err: &EthereumClientError) -> bool {
+    match err {
+        EthereumClientError::HttpIoError | EthereumClientError::HttpTimeout => true,
+        EthereumClientError::HttpErrorCode(code) => *code == 429 || *code >= 500,
+        _ => false,
+    }
 }
 
 pub fn fetch_and_decode_logs(
-    server: &str,
+    server: &Endpoint,
     params: Vec<&str>,
 ) -> Result<Vec<EthereumLogEvent>, EthereumClientError> {
     let body_str: String = send_rpc(server, "eth_getLogs", params)?;
     let deserialized_body = deserialize_get_logs_response(&body_str)
         .map_err(|_| EthereumClientError::JsonParseError)?;
-    let eth_logs = deserialized_body
-        .result
-        .ok_or(EthereumClientError::JsonParseError)?;
+    let eth_logs = logs_result(deserialized_body)?;
 
     if eth_logs.len() > 0 {
         debug::native::info!("Eth Starport found {} logs", eth_logs.len());
     }
 
-    Ok(eth_logs
-        .into_iter()
-        .filter_map(|eth_log| {
-            match (
-                parse_word(eth_log.block_hash),
-                parse_u64(eth_log.transaction_index),
-                eth_log.data,
-                eth_log.topics,
-                parse_u64(eth_log.block_number),
-                parse_u64(eth_log.log_index),
-            ) {
-                (
-                    Some(block_hash),
-                    Some(transaction_index),
-                    Some(data),
-                    Some(topics),
-                    Some(block_number),
-                    Some(log_index),
-                ) => match decode_event(topics, data) {
-                    Ok(event) => Some(EthereumLogEvent {
-                        block_hash,
-                        block_number,
-                        transaction_index,
-                        log_index,
-                        event,
-                    }),
-                    Err(err) => {
-                        println!("Failed to parse log {:?}", err);
-                        None
-                    }
-                },
-                _ => {
-                    println!("Missing critical field from eth log event");
+    decode_logs(eth_logs)
+}
 
-                    None
+/// Fetch and decode the logs of a contract from `from_block` up to `to_block`, paging through
+///  the range with calls spanning at most `paging.max_block_span` blocks.
+///
+/// Calls are sent `paging.batch_size` at a time in a batch request, and the blocks of a call
+///  the node refuses for returning too many results are fetched again in smaller windows.
+///
+/// If the node refuses the logs of a single block, the logs before it are returned on their own,
+///  or if there are none it fails with `BlockTooManyResults`, so another node may be tried.
+pub fn fetch_and_decode_logs_paged(
+    server: &Endpoint,
+    address: &str,
+    from_block: u64,
+    to_block: u64,
+    paging: LogsPaging,
+) -> Result<LogsPage, EthereumClientError> {
+    let mut span = paging.max_block_span.max(1);
+    let mut next_block = from_block;
+    let mut batches = 0;
+    let mut logs = vec![];
+
+    while next_block <= to_block && batches < paging.max_batches.max(1) {
+        let windows = log_windows(next_block, to_block, span, paging.batch_size.max(1));
+        let responses = fetch_logs_batch(server, address, &windows)?;
+        let mut progressed = false;
+        for (&(start, end), response) in windows.iter().zip(responses) {
+            match response {
+                Ok(eth_logs) => {
+                    logs.extend(decode_logs(eth_logs)?);
+                    next_block = end + 1;
+                    progressed = true;
+                }
+                Err(EthereumClientError::TooManyResults) if end > start => {
+                    span = (end - start + 1) / 2;
+                    debug::native::info!(
+                        "Too many logs from {:#X} to {:#X}, shrinking span to {}",
+                        start,
+                        end,
+                        span
+                    );
+                    break;
                 }
+                Err(EthereumClientError::TooManyResults) => {
+                    debug::warn!(
+                        "RPC endpoint {} refuses the logs of block {:#X} for returning too many results",
+                        server.url,
+                        start
+                    );
+                    if next_block == from_block {
+                        return Err(EthereumClientError::BlockTooManyResults(start));
+                    }
+                    return Ok(LogsPage {
+                        to_block: next_block - 1,
+                        logs,
+                    });
+                }
+                Err(err) => return Err(err),
             }
+        }
+        if progressed {
+            batches += 1;
+        }
+    }
+
+    if logs.len() > 0 {
+        debug::native::info!("Eth Starport found {} logs", logs.len());
+    }
+
+    Ok(LogsPage {
+        to_block: next_block.saturating_sub(1),
+        logs,
+    })
+}
+
+/// Split the blocks from `from_block` up to `to_block` into at most `count` windows of `span` blocks.
+fn log_windows(from_block: u64, to_block: u64, span: u64, count: usize) -> Vec<(u64, u64)> {
+    let mut windows = vec![];
+    let mut start = from_block;
+    while start <= to_block && windows.len() < count {
+        let end = start.saturating_add(span - 1).min(to_block);
+        windows.push((start, end));
+        if end == to_block {
+            break;
+        }
+        start = end + 1;
+    }
+    windows
+}
+
+/// Fetch the logs of a contract for each window of blocks, in a single request.
+/// A lone window is sent as a plain call, since not every node accepts batch requests.
+fn fetch_logs_batch(
+    server: &Endpoint,
+    address: &str,
+    windows: &[(u64, u64)],
+) -> Result<Vec<Result<Vec<LogObject>, EthereumClientError>>, EthereumClientError> {
+    let params: Vec<String> = windows
+        .iter()
+        .map(|(start, end)| {
+            format!(
+                r#"{{"address": "{}", "fromBlock": "{:#X}", "toBlock": "{:#X}"}}"#,
+                address, start, end
+            )
+        })
+        .collect();
+
+    if let [param] = &params[..] {
+        let body_str: String = send_rpc(server, "eth_getLogs", vec![&param[..]])?;
+        let deserialized_body = deserialize_get_logs_response(&body_str)
+            .map_err(|_| EthereumClientError::JsonParseError)?;
+        return Ok(vec![logs_result(deserialized_body)]);
+    }
+
+    let body_str: String = send_rpc_batch(
+        server,
+        "eth_getLogs",
+        params.iter().map(|param| vec![&param[..]]).collect(),
+    )?;
+    let mut deserialized_body = deserialize_get_logs_batch_response(&body_str)
+        .map_err(|_| EthereumClientError::JsonParseError)?;
+
+    // Responses to a batch may come back in any order, so match them up by id
+    (1..=windows.len() as u64)
+        .map(|id| {
+            let index = deserialized_body
+                .iter()
+                .position(|response| response.id == Some(id))
+                .ok_or(EthereumClientError::JsonParseError)?;
+            Ok(logs_result(deserialized_body.swap_remove(index)))
         })
-        .collect())
+        .collect()
+}
+
+fn logs_result(response: EventsResponse<LogObject>) -> Result<Vec<LogObject>, EthereumClientError> {
+    match response {
+        EventsResponse {
+            result: Some(eth_logs),
+            ..
+        } => Ok(eth_logs),
+        EventsResponse {
+            error: Some(error), ..
+        } if error.is_too_many_results() => Err(EthereumClientError::TooManyResults),
+        _ => Err(EthereumClientError::JsonParseError),
+    }
+}
+
+/// Decode logs into events, keeping any log which can't be decoded as an unknown event,
+///  and skipping the logs of starport events which the gateway doesn't act on.
+///
+/// A log missing the fields which identify it can't be kept at all, so fetching fails instead,
+///  rather than skipping past the log and losing it.
+fn decode_logs(eth_logs: Vec<LogObject>) -> Result<Vec<EthereumLogEvent>, EthereumClientError> {
+    let logs = eth_logs
+        .into_iter()
+        .map(decode_log)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(logs.into_iter().flatten().collect())
 }
 
-pub fn fetch_latest_block(server: &str) -> Result<u64, EthereumClientError> {
+fn decode_log(eth_log: LogObject) -> Result<Option<EthereumLogEvent>, EthereumClientError> {
+    match (
+        parse_word(eth_log.block_hash),
+        parse_u64(eth_log.transaction_index),
+        parse_u64(eth_log.block_number),
+        parse_u64(eth_log.log_index),
+    ) {
+        (Some(block_hash), Some(transaction_index), Some(block_number), Some(log_index)) => {
+            let event = match (eth_log.topics, eth_log.data) {
+                (Some(topics), Some(data)) => decode_event(topics.clone(), data.clone())
+                    .unwrap_or_else(|error| {
+                        Some(EthereumEvent::Unknown {
+                            topics,
+                            data,
+                            error,
+                        })
+                    }),
+                (topics, data) => Some(EthereumEvent::Unknown {
+                    topics: topics.unwrap_or_default(),
+                    data: data.unwrap_or_default(),
+                    error: EventError::MissingTopicsOrData,
+                }),
+            };
+            if let Some(EthereumEvent::Unknown { error, .. }) = &event {
+                debug::warn!(
+                    "Failed to decode log {} in block {}: {:?}",
+                    log_index,
+                    block_number,
+                    error
+                );
+            }
+            Ok(event.map(|event| EthereumLogEvent {
+                block_hash,
+                block_number,
+                transaction_index,
+                log_index,
+                event,
+            }))
+        }
+        _ => {
+            debug::warn!("Missing critical field from eth log event");
+            Err(EthereumClientError::MissingLogField)
+        }
+    }
+}
+
+pub fn fetch_latest_block(server: &Endpoint) -> Result<u64, EthereumClientError> {
     let body_str: String = send_rpc(server, "eth_blockNumber", vec![])?;
     let deserialized_body = deserialize_get_block_number_response(&body_str)
         .map_err(|_| EthereumClientError::JsonParseError)?;
@@ -200,6 +606,28 @@ pub fn fetch_latest_block(server: &str) -> Result<u64, EthereumClientError> {
     .ok_or(EthereumClientError::JsonParseError)
 }
 
+pub fn fetch_block_hash(
+    server: &Endpoint,
+    block_number: u64,
+) -> Result<[u8; 32], EthereumClientError> {
+    let block_number_param = format!(r#""{:#X}""#, block_number);
+    let body_str: String = send_rpc(
+        server,
+        "eth_getBlockByNumber",
+        vec![&block_number_param, "false"],
+    )?;
+    let deserialized_body = deserialize_get_block_by_number_response(&body_str)
+        .map_err(|_| EthereumClientError::JsonParseError)?;
+
+    parse_word(
+        deserialized_body
+            .result
+            .ok_or(EthereumClientError::JsonParseError)?
+            .hash,
+    )
+    .ok_or(EthereumClientError::JsonParseError)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
@@ -293,6 +721,34 @@ mod tests {
         assert_eq!(result.unwrap(), expected);
     }
 
+    #[test]
+    fn test_deserialize_get_block_by_number_happy_path() {
+        const RESPONSE: &str = r#"{
+      "jsonrpc": "2.0",
+      "id": 1,
+      "result": {
+        "number": "0xb27449",
+        "hash": "0x5d4b4e3bb2d5b0c0ef1e1a2a3a0bdbd6db8d1f5a3a3c2d6e1d7a8c0c9e3f2b1a",
+        "parentHash": "0x0f6a1d7b9e0c3a5c2f8e4d1b7a9c6e3f0d2b4a8c1e5f7a9b3d6c0e2f4a8b1c5d"
+      }
+    }"#;
+        let result = deserialize_get_block_by_number_response(RESPONSE);
+        let expected = BlockObjectResponse {
+            id: Some(1),
+            result: Some(BlockObject {
+                number: Some(String::from("0xb27449")),
+                hash: Some(String::from(
+                    "0x5d4b4e3bb2d5b0c0ef1e1a2a3a0bdbd6db8d1f5a3a3c2d6e1d7a8c0c9e3f2b1a",
+                )),
+                parent_hash: Some(String::from(
+                    "0x0f6a1d7b9e0c3a5c2f8e4d1b7a9c6e3f0d2b4a8c1e5f7a9b3d6c0e2f4a8b1c5d",
+                )),
+            }),
+            error: None,
+        };
+        assert_eq!(result.unwrap(), expected);
+    }
+
     #[test]
     fn test_deserialize_get_logs_request_totally_unexpected_input() {
         const RESPONSE: &str = r#"{"USD": 2}"#;
@@ -304,4 +760,197 @@ mod tests {
         };
         assert_eq!(result.unwrap(), expected);
     }
+
+    fn log_object(topics: Option<Vec<String>>, data: Option<String>) -> LogObject {
+        LogObject {
+            removed: Some(false),
+            log_index: Some(String::from("0x1d")),
+            transaction_index: Some(String::from("0x1d")),
+            transaction_hash: None,
+            block_hash: Some(String::from(
+                "0x7c5a35e9cb3e8ae0e221ab470abae9d446c3a5626ce6689fc777dcffcab52c70",
+            )),
+            block_number: Some(String::from("0x5c29fb")),
+            address: None,
+            data,
+            topics,
+        }
+    }
+
+    #[test]
+    fn test_decode_log_keeps_undecodable_logs() {
+        let topic = "0x241ea03ca20251805084d27d4440371c34a0b85ff108f6bb5611248f73818b80";
+        let log = decode_log(log_object(
+            Some(vec![String::from(topic)]),
+            Some(String::from("0x01")),
+        ))
+        .unwrap()
+        .unwrap();
+        assert_eq!(log.block_number, 0x5c29fb);
+        assert_eq!(log.log_index, 0x1d);
+        assert_eq!(
+            log.event,
+            EthereumEvent::Unknown {
+                topics: vec![String::from(topic)],
+                data: String::from("0x01"),
+                error: EventError::UnknownEventTopic(
+                    crate::hex::parse_word(Some(String::from(topic))).unwrap()
+                ),
+            }
+        );
+
+        assert_eq!(
+            decode_log(log_object(None, Some(String::from("0x01"))))
+                .unwrap()
+                .unwrap()
+                .event,
+            EthereumEvent::Unknown {
+                topics: vec![],
+                data: String::from("0x01"),
+                error: EventError::MissingTopicsOrData,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_log_skips_ignored_starport_events() {
+        // NoticeReplay(bytes32), which the gateway doesn't act on
+        let log = log_object(
+            Some(vec![
+                String::from("0xaa1bfc5b12d79088f08d530fb8f8acb278a400400e829512a39851b5bb999a2c"),
+                String::from("0x0101010101010101010101010101010101010101010101010101010101010101"),
+            ]),
+            Some(String::from("0x")),
+        );
+        assert_eq!(decode_log(log), Ok(None));
+    }
+
+    #[test]
+    fn test_decode_log_missing_critical_field() {
+        let mut log = log_object(Some(vec![]), Some(String::from("0x")));
+        log.block_number = None;
+        assert_eq!(decode_log(log), Err(EthereumClientError::MissingLogField));
+    }
+
+    #[test]
+    fn test_deserialize_get_logs_batch_response() {
+        const RESPONSE: &str = r#"[
+      {
+        "jsonrpc": "2.0",
+        "id": 2,
+        "error": {
+          "code": -32005,
+          "message": "query returned more than 10000 results"
+        }
+      },
+      {
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": []
+      }
+    ]"#;
+        let mut result = deserialize_get_logs_batch_response(RESPONSE).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].id, Some(2));
+        assert_eq!(
+            logs_result(result.remove(0)),
+            Err(EthereumClientError::TooManyResults)
+        );
+        assert_eq!(result[0].id, Some(1));
+        assert_eq!(logs_result(result.remove(0)), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_is_too_many_results() {
+        let error = |code: Option<i64>, message: &str| ResponseError {
+            message: Some(String::from(message)),
+            code,
+        };
+        assert!(error(Some(-32005), "limit exceeded").is_too_many_results());
+        assert!(error(Some(-32000), "Log response size exceeded.").is_too_many_results());
+        assert!(error(
+            Some(-32602),
+            "eth_getLogs is limited to a 10,000 block range"
+        )
+        .is_too_many_results());
+        assert!(error(None, "Query returned more than 10000 results").is_too_many_results());
+        assert!(
+            !error(Some(-32602), "invalid argument 0: hex string has length 3")
+                .is_too_many_results()
+        );
+        assert!(!ResponseError {
+            message: None,
+            code: Some(-32000)
+        }
+        .is_too_many_results());
+    }
+
+    #[test]
+    fn test_logs_result_other_error() {
+        let response = deserialize_get_logs_response(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"method not found"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            logs_result(response),
+            Err(EthereumClientError::JsonParseError)
+        );
+    }
+
+    #[test]
+    fn test_log_windows() {
+        assert_eq!(log_windows(0, 9, 10, 3), vec![(0, 9)]);
+        assert_eq!(log_windows(0, 24, 10, 3), vec![(0, 9), (10, 19), (20, 24)]);
+        assert_eq!(log_windows(5, 100, 10, 2), vec![(5, 14), (15, 24)]);
+        assert_eq!(log_windows(7, 7, 1, 4), vec![(7, 7)]);
+        assert_eq!(log_windows(8, 7, 10, 4), vec![]);
+        assert_eq!(
+            log_windows(u64::MAX - 1, u64::MAX, 10, 4),
+            vec![(u64::MAX - 1, u64::MAX)]
+        );
+    }
+
+    #[test]
+    fn test_rpc_call() {
+        assert_eq!(
+            rpc_call("eth_getBlockByNumber", vec![r#""0x1""#, "false"], 3),
+            r#"{"jsonrpc":"2.0","method":"eth_getBlockByNumber","params":["0x1",false],"id":3}"#
+        );
+    }
+
+    #[test]
+    fn test_http_options_debug_hides_header_values() {
+        let options = HttpOptions::new(
+            Some(5_000),
+            None,
+            vec![(String::from("Authorization"), String::from("Bearer secret"))],
+        );
+        assert_eq!(options.retries, 0);
+        assert_eq!(
+            format!("{:?}", options),
+            r#"HttpOptions { timeout_ms: 5000, retries: 0, headers: ["Authorization"] }"#
+        );
+        assert_eq!(
+            HttpOptions::default().timeout_ms,
+            DEFAULT_HTTP_TIMEOUT_MILLIS
+        );
+    }
+
+    #[test]
+    fn test_is_transient() {
+        assert!(is_transient(&EthereumClientError::HttpTimeout));
+        assert!(is_transient(&EthereumClientError::HttpIoError));
+        assert!(is_transient(&EthereumClientError::HttpErrorCode(429)));
+        assert!(is_transient(&EthereumClientError::HttpErrorCode(503)));
+        assert!(!is_transient(&EthereumClientError::HttpErrorCode(401)));
+        assert!(!is_transient(&EthereumClientError::JsonParseError));
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        assert_eq!(retry_backoff_millis(1), HTTP_RETRY_BACKOFF_BASE_MILLIS);
+        assert_eq!(retry_backoff_millis(2), 2 * HTTP_RETRY_BACKOFF_BASE_MILLIS);
+        assert_eq!(retry_backoff_millis(3), 4 * HTTP_RETRY_BACKOFF_BASE_MILLIS);
+        assert!(retry_backoff_millis(u32::MAX) > MAX_HTTP_REQUEST_MILLIS);
+    }
 }
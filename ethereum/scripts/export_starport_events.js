@@ -0,0 +1,37 @@
+// Exports the events of the compiled Starport ABI to `.build/Starport-events.json`,
+//  which the gateway's ethereum client generates its event definitions from.
+// Run after `yarn compile`, and commit the result whenever the Starport's events change.
+const fs = require('fs');
+const path = require('path');
+
+const buildFile = process.env['BUILD_FILE'] || path.join(__dirname, '..', '.build', 'contracts.json');
+const eventsFile = path.join(__dirname, '..', '.build', 'Starport-events.json');
+
+function readStarportABI() {
+  let contracts;
+  try {
+    contracts = JSON.parse(fs.readFileSync(buildFile, 'utf8')).contracts;
+  } catch (e) {
+    throw new Error(`Compiled contracts missing-- please run \`yarn compile\` first. ${e.toString()}`);
+  }
+  let contractObj = Object.entries(contracts).find(([name, contract]) => name.split(':')[1] === 'Starport');
+  if (!contractObj) {
+    throw new Error(`Could not find contract: Starport`);
+  }
+  let [_, contract] = contractObj;
+  return typeof (contract.abi) === 'string' ? JSON.parse(contract.abi) : contract.abi;
+}
+
+// Only the fields the compiler emits for events, in a fixed order, so the output is stable
+let events = readStarportABI()
+  .filter((item) => item.type === 'event')
+  .map(({ anonymous, inputs, name, type }) => ({
+    anonymous,
+    inputs: inputs.map(({ indexed, internalType, name, type }) => ({ indexed, internalType, name, type })),
+    name,
+    type
+  }))
+  .sort((a, b) => a.name.localeCompare(b.name));
+
+fs.writeFileSync(eventsFile, JSON.stringify(events, null, 2) + '\n');
+console.log(`Wrote ${events.length} Starport events to ${eventsFile}`);
@@ -81,7 +81,12 @@ buildScenarios('Gov Scenarios', gov_scen_info, [
     name: "Remove Auth",
     scenario: async ({ ctx, chain, starport, validators }) => {
       const alice = validators.validatorInfoMap.alice;
-      const newAuthsRaw = [{ substrate_id: ctx.actors.keyring.decodeAddress(alice.aura_key), eth_address: alice.eth_account }];
+      const newAuthsRaw = [{
+        substrate_id: ctx.actors.keyring.decodeAddress(alice.aura_key),
+        eth_address: alice.eth_account,
+        dot_id: ctx.actors.keyring.decodeAddress(alice.aura_key),
+        sol_id: ctx.actors.keyring.decodeAddress(alice.grandpa_key)
+      }];
 
       let extrinsic = ctx.api().tx.cash.changeValidators(newAuthsRaw);
 
@@ -127,11 +132,18 @@ buildScenarios('Gov Scenarios', gov_scen_info, [
       await chain.setKeys(charlie, newValidatorKeys);
 
       const { alice, bob } = validators.validatorInfoMap;
-      const toValKeys = (substrateId, ethAccount) => {return  {substrate_id: keyring.decodeAddress(substrateId), eth_address: ethAccount} };
+      const toValKeys = (substrateId, ethAccount, auraKey, grandpaKey) => {
+        return {
+          substrate_id: keyring.decodeAddress(substrateId),
+          eth_address: ethAccount,
+          dot_id: keyring.decodeAddress(auraKey),
+          sol_id: keyring.decodeAddress(grandpaKey)
+        };
+      };
       const allAuthsRaw = [
-        toValKeys(alice.aura_key, alice.eth_account),
-        toValKeys(bob.aura_key, bob.eth_account),
-        toValKeys(charlieGatewayId, eth_account),
+        toValKeys(alice.aura_key, alice.eth_account, alice.aura_key, alice.grandpa_key),
+        toValKeys(bob.aura_key, bob.eth_account, bob.aura_key, bob.grandpa_key),
+        toValKeys(charlieGatewayId, eth_account, newValidatorKeys.aura, newValidatorKeys.grandpa),
       ];
 
       const extrinsic = ctx.api().tx.cash.changeValidators(allAuthsRaw);
@@ -173,7 +185,14 @@ buildScenarios('Gov Scenarios', gov_scen_info, [
       const charlieGatewayId = charlie.address;
 
       const { alice, bob } = validators.validatorInfoMap;
-      const toValKeys = (substrateId, ethAccount) => {return  {substrate_id: keyring.decodeAddress(substrateId), eth_address: ethAccount} };
+      const toValKeys = (substrateId, ethAccount) => {
+        return {
+          substrate_id: keyring.decodeAddress(substrateId),
+          eth_address: ethAccount,
+          dot_id: keyring.decodeAddress(substrateId),
+          sol_id: keyring.decodeAddress(substrateId)
+        };
+      };
       const allAuthsRaw = [
         toValKeys(alice.aura_key, alice.eth_account),
         toValKeys(bob.aura_key, bob.eth_account),
@@ -151,17 +151,18 @@ class Actor {
   async liquidityForToken(token) {
     let assetBalance = await this.ctx.api().query.cash.assetBalances(token.toChainAsset(), this.toChainAccount());
     let price = await token.getPrice();
-    let liquidityFactor = await token.getLiquidityFactor();
-    console.log({token: token.symbol, assetBalance, price, liquidityFactor});
+    let collateralFactor = await token.getCollateralFactor();
+    let borrowFactor = await token.getBorrowFactor();
+    console.log({token: token.symbol, assetBalance, price, collateralFactor, borrowFactor});
 
     if (assetBalance == 0) {
       return 0;
     } else if (assetBalance > 0) {
-      // AssetBalance • LiquidityFactor_Asset • Price_Asset
-      return token.toTokenAmount(assetBalance.toBigInt()) * price * liquidityFactor;
+      // AssetBalance • CollateralFactor_Asset • Price_Asset
+      return token.toTokenAmount(assetBalance.toBigInt()) * price * collateralFactor;
     } else {
-      // AssetBalance ÷ LiquidityFactor_Asset • Price_Asset
-      return token.toTokenAmount(assetBalance.toBigInt()) * price / liquidityFactor;
+      // AssetBalance ÷ BorrowFactor_Asset • Price_Asset
+      return token.toTokenAmount(assetBalance.toBigInt()) * price / borrowFactor;
     }
   }
 
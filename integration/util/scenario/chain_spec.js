@@ -31,7 +31,9 @@ async function baseChainSpec(validatorsInfoHash, tokensInfoHash, ctx) {
   let session_args = validatorsInfo.map(([_, v]) => [v.aura_key, v.aura_key, {aura: v.aura_key, grandpa: v.grandpa_key}]);
   let validators = validatorsInfo.filter(([_, v]) => v.validator).map(([_, v]) => ({
       substrate_id: Array.from(ctx.actors.keyring.decodeAddress(v.aura_key)), // from ss58 str => byte array
-      eth_address: v.eth_account
+      eth_address: v.eth_account,
+      dot_id: Array.from(ctx.actors.keyring.decodeAddress(v.aura_key)),
+      sol_id: Array.from(ctx.actors.keyring.decodeAddress(v.grandpa_key))
   }));
 
   let assets = tokens.all().map((token) => ({
@@ -39,7 +41,8 @@ async function baseChainSpec(validatorsInfoHash, tokensInfoHash, ctx) {
     decimals: token.decimals,
     symbol: token.symbol.toUpperCase(),
     ticker: token.priceTicker,
-    liquidity_factor: Math.floor(token.liquidityFactor * 1e18),
+    collateral_factor: Math.floor(token.liquidityFactor * 1e18),
+    borrow_factor: Math.floor(token.liquidityFactor * 1e18),
     rate_model: {
       Kink: {
         zero_rate: 0,
@@ -49,7 +52,8 @@ async function baseChainSpec(validatorsInfoHash, tokensInfoHash, ctx) {
       }
     },
     miner_shares: 1000,
-    supply_cap: 0
+    supply_cap: 0,
+    borrow_cap: 0
   }));
 
   let initialYieldConfig = {};
@@ -90,7 +94,8 @@ async function baseChainSpec(validatorsInfoHash, tokensInfoHash, ctx) {
           keys: session_args
         },
         palletOracle: {
-          reporters
+          reporters,
+          minReporters: 1
         }
       }
     }
@@ -142,9 +142,14 @@ class Token {
     }
   }
 
-  async getLiquidityFactor() {
-    let liquidityFactor = await this.getAssetInfo('liquidity_factor');
-    return descale(liquidityFactor, 18);
+  async getCollateralFactor() {
+    let collateralFactor = await this.getAssetInfo('collateral_factor');
+    return descale(collateralFactor, 18);
+  }
+
+  async getBorrowFactor() {
+    let borrowFactor = await this.getAssetInfo('borrow_factor');
+    return descale(borrowFactor, 18);
   }
 
   async totalChainSupply() {
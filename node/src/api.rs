@@ -37,9 +37,11 @@ pub struct ApiAssetData {
     balance: String,
     total_supply: String,
     total_borrow: String,
+    borrow_cap: String,
     supply_rate: String,
     borrow_rate: String,
-    liquidity_factor: String,
+    collateral_factor: String,
+    borrow_factor: String,
     price: String,
 }
 
@@ -153,9 +155,11 @@ where
             balance: format!("{}", account_balance),
             total_supply: format!("{}", total_supply),
             total_borrow: format!("{}", total_borrow),
+            borrow_cap: format!("{}", asset_info.borrow_cap),
             supply_rate: format!("{}", supply_rate.0),
             borrow_rate: format!("{}", borrow_rate.0),
-            liquidity_factor: format!("{}", asset_info.liquidity_factor.0),
+            collateral_factor: format!("{}", asset_info.collateral_factor.0),
+            borrow_factor: format!("{}", asset_info.borrow_factor.0),
             price: format!("{}", price),
         })
     }
@@ -4,13 +4,13 @@ use gateway_runtime::{
 };
 use our_std::{convert::TryInto, str::FromStr};
 use pallet_cash::{
-    chains::{Chain, Ethereum},
+    chains::{Chain, ChainId, Ethereum, EVM_CHAINS},
     types::{AssetInfo, Timestamp, ValidatorKeys, APR},
 };
 
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{ed25519, sr25519, Pair, Public};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::traits::{IdentifyAccount, Verify};
 
@@ -55,7 +55,8 @@ pub fn authority_keys_from_seed(
 /// Get the properties key of the chain spec file - a basic valid configuration
 fn get_properties() -> sc_service::Properties {
     let value = serde_json::json! ({
-        "eth_starport_address" : ""
+        "eth_starport_address" : "",
+        "matic_starport_address" : ""
     });
     let as_object = value.as_object();
     let unwrapped = as_object.unwrap();
@@ -77,7 +78,8 @@ fn development_genesis() -> GenesisConfig {
         // Initial assets
         vec![
             AssetInfo {
-                liquidity_factor: FromStr::from_str("6789").unwrap(),
+                collateral_factor: FromStr::from_str("6789").unwrap(),
+                borrow_factor: FromStr::from_str("6789").unwrap(),
                 ..AssetInfo::minimal(
                     FromStr::from_str("eth:0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE").unwrap(),
                     FromStr::from_str("ETH/18").unwrap(),
@@ -85,7 +87,8 @@ fn development_genesis() -> GenesisConfig {
             },
             AssetInfo {
                 ticker: FromStr::from_str("USD").unwrap(),
-                liquidity_factor: FromStr::from_str("6789").unwrap(),
+                collateral_factor: FromStr::from_str("6789").unwrap(),
+                borrow_factor: FromStr::from_str("6789").unwrap(),
                 ..AssetInfo::minimal(
                     FromStr::from_str("eth:0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap(),
                     FromStr::from_str("USDC/6").unwrap(),
@@ -206,12 +209,15 @@ fn testnet_genesis(
                 .map(|v| ValidatorKeys {
                     substrate_id: v.0.clone(),
                     eth_address: v.1,
+                    dot_id: sr25519::Public::from(v.2.clone()).0,
+                    sol_id: ed25519::Public::from(v.3.clone()).0,
                 })
                 .collect::<Vec<_>>(),
         }),
 
         pallet_oracle: Some(OracleConfig {
             reporters: reporters.try_into().unwrap(),
+            min_reporters: 1,
         }),
     }
 }
@@ -221,14 +227,30 @@ fn testnet_genesis(
 pub fn extract_configuration_from_properties(
     properties: &sp_chain_spec::Properties,
 ) -> Option<runtime_interfaces::Config> {
-    let key_address = "eth_starport_address".to_owned();
-    let eth_starport_address = properties.get(&key_address)?;
-    let eth_starport_address_str = eth_starport_address.as_str()?;
+    let mut starports = vec![];
+    for evm_chain in EVM_CHAINS.iter() {
+        let key_address = format!("{}_starport_address", evm_chain.config_name);
+        // the ethereum starport is required, chains without another only follow the ethereum starport
+        let starport_address_str = match properties.get(&key_address) {
+            Some(starport_address) => starport_address.as_str()?,
+            None if evm_chain.chain_id == ChainId::Eth => return None,
+            None => continue,
+        };
+        // without a deploy block, events are fetched from genesis
+        let key_block = format!("{}_starport_deploy_block", evm_chain.config_name);
+        let deploy_block = match properties.get(&key_block) {
+            Some(deploy_block) => deploy_block.as_u64()?,
+            None => 0,
+        };
+        starports.push(runtime_interfaces::StarportDeployment::new(
+            evm_chain.config_name,
+            starport_address_str,
+            deploy_block,
+        ));
+    }
 
     // todo: eager validation of some kind here - basic sanity checking? or no?
-    Some(runtime_interfaces::new_config(
-        eth_starport_address_str.into(),
-    ))
+    Some(runtime_interfaces::new_config(starports))
 }
 
 #[cfg(test)]
@@ -248,13 +270,56 @@ pub(crate) mod tests {
         let config = extract_configuration_from_properties(&properties);
         assert!(config.is_some());
         let config = config.unwrap();
-        let actual_eth_starport_address = config.get_eth_starport_address();
+        let actual_eth_starport_address = config.get_starport_address("eth");
         // let actual = String::from_utf8(actual).unwrap();
 
         assert_eq!(
             actual_eth_starport_address.as_slice(),
             expected_starport.as_bytes()
         );
+        assert_eq!(config.get_starport_address("matic"), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_extract_configuration_from_properties_with_matic_starport() {
+        let properties = serde_json::json!({
+            "eth_starport_address": "hello starport",
+            "matic_starport_address": "hello matic starport"
+        });
+        let properties = properties.as_object().unwrap();
+
+        let config = extract_configuration_from_properties(&properties).unwrap();
+        assert_eq!(
+            config.get_starport_address("matic").as_slice(),
+            "hello matic starport".as_bytes()
+        );
+
+        let properties = serde_json::json!({
+            "eth_starport_address": "hello starport",
+            "matic_starport_address": 0
+        });
+        let properties = properties.as_object().unwrap();
+        assert!(extract_configuration_from_properties(&properties).is_none());
+    }
+
+    #[test]
+    fn test_extract_configuration_from_properties_with_deploy_block() {
+        let properties = serde_json::json!({
+            "eth_starport_address": "hello starport",
+            "eth_starport_deploy_block": 9_000_000
+        });
+        let properties = properties.as_object().unwrap();
+
+        let config = extract_configuration_from_properties(&properties).unwrap();
+        assert_eq!(config.get_starport_deploy_block("eth"), 9_000_000);
+        assert_eq!(config.get_starport_deploy_block("matic"), 0);
+
+        let properties = serde_json::json!({
+            "eth_starport_address": "hello starport",
+            "eth_starport_deploy_block": "9000000"
+        });
+        let properties = properties.as_object().unwrap();
+        assert!(extract_configuration_from_properties(&properties).is_none());
     }
 
     /// Bad case - we do _not_ have the keys we need to return the OCW configuration
@@ -7,10 +7,73 @@ pub struct GatewayCmd {
     #[structopt(long = "eth-key-id")]
     pub eth_key_id: Option<String>,
 
-    /// Set the ETH RPC Url for interfacing with ethereum
+    /// Set the ETH RPC Url for interfacing with ethereum, or several comma separated Urls to fail over between
     #[structopt(long = "eth-rpc-url")]
     pub eth_rpc_url: Option<String>,
 
+    /// Require this many ETH RPC Urls to return identical logs before signing an event
+    #[structopt(long = "eth-rpc-quorum")]
+    pub eth_rpc_quorum: Option<u32>,
+
+    /// Fetch logs from the ETH RPC Urls at most this many blocks at a time (default 10000)
+    #[structopt(long = "eth-rpc-logs-block-span")]
+    pub eth_rpc_logs_block_span: Option<u64>,
+
+    /// Send at most this many log requests together in each batch request to the ETH RPC Urls (default 10)
+    #[structopt(long = "eth-rpc-logs-batch-size")]
+    pub eth_rpc_logs_batch_size: Option<u32>,
+
+    /// Give up on a request to the ETH RPC Urls after this many milliseconds (default 2000)
+    #[structopt(long = "eth-rpc-timeout-ms")]
+    pub eth_rpc_timeout_ms: Option<u64>,
+
+    /// Retry a request to the ETH RPC Urls up to this many times if it times out or fails with a 429 or 5xx,
+    /// backing off between attempts, for at most 10 seconds in all
+    #[structopt(long = "eth-rpc-retries")]
+    pub eth_rpc_retries: Option<u32>,
+
+    /// Send a `Name: value` header with each request to the ETH RPC Urls, or a `url=<Url> Name: value` header
+    /// with requests to that Url alone, may be given more than once
+    /// (prefer the ETH_RPC_HEADERS env var for credentials, which the command line would expose)
+    #[structopt(long = "eth-rpc-header", number_of_values = 1)]
+    pub eth_rpc_headers: Vec<String>,
+
+    /// Set the Matic RPC Url for interfacing with polygon, or several comma separated Urls to fail over between,
+    /// to follow the Matic starport
+    #[structopt(long = "matic-rpc-url")]
+    pub matic_rpc_url: Option<String>,
+
+    /// Require this many Matic RPC Urls to return identical logs before signing an event
+    #[structopt(long = "matic-rpc-quorum")]
+    pub matic_rpc_quorum: Option<u32>,
+
+    /// Fetch logs from the Matic RPC Urls at most this many blocks at a time (default 10000)
+    #[structopt(long = "matic-rpc-logs-block-span")]
+    pub matic_rpc_logs_block_span: Option<u64>,
+
+    /// Send at most this many log requests together in each batch request to the Matic RPC Urls (default 10)
+    #[structopt(long = "matic-rpc-logs-batch-size")]
+    pub matic_rpc_logs_batch_size: Option<u32>,
+
+    /// Give up on a request to the Matic RPC Url after this many milliseconds (default 2000)
+    #[structopt(long = "matic-rpc-timeout-ms")]
+    pub matic_rpc_timeout_ms: Option<u64>,
+
+    /// Retry a request to the Matic RPC Url up to this many times if it times out or fails with a 429 or 5xx,
+    /// backing off between attempts, for at most 10 seconds in all
+    #[structopt(long = "matic-rpc-retries")]
+    pub matic_rpc_retries: Option<u32>,
+
+    /// Send a `Name: value` header with each request to the Matic RPC Urls, or a `url=<Url> Name: value` header
+    /// with requests to that Url alone, may be given more than once
+    /// (prefer the MATIC_RPC_HEADERS env var for credentials, which the command line would expose)
+    #[structopt(long = "matic-rpc-header", number_of_values = 1)]
+    pub matic_rpc_headers: Vec<String>,
+
+    /// Run the liquidation keeper, liquidating from the account of the given keyring key ID
+    #[structopt(long = "liquidator-account")]
+    pub liquidator_account: Option<String>,
+
     /// Set the miner address (only useful for validator)
     #[structopt(long = "miner")]
     pub miner: Option<String>,
@@ -18,6 +81,20 @@ pub struct GatewayCmd {
     /// Open price feed URL
     #[structopt(long = "opf-url")]
     pub opf_url: Option<String>,
+
+    /// Give up on a request to the open price feed after this many milliseconds (default 2000)
+    #[structopt(long = "opf-timeout-ms")]
+    pub opf_timeout_ms: Option<u64>,
+
+    /// Retry a request to the open price feed up to this many times if it times out or fails with a 429 or 5xx,
+    /// backing off between attempts, for at most 10 seconds in all
+    #[structopt(long = "opf-retries")]
+    pub opf_retries: Option<u32>,
+
+    /// Send a `Name: value` header with each request to the open price feed, may be given more than once
+    /// (prefer the OPF_HEADERS env var for credentials, which the command line would expose)
+    #[structopt(long = "opf-header", number_of_values = 1)]
+    pub opf_headers: Vec<String>,
 }
 
 #[derive(Debug, StructOpt)]
@@ -135,10 +135,60 @@ pub fn run() -> sc_cli::Result<()> {
             let runner = cli.create_runner(&cli.run)?;
             runtime_interfaces::initialize_validator_config(
                 cli.gateway.eth_key_id.clone(),
-                cli.gateway.eth_rpc_url.clone(),
+                vec![
+                    (
+                        "eth".into(),
+                        runtime_interfaces::EvmRpcConfig::new(
+                            cli.gateway.eth_rpc_url.clone(),
+                            cli.gateway.eth_rpc_quorum,
+                            cli.gateway.eth_rpc_logs_block_span,
+                            cli.gateway.eth_rpc_logs_batch_size,
+                        ),
+                    ),
+                    (
+                        "matic".into(),
+                        runtime_interfaces::EvmRpcConfig::new(
+                            cli.gateway.matic_rpc_url.clone(),
+                            cli.gateway.matic_rpc_quorum,
+                            cli.gateway.matic_rpc_logs_block_span,
+                            cli.gateway.matic_rpc_logs_batch_size,
+                        ),
+                    ),
+                ],
+                cli.gateway.liquidator_account.clone(),
                 cli.gateway.miner.clone(),
                 cli.gateway.opf_url.clone(),
-            );
+            )
+            .map_err(sc_cli::Error::Input)?;
+            runtime_interfaces::initialize_validator_http_config(
+                vec![
+                    (
+                        "eth".into(),
+                        runtime_interfaces::HttpConfig::parse(
+                            cli.gateway.eth_rpc_timeout_ms,
+                            cli.gateway.eth_rpc_retries,
+                            &cli.gateway.eth_rpc_headers,
+                        )
+                        .map_err(sc_cli::Error::Input)?,
+                    ),
+                    (
+                        "matic".into(),
+                        runtime_interfaces::HttpConfig::parse(
+                            cli.gateway.matic_rpc_timeout_ms,
+                            cli.gateway.matic_rpc_retries,
+                            &cli.gateway.matic_rpc_headers,
+                        )
+                        .map_err(sc_cli::Error::Input)?,
+                    ),
+                ],
+                runtime_interfaces::HttpConfig::parse(
+                    cli.gateway.opf_timeout_ms,
+                    cli.gateway.opf_retries,
+                    &cli.gateway.opf_headers,
+                )
+                .map_err(sc_cli::Error::Input)?,
+            )
+            .map_err(sc_cli::Error::Input)?;
             Ok(runner.run_node_until_exit(|config| async move {
                 match config.role {
                     Role::Light => service::new_light(config),
@@ -1,5 +1,6 @@
 use pallet_cash::{
-    chains::{ChainAccount, ChainAsset},
+    chains::{ChainAccount, ChainAsset, ChainId},
+    notices::NoticeId,
     rates::APR,
     reason::Reason,
     types::{AssetAmount, AssetBalance, AssetInfo},
@@ -17,5 +18,6 @@ sp_api::decl_runtime_apis! {
         fn get_price(ticker: String) -> Result<AssetPrice, Reason>;
         fn get_price_with_ticker(ticker: Ticker) -> Result<AssetPrice, Reason>;
         fn get_rates(asset: ChainAsset) -> Result<(APR, APR), Reason>;
+        fn is_notice_ready(chain_id: ChainId, notice_id: NoticeId) -> Result<bool, Reason>;
     }
 }
@@ -42,6 +42,8 @@ benchmarks! {
         ValidatorKeys {
             substrate_id,
             eth_address,
+            dot_id: [0; 32],
+            sol_id: [0; 32],
         },
     );
 
@@ -72,6 +74,8 @@ benchmarks! {
         ValidatorKeys {
             substrate_id,
             eth_address,
+            dot_id: [0; 32],
+            sol_id: [0; 32],
         },
     );
     // Dispatch a signed extrinsic.
@@ -3,11 +3,22 @@ pub use our_std::vec::Vec;
 
 use crate::rates::APR;
 use crate::reason::Reason;
-use crate::types::{AssetAmount, CashIndex, Timestamp};
+use crate::types::{AssetAmount, CashIndex, Timestamp, ValidatorKeys};
+use crate::Validators;
 
 use codec::{Decode, Encode};
+use frame_support::storage::IterableStorageMap;
 use gateway_crypto::public_key_bytes_to_eth_address;
 use our_std::{str::FromStr, Debuggable, Deserialize, RuntimeDebug, Serialize};
+use sp_core::{
+    crypto::{key_types, AccountId32},
+    ed25519, sr25519,
+};
+use sp_runtime::{traits::Verify, MultiSignature};
+use trx_request::address::{
+    decode_base58, decode_ss58, decode_tezos, decode_tezos_account, encode_base58, encode_ss58,
+    encode_tezos, encode_tezos_account, DOT_SS58_PREFIX, GATE_SS58_PREFIX, TEZ_KT1_PREFIX,
+};
 
 use types_derive::{type_alias, Types};
 
@@ -20,6 +31,7 @@ pub enum ChainId {
     Dot,
     Sol,
     Tez,
+    Matic,
 }
 
 impl ChainId {
@@ -30,6 +42,7 @@ impl ChainId {
             ChainId::Dot => Ok(ChainAccount::Dot(Polkadot::str_to_address(addr)?)),
             ChainId::Sol => Ok(ChainAccount::Sol(Solana::str_to_address(addr)?)),
             ChainId::Tez => Ok(ChainAccount::Tez(Tezos::str_to_address(addr)?)),
+            ChainId::Matic => Ok(ChainAccount::Matic(Ethereum::str_to_address(addr)?)),
         }
     }
 
@@ -39,7 +52,10 @@ impl ChainId {
             ChainId::Eth => Ok(ChainAsset::Eth(Ethereum::str_to_address(addr)?)),
             ChainId::Dot => Ok(ChainAsset::Dot(Polkadot::str_to_address(addr)?)),
             ChainId::Sol => Ok(ChainAsset::Sol(Solana::str_to_address(addr)?)),
-            ChainId::Tez => Ok(ChainAsset::Tez(Tezos::str_to_address(addr)?)),
+            ChainId::Tez => Ok(ChainAsset::Tez(
+                decode_tezos(addr, TEZ_KT1_PREFIX).ok_or(Reason::BadAddress)?,
+            )),
+            ChainId::Matic => Ok(ChainAsset::Matic(Ethereum::str_to_address(addr)?)),
         }
     }
 
@@ -50,6 +66,7 @@ impl ChainId {
             ChainId::Dot => Ok(ChainAccount::Dot(<Polkadot as Chain>::signer_address()?)),
             ChainId::Sol => Ok(ChainAccount::Sol(<Solana as Chain>::signer_address()?)),
             ChainId::Tez => Ok(ChainAccount::Tez(<Tezos as Chain>::signer_address()?)),
+            ChainId::Matic => Ok(ChainAccount::Matic(<Ethereum as Chain>::signer_address()?)),
         }
     }
 
@@ -60,6 +77,7 @@ impl ChainId {
             ChainId::Dot => ChainHash::Dot(<Polkadot as Chain>::hash_bytes(data)),
             ChainId::Sol => ChainHash::Sol(<Solana as Chain>::hash_bytes(data)),
             ChainId::Tez => ChainHash::Tez(<Tezos as Chain>::hash_bytes(data)),
+            ChainId::Matic => ChainHash::Matic(<Ethereum as Chain>::hash_bytes(data)),
         }
     }
 
@@ -80,6 +98,9 @@ impl ChainId {
             ChainId::Tez => Ok(ChainSignature::Tez(<Tezos as Chain>::sign_message(
                 message,
             )?)),
+            ChainId::Matic => Ok(ChainSignature::Matic(<Ethereum as Chain>::sign_message(
+                message,
+            )?)),
         }
     }
 
@@ -90,10 +111,86 @@ impl ChainId {
             ChainId::Dot => ChainHash::Dot(<Polkadot as Chain>::zero_hash()),
             ChainId::Sol => ChainHash::Sol(<Solana as Chain>::zero_hash()),
             ChainId::Tez => ChainHash::Tez(<Tezos as Chain>::zero_hash()),
+            ChainId::Matic => ChainHash::Matic(<Ethereum as Chain>::zero_hash()),
+        }
+    }
+
+    /// Whether the chain runs a starport on the EVM, sharing Ethereum addresses, signatures and events.
+    pub fn is_evm(self) -> bool {
+        match self {
+            ChainId::Eth | ChainId::Matic => true,
+            ChainId::Gate | ChainId::Dot | ChainId::Sol | ChainId::Tez => false,
+        }
+    }
+
+    /// The account on an EVM chain with the given address.
+    pub fn evm_account(
+        self,
+        address: <Ethereum as Chain>::Address,
+    ) -> Result<ChainAccount, Reason> {
+        match self {
+            ChainId::Eth => Ok(ChainAccount::Eth(address)),
+            ChainId::Matic => Ok(ChainAccount::Matic(address)),
+            _ => Err(Reason::InvalidChain),
+        }
+    }
+
+    /// The asset on an EVM chain with the given address.
+    pub fn evm_asset(self, address: <Ethereum as Chain>::Address) -> Result<ChainAsset, Reason> {
+        match self {
+            ChainId::Eth => Ok(ChainAsset::Eth(address)),
+            ChainId::Matic => Ok(ChainAsset::Matic(address)),
+            _ => Err(Reason::InvalidChain),
         }
     }
+
+    /// The description of the chain, if it runs a starport on the EVM.
+    pub fn evm_chain(self) -> Result<&'static EvmChain, Reason> {
+        EVM_CHAINS
+            .iter()
+            .find(|evm_chain| evm_chain.chain_id == self)
+            .ok_or(Reason::InvalidChain)
+    }
+
+    /// The hash on an EVM chain with the given value.
+    pub fn evm_hash(self, hash: <Ethereum as Chain>::Hash) -> Result<ChainHash, Reason> {
+        match self {
+            ChainId::Eth => Ok(ChainHash::Eth(hash)),
+            ChainId::Matic => Ok(ChainHash::Matic(hash)),
+            _ => Err(Reason::InvalidChain),
+        }
+    }
+}
+
+/// A chain running a starport on the EVM, with its own RPC endpoints, events and notices.
+#[derive(Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct EvmChain {
+    pub chain_id: ChainId,
+    /// The name the chain is configured under, e.g. `<name>_starport_address` in the chain spec
+    ///  and `<NAME>_RPC_URL` / `<NAME>_RPC_QUORUM` for validators.
+    pub config_name: &'static str,
+    /// The prefix of messages signed by accounts on the chain, so they can't be replayed on another EVM chain.
+    pub message_prefix: &'static [u8],
+    /// The name within the OCW storage keys which track fetching events from the chain.
+    pub cursor_name: &'static str,
 }
 
+/// The chains running a starport on the EVM.
+pub static EVM_CHAINS: [EvmChain; 2] = [
+    EvmChain {
+        chain_id: ChainId::Eth,
+        config_name: "eth",
+        message_prefix: b"",
+        cursor_name: "ethereum",
+    },
+    EvmChain {
+        chain_id: ChainId::Matic,
+        config_name: "matic",
+        message_prefix: b"Matic:",
+        cursor_name: "matic",
+    },
+];
+
 impl Default for ChainId {
     fn default() -> Self {
         ChainId::Eth
@@ -108,13 +205,18 @@ pub enum ChainAccount {
     Dot(<Polkadot as Chain>::Address),
     Sol(<Solana as Chain>::Address),
     Tez(<Tezos as Chain>::Address),
+    Matic(<Ethereum as Chain>::Address),
 }
 
 impl ChainAccount {
     pub fn chain_id(&self) -> ChainId {
         match *self {
+            ChainAccount::Gate(_) => ChainId::Gate,
             ChainAccount::Eth(_) => ChainId::Eth,
-            _ => panic!("XXX not implemented"),
+            ChainAccount::Dot(_) => ChainId::Dot,
+            ChainAccount::Sol(_) => ChainId::Sol,
+            ChainAccount::Tez(_) => ChainId::Tez,
+            ChainAccount::Matic(_) => ChainId::Matic,
         }
     }
 }
@@ -138,8 +240,12 @@ impl FromStr for ChainAccount {
 impl From<ChainAccount> for String {
     fn from(asset: ChainAccount) -> String {
         match asset {
+            ChainAccount::Gate(address) => format!("GATE:{}", Gateway::address_string(&address)),
             ChainAccount::Eth(address) => format!("ETH:0x{}", hex::encode(address)),
-            _ => panic!("XXX not implemented"),
+            ChainAccount::Dot(address) => format!("DOT:{}", Polkadot::address_string(&address)),
+            ChainAccount::Sol(address) => format!("SOL:{}", Solana::address_string(&address)),
+            ChainAccount::Tez(address) => format!("TEZ:{}", Tezos::address_string(&address)),
+            ChainAccount::Matic(address) => format!("MATIC:0x{}", hex::encode(address)),
         }
     }
 }
@@ -151,15 +257,20 @@ pub enum ChainAsset {
     Eth(<Ethereum as Chain>::Address),
     Dot(<Polkadot as Chain>::Address),
     Sol(<Solana as Chain>::Address),
-    Tez(<Tezos as Chain>::Address),
+    Tez(tez::ContractHash),
+    Matic(<Ethereum as Chain>::Address),
 }
 
 // For serialize (which we don't really use, but are required to implement)
 impl ChainAsset {
     pub fn chain_id(&self) -> ChainId {
         match *self {
+            ChainAsset::Gate(_) => ChainId::Gate,
             ChainAsset::Eth(_) => ChainId::Eth,
-            _ => panic!("XXX not implemented"),
+            ChainAsset::Dot(_) => ChainId::Dot,
+            ChainAsset::Sol(_) => ChainId::Sol,
+            ChainAsset::Tez(_) => ChainId::Tez,
+            ChainAsset::Matic(_) => ChainId::Matic,
         }
     }
 }
@@ -182,8 +293,14 @@ impl FromStr for ChainAsset {
 impl From<ChainAsset> for String {
     fn from(asset: ChainAsset) -> String {
         match asset {
+            ChainAsset::Gate(address) => format!("GATE:{}", Gateway::address_string(&address)),
             ChainAsset::Eth(address) => format!("ETH:0x{}", hex::encode(address)),
-            _ => panic!("XXX not implemented"),
+            ChainAsset::Dot(address) => format!("DOT:{}", Polkadot::address_string(&address)),
+            ChainAsset::Sol(address) => format!("SOL:{}", Solana::address_string(&address)),
+            ChainAsset::Tez(address) => {
+                format!("TEZ:{}", encode_tezos(&address, TEZ_KT1_PREFIX))
+            }
+            ChainAsset::Matic(address) => format!("MATIC:0x{}", hex::encode(address)),
         }
     }
 }
@@ -196,6 +313,7 @@ pub enum ChainAccountSignature {
     Dot(<Polkadot as Chain>::Address, <Polkadot as Chain>::Signature),
     Sol(<Solana as Chain>::Address, <Solana as Chain>::Signature),
     Tez(<Tezos as Chain>::Address, <Tezos as Chain>::Signature),
+    Matic(<Ethereum as Chain>::Address, <Ethereum as Chain>::Signature),
 }
 
 impl ChainAccountSignature {
@@ -206,20 +324,53 @@ impl ChainAccountSignature {
             ChainAccountSignature::Dot(_, sig) => ChainSignature::Dot(sig),
             ChainAccountSignature::Sol(_, sig) => ChainSignature::Sol(sig),
             ChainAccountSignature::Tez(_, sig) => ChainSignature::Tez(sig),
+            ChainAccountSignature::Matic(_, sig) => ChainSignature::Matic(sig),
         }
     }
 
     pub fn recover_account(self, message: &[u8]) -> Result<ChainAccount, Reason> {
         match self {
+            ChainAccountSignature::Gate(gate_account, gate_sig) => {
+                <Gateway as Chain>::verify_user_signature(message, gate_sig, &gate_account)?;
+                Ok(ChainAccount::Gate(gate_account))
+            }
             ChainAccountSignature::Eth(eth_account, eth_sig) => {
-                let recovered = <Ethereum as Chain>::recover_user_address(message, eth_sig)?;
-                if eth_account == recovered {
-                    Ok(ChainAccount::Eth(recovered))
-                } else {
-                    Err(Reason::SignatureAccountMismatch)
-                }
+                <Ethereum as Chain>::verify_user_signature(message, eth_sig, &eth_account)?;
+                Ok(ChainAccount::Eth(eth_account))
+            }
+            ChainAccountSignature::Dot(dot_account, dot_sig) => {
+                <Polkadot as Chain>::verify_user_signature(message, dot_sig, &dot_account)?;
+                Ok(ChainAccount::Dot(dot_account))
+            }
+            ChainAccountSignature::Sol(sol_account, sol_sig) => {
+                <Solana as Chain>::verify_user_signature(message, sol_sig, &sol_account)?;
+                Ok(ChainAccount::Sol(sol_account))
+            }
+            ChainAccountSignature::Tez(tez_account, tez_sig) => {
+                <Tezos as Chain>::verify_user_signature(message, tez_sig, &tez_account)?;
+                Ok(ChainAccount::Tez(tez_account))
+            }
+            // Matic accounts share their keys with Ethereum accounts, so they sign for their chain
+            ChainAccountSignature::Matic(matic_account, matic_sig) => {
+                <Ethereum as Chain>::verify_user_signature(
+                    &[ChainId::Matic.evm_chain()?.message_prefix, message].concat(),
+                    matic_sig,
+                    &matic_account,
+                )?;
+                Ok(ChainAccount::Matic(matic_account))
             }
-            _ => panic!("XXX not implemented"),
+        }
+    }
+
+    /// Recover the account from a signature of EIP-712 typed data, which only Ethereum accounts produce.
+    pub fn recover_typed_account(self, typed_data: &[u8]) -> Result<ChainAccount, Reason> {
+        match self {
+            ChainAccountSignature::Eth(eth_account, eth_sig) => {
+                <Ethereum as Chain>::verify_signature(typed_data, eth_sig, &eth_account)
+                    .map_err(|_| Reason::SignatureAccountMismatch)?;
+                Ok(ChainAccount::Eth(eth_account))
+            }
+            _ => Err(Reason::SignatureMismatch),
         }
     }
 }
@@ -232,6 +383,7 @@ pub enum ChainHash {
     Dot(<Polkadot as Chain>::Hash),
     Sol(<Solana as Chain>::Hash),
     Tez(<Tezos as Chain>::Hash),
+    Matic(<Ethereum as Chain>::Hash),
 }
 
 /// Type for a signature tied to a chain.
@@ -242,13 +394,18 @@ pub enum ChainSignature {
     Dot(<Polkadot as Chain>::Signature),
     Sol(<Solana as Chain>::Signature),
     Tez(<Tezos as Chain>::Signature),
+    Matic(<Ethereum as Chain>::Signature),
 }
 
 impl ChainSignature {
     pub fn chain_id(&self) -> ChainId {
         match *self {
+            ChainSignature::Gate(_) => ChainId::Gate,
             ChainSignature::Eth(_) => ChainId::Eth,
-            _ => panic!("XXX not implemented"),
+            ChainSignature::Dot(_) => ChainId::Dot,
+            ChainSignature::Sol(_) => ChainId::Sol,
+            ChainSignature::Tez(_) => ChainId::Tez,
+            ChainSignature::Matic(_) => ChainId::Matic,
         }
     }
 
@@ -257,8 +414,35 @@ impl ChainSignature {
             ChainSignature::Eth(eth_sig) => Ok(ChainAccount::Eth(
                 <Ethereum as Chain>::recover_address(message, *eth_sig)?,
             )),
+            ChainSignature::Dot(dot_sig) => Ok(ChainAccount::Dot(
+                <Polkadot as Chain>::recover_address(message, *dot_sig)?,
+            )),
+            ChainSignature::Sol(sol_sig) => Ok(ChainAccount::Sol(
+                <Solana as Chain>::recover_address(message, *sol_sig)?,
+            )),
+            ChainSignature::Matic(matic_sig) => Ok(ChainAccount::Matic(
+                <Ethereum as Chain>::recover_address(message, *matic_sig)?,
+            )),
+            ChainSignature::Gate(_) | ChainSignature::Tez(_) => Err(Reason::NotImplemented),
+        }
+    }
 
-            _ => panic!("XXX not implemented"),
+    /// Check that the signature was produced by the given signer, for chains whose signatures can't be recovered.
+    pub fn verify(&self, message: &[u8], signer: ChainAccount) -> Result<(), Reason> {
+        match (self, signer) {
+            (ChainSignature::Eth(eth_sig), ChainAccount::Eth(eth_account)) => {
+                <Ethereum as Chain>::verify_signature(message, *eth_sig, &eth_account)
+            }
+            (ChainSignature::Dot(dot_sig), ChainAccount::Dot(dot_account)) => {
+                <Polkadot as Chain>::verify_signature(message, *dot_sig, &dot_account)
+            }
+            (ChainSignature::Sol(sol_sig), ChainAccount::Sol(sol_account)) => {
+                <Solana as Chain>::verify_signature(message, *sol_sig, &sol_account)
+            }
+            (ChainSignature::Matic(matic_sig), ChainAccount::Matic(matic_account)) => {
+                <Ethereum as Chain>::verify_signature(message, *matic_sig, &matic_account)
+            }
+            _ => Err(Reason::SignatureMismatch),
         }
     }
 }
@@ -271,6 +455,7 @@ pub enum ChainSignatureList {
     Dot(Vec<(<Polkadot as Chain>::Address, <Polkadot as Chain>::Signature)>),
     Sol(Vec<(<Solana as Chain>::Address, <Solana as Chain>::Signature)>),
     Tez(Vec<(<Tezos as Chain>::Address, <Tezos as Chain>::Signature)>),
+    Matic(Vec<(<Ethereum as Chain>::Address, <Ethereum as Chain>::Signature)>),
 }
 
 // Implement deserialization for ChainIds so we can use them in GenesisConfig / ChainSpec JSON.
@@ -279,8 +464,12 @@ impl FromStr for ChainId {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_ascii_uppercase().as_str() {
+            "GATE" => Ok(ChainId::Gate),
             "ETH" => Ok(ChainId::Eth),
+            "DOT" => Ok(ChainId::Dot),
             "SOL" => Ok(ChainId::Sol),
+            "TEZ" => Ok(ChainId::Tez),
+            "MATIC" => Ok(ChainId::Matic),
             _ => Err(Reason::BadChainId),
         }
     }
@@ -311,6 +500,30 @@ pub trait Chain {
     fn signer_address() -> Result<Self::Address, Reason>;
     fn str_to_address(addr: &str) -> Result<Self::Address, Reason>;
     fn address_string(address: &Self::Address) -> String;
+
+    fn verify_user_signature(
+        data: &[u8],
+        signature: Self::Signature,
+        address: &Self::Address,
+    ) -> Result<(), Reason> {
+        if Self::recover_user_address(data, signature)? == *address {
+            Ok(())
+        } else {
+            Err(Reason::SignatureAccountMismatch)
+        }
+    }
+
+    fn verify_signature(
+        data: &[u8],
+        signature: Self::Signature,
+        address: &Self::Address,
+    ) -> Result<(), Reason> {
+        if Self::recover_address(data, signature)? == *address {
+            Ok(())
+        } else {
+            Err(Reason::SignatureMismatch)
+        }
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
@@ -332,7 +545,7 @@ impl Chain for Gateway {
     const ID: ChainId = ChainId::Gate;
 
     #[type_alias("Gateway__Chain__")]
-    type Address = [u8; 20];
+    type Address = [u8; 32];
 
     #[type_alias("Gateway__Chain__")]
     type Amount = u128;
@@ -350,8 +563,9 @@ impl Chain for Gateway {
     type Hash = [u8; 32];
 
     #[type_alias("Gateway__Chain__")]
-    type PublicKey = [u8; 64];
+    type PublicKey = [u8; 32];
 
+    /// A SCALE encoded `MultiSignature`, i.e. a scheme byte (0 = ed25519, 1 = sr25519) and the signature.
     #[type_alias("Gateway__Chain__")]
     type Signature = [u8; 65];
 
@@ -362,38 +576,54 @@ impl Chain for Gateway {
     type Event = comp::Event;
 
     fn zero_hash() -> Self::Hash {
-        panic!("XXX not implemented");
+        [0u8; 32]
     }
 
-    fn hash_bytes(_data: &[u8]) -> Self::Hash {
-        panic!("XXX not implemented");
+    fn hash_bytes(data: &[u8]) -> Self::Hash {
+        sp_io::hashing::blake2_256(data)
     }
 
+    // Neither sr25519 nor ed25519 signatures commit to the signer, so they must be verified against a known account
     fn recover_user_address(
         _data: &[u8],
         _signature: Self::Signature,
     ) -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+        Err(Reason::NotImplemented)
     }
 
     fn recover_address(_data: &[u8], _signature: Self::Signature) -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+        Err(Reason::NotImplemented)
+    }
+
+    fn verify_user_signature(
+        data: &[u8],
+        signature: Self::Signature,
+        address: &Self::Address,
+    ) -> Result<(), Reason> {
+        let multi_signature =
+            MultiSignature::decode(&mut &signature[..]).map_err(|_| Reason::SignatureMismatch)?;
+        if multi_signature.verify(data, &AccountId32::from(*address)) {
+            Ok(())
+        } else {
+            Err(Reason::SignatureAccountMismatch)
+        }
     }
 
+    // Gateway accounts hold balances natively, so validators never sign anything for them
     fn sign_message(_message: &[u8]) -> Result<Self::Signature, Reason> {
-        panic!("XXX not implemented");
+        Err(Reason::NotImplemented)
     }
 
     fn signer_address() -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+        Err(Reason::NotImplemented)
     }
 
-    fn str_to_address(_addr: &str) -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+    fn str_to_address(addr: &str) -> Result<Self::Address, Reason> {
+        decode_ss58(addr, GATE_SS58_PREFIX).ok_or(Reason::BadAddress)
     }
 
-    fn address_string(_address: &Self::Address) -> String {
-        panic!("XXX not implemented");
+    fn address_string(address: &Self::Address) -> String {
+        encode_ss58(address, GATE_SS58_PREFIX)
     }
 }
 
@@ -494,7 +724,7 @@ impl Chain for Polkadot {
     const ID: ChainId = ChainId::Dot;
 
     #[type_alias("Polkadot__Chain__")]
-    type Address = [u8; 20];
+    type Address = [u8; 32];
 
     #[type_alias("Polkadot__Chain__")]
     type Amount = u128;
@@ -512,10 +742,10 @@ impl Chain for Polkadot {
     type Hash = [u8; 32];
 
     #[type_alias("Polkadot__Chain__")]
-    type PublicKey = [u8; 64];
+    type PublicKey = [u8; 32];
 
     #[type_alias("Polkadot__Chain__")]
-    type Signature = [u8; 65];
+    type Signature = [u8; 64];
 
     #[type_alias("Polkadot__Chain__")]
     type EventId = dot::EventId;
@@ -524,46 +754,93 @@ impl Chain for Polkadot {
     type Event = dot::Event;
 
     fn zero_hash() -> Self::Hash {
-        panic!("XXX not implemented");
+        [0u8; 32]
     }
 
-    fn hash_bytes(_data: &[u8]) -> Self::Hash {
-        panic!("XXX not implemented");
+    fn hash_bytes(data: &[u8]) -> Self::Hash {
+        sp_io::hashing::blake2_256(data)
     }
 
+    // sr25519 signatures do not commit to the signer, so they must be verified against a known account
     fn recover_user_address(
         _data: &[u8],
         _signature: Self::Signature,
     ) -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+        Err(Reason::NotImplemented)
     }
 
     fn recover_address(_data: &[u8], _signature: Self::Signature) -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+        Err(Reason::NotImplemented)
     }
 
-    fn sign_message(_message: &[u8]) -> Result<Self::Signature, Reason> {
-        panic!("XXX not implemented");
+    fn verify_user_signature(
+        data: &[u8],
+        signature: Self::Signature,
+        address: &Self::Address,
+    ) -> Result<(), Reason> {
+        if sr25519_verify(data, signature, address) {
+            Ok(())
+        } else {
+            Err(Reason::SignatureAccountMismatch)
+        }
+    }
+
+    fn verify_signature(
+        data: &[u8],
+        signature: Self::Signature,
+        address: &Self::Address,
+    ) -> Result<(), Reason> {
+        if sr25519_verify(data, signature, address) {
+            Ok(())
+        } else {
+            Err(Reason::SignatureMismatch)
+        }
+    }
+
+    // Validators sign Polkadot notices with the sr25519 key registered as their dot id,
+    // which must be held in the aura keystore
+    fn sign_message(message: &[u8]) -> Result<Self::Signature, Reason> {
+        let public = sr25519::Public::from_raw(Self::signer_address()?);
+        let signature = sp_io::crypto::sr25519_sign(key_types::AURA, &public, message)
+            .ok_or(Reason::KeyNotFound)?;
+        Ok(signature.0)
     }
 
     fn signer_address() -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+        Ok(local_validator_keys()?.dot_id)
     }
 
-    fn str_to_address(_addr: &str) -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+    fn str_to_address(addr: &str) -> Result<Self::Address, Reason> {
+        decode_ss58(addr, DOT_SS58_PREFIX).ok_or(Reason::BadAddress)
     }
 
-    fn address_string(_address: &Self::Address) -> String {
-        panic!("XXX not implemented");
+    fn address_string(address: &Self::Address) -> String {
+        encode_ss58(address, DOT_SS58_PREFIX)
     }
 }
 
+/// The keys registered for the validator running this node, identified by its eth key.
+fn local_validator_keys() -> Result<ValidatorKeys, Reason> {
+    let eth_address = <Ethereum as Chain>::signer_address()?;
+    Validators::iter()
+        .map(|(_, keys)| keys)
+        .find(|keys| keys.eth_address == eth_address)
+        .ok_or(Reason::UnknownValidator)
+}
+
+fn sr25519_verify(data: &[u8], signature: [u8; 64], address: &[u8; 32]) -> bool {
+    sp_io::crypto::sr25519_verify(
+        &sr25519::Signature::from_raw(signature),
+        data,
+        &sr25519::Public::from_raw(*address),
+    )
+}
+
 impl Chain for Solana {
     const ID: ChainId = ChainId::Sol;
 
     #[type_alias("Solana__Chain__")]
-    type Address = [u8; 20];
+    type Address = [u8; 32];
 
     #[type_alias("Solana__Chain__")]
     type Amount = u128;
@@ -581,10 +858,10 @@ impl Chain for Solana {
     type Hash = [u8; 32];
 
     #[type_alias("Solana__Chain__")]
-    type PublicKey = [u8; 64];
+    type PublicKey = [u8; 32];
 
     #[type_alias("Solana__Chain__")]
-    type Signature = [u8; 65];
+    type Signature = [u8; 64];
 
     #[type_alias("Solana__Chain__")]
     type EventId = sol::EventId;
@@ -593,46 +870,85 @@ impl Chain for Solana {
     type Event = sol::Event;
 
     fn zero_hash() -> Self::Hash {
-        panic!("XXX not implemented");
+        [0u8; 32]
     }
 
-    fn hash_bytes(_data: &[u8]) -> Self::Hash {
-        panic!("XXX not implemented");
+    fn hash_bytes(data: &[u8]) -> Self::Hash {
+        sp_io::hashing::sha2_256(data)
     }
 
+    // ed25519 signatures do not commit to the signer, so they must be verified against a known account
     fn recover_user_address(
         _data: &[u8],
         _signature: Self::Signature,
     ) -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+        Err(Reason::NotImplemented)
     }
 
     fn recover_address(_data: &[u8], _signature: Self::Signature) -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+        Err(Reason::NotImplemented)
     }
 
-    fn sign_message(_message: &[u8]) -> Result<Self::Signature, Reason> {
-        panic!("XXX not implemented");
+    fn verify_user_signature(
+        data: &[u8],
+        signature: Self::Signature,
+        address: &Self::Address,
+    ) -> Result<(), Reason> {
+        if ed25519_verify(data, signature, address) {
+            Ok(())
+        } else {
+            Err(Reason::SignatureAccountMismatch)
+        }
+    }
+
+    fn verify_signature(
+        data: &[u8],
+        signature: Self::Signature,
+        address: &Self::Address,
+    ) -> Result<(), Reason> {
+        if ed25519_verify(data, signature, address) {
+            Ok(())
+        } else {
+            Err(Reason::SignatureMismatch)
+        }
+    }
+
+    // Validators sign Solana notices with the ed25519 key registered as their sol id,
+    // which must be held in the grandpa keystore
+    fn sign_message(message: &[u8]) -> Result<Self::Signature, Reason> {
+        let public = ed25519::Public::from_raw(Self::signer_address()?);
+        let signature = sp_io::crypto::ed25519_sign(key_types::GRANDPA, &public, message)
+            .ok_or(Reason::KeyNotFound)?;
+        Ok(signature.0)
     }
 
     fn signer_address() -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+        Ok(local_validator_keys()?.sol_id)
     }
 
-    fn str_to_address(_addr: &str) -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+    fn str_to_address(addr: &str) -> Result<Self::Address, Reason> {
+        decode_base58(addr).ok_or(Reason::BadAddress)
     }
 
-    fn address_string(_address: &Self::Address) -> String {
-        panic!("XXX not implemented");
+    fn address_string(address: &Self::Address) -> String {
+        encode_base58(address)
     }
 }
 
+fn ed25519_verify(data: &[u8], signature: [u8; 64], address: &[u8; 32]) -> bool {
+    sp_io::crypto::ed25519_verify(
+        &ed25519::Signature::from_raw(signature),
+        data,
+        &ed25519::Public::from_raw(*address),
+    )
+}
+
 impl Chain for Tezos {
     const ID: ChainId = ChainId::Tez;
 
+    /// The tag of the account's curve, followed by its key hash.
     #[type_alias("Tezos__Chain__")]
-    type Address = [u8; 20];
+    type Address = [u8; 21];
 
     #[type_alias("Tezos__Chain__")]
     type Amount = u128;
@@ -662,38 +978,39 @@ impl Chain for Tezos {
     type Event = tez::Event;
 
     fn zero_hash() -> Self::Hash {
-        panic!("XXX not implemented");
+        [0u8; 32]
     }
 
-    fn hash_bytes(_data: &[u8]) -> Self::Hash {
-        panic!("XXX not implemented");
+    fn hash_bytes(data: &[u8]) -> Self::Hash {
+        sp_io::hashing::blake2_256(data)
     }
 
     fn recover_user_address(
         _data: &[u8],
         _signature: Self::Signature,
     ) -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+        Err(Reason::NotImplemented)
     }
 
     fn recover_address(_data: &[u8], _signature: Self::Signature) -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+        Err(Reason::NotImplemented)
     }
 
     fn sign_message(_message: &[u8]) -> Result<Self::Signature, Reason> {
-        panic!("XXX not implemented");
+        Err(Reason::NotImplemented)
     }
 
     fn signer_address() -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+        Err(Reason::NotImplemented)
     }
 
-    fn str_to_address(_addr: &str) -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+    // Accounts are implicit (tz1, tz2 or tz3), assets are originated contracts (KT1)
+    fn str_to_address(addr: &str) -> Result<Self::Address, Reason> {
+        decode_tezos_account(addr).ok_or(Reason::BadAddress)
     }
 
-    fn address_string(_address: &Self::Address) -> String {
-        panic!("XXX not implemented");
+    fn address_string(address: &Self::Address) -> String {
+        encode_tezos_account(address).unwrap_or_else(|| format!("0x{}", hex::encode(address)))
     }
 }
 
@@ -732,6 +1049,9 @@ pub mod eth {
     #[type_alias("eth__")]
     pub type BlockNumber = u64;
 
+    #[type_alias("eth__")]
+    pub type BlockHash = [u8; 32];
+
     #[type_alias("eth__")]
     pub type LogIndex = u64;
 
@@ -800,6 +1120,337 @@ pub mod tez {
     #[type_alias("tez__")]
     pub type EventId = (u128, u128);
 
+    /// The hash identifying an originated contract.
+    #[type_alias("tez__")]
+    pub type ContractHash = [u8; 20];
+
     #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
     pub struct Event {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_core::Pair;
+
+    const ALICE: [u8; 32] = [
+        212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88,
+        133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125,
+    ];
+
+    #[test]
+    fn test_polkadot_addresses() {
+        let alice = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5";
+
+        assert_eq!(ChainId::from_str("dot"), Ok(ChainId::Dot));
+        assert_eq!(Polkadot::str_to_address(alice), Ok(ALICE));
+        assert_eq!(Polkadot::address_string(&ALICE), alice);
+        assert_eq!(
+            ChainAccount::from_str(&format!("DOT:{}", alice)),
+            Ok(ChainAccount::Dot(ALICE))
+        );
+        assert_eq!(
+            String::from(ChainAccount::Dot(ALICE)),
+            format!("DOT:{}", alice)
+        );
+        assert_eq!(ChainAccount::Dot(ALICE).chain_id(), ChainId::Dot);
+
+        // a gateway (substrate generic) address is not a polkadot address
+        assert_eq!(
+            Polkadot::str_to_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+            Err(Reason::BadAddress)
+        );
+    }
+
+    #[test]
+    fn test_polkadot_signatures() {
+        let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+        let message = b"0:(Extract 100 Cash Dot:15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5)";
+        let signature = pair.sign(&message[..]).0;
+
+        assert_eq!(pair.public().0, ALICE);
+        assert_eq!(
+            ChainAccountSignature::Dot(ALICE, signature).recover_account(&message[..]),
+            Ok(ChainAccount::Dot(ALICE))
+        );
+        assert_eq!(
+            ChainAccountSignature::Dot([1; 32], signature).recover_account(&message[..]),
+            Err(Reason::SignatureAccountMismatch)
+        );
+        assert_eq!(
+            ChainAccountSignature::Dot(ALICE, signature).recover_account(b"1:(Extract 100 Cash)"),
+            Err(Reason::SignatureAccountMismatch)
+        );
+
+        let chain_signature = ChainSignature::Dot(signature);
+        assert_eq!(chain_signature.chain_id(), ChainId::Dot);
+        assert_eq!(
+            chain_signature.verify(&message[..], ChainAccount::Dot(ALICE)),
+            Ok(())
+        );
+        assert_eq!(
+            chain_signature.verify(&message[..], ChainAccount::Eth([1; 20])),
+            Err(Reason::SignatureMismatch)
+        );
+        assert_eq!(
+            chain_signature.recover(&message[..]),
+            Err(Reason::NotImplemented)
+        );
+    }
+
+    #[test]
+    fn test_gateway_addresses() {
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+        assert_eq!(ChainId::from_str("gate"), Ok(ChainId::Gate));
+        assert_eq!(Gateway::str_to_address(alice), Ok(ALICE));
+        assert_eq!(Gateway::address_string(&ALICE), alice);
+        assert_eq!(
+            ChainAccount::from_str(&format!("Gate:{}", alice)),
+            Ok(ChainAccount::Gate(ALICE))
+        );
+        assert_eq!(
+            String::from(ChainAccount::Gate(ALICE)),
+            format!("GATE:{}", alice)
+        );
+        assert_eq!(ChainAccount::Gate(ALICE).chain_id(), ChainId::Gate);
+
+        // a polkadot address is not a gateway address
+        assert_eq!(
+            Gateway::str_to_address("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"),
+            Err(Reason::BadAddress)
+        );
+    }
+
+    #[test]
+    fn test_gateway_signatures() {
+        let message = b"0:(Transfer 1 Cash Gate:5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY)";
+        let sr_pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+        let sr_signature = MultiSignature::from(sr_pair.sign(&message[..])).encode();
+        let ed_pair = ed25519::Pair::from_string("//Alice", None).unwrap();
+        let ed_signature = MultiSignature::from(ed_pair.sign(&message[..])).encode();
+        let mut sr_sig = [0u8; 65];
+        let mut ed_sig = [0u8; 65];
+        sr_sig.copy_from_slice(&sr_signature[..]);
+        ed_sig.copy_from_slice(&ed_signature[..]);
+
+        assert_eq!(sr_sig[0], 1);
+        assert_eq!(ed_sig[0], 0);
+        assert_eq!(
+            ChainAccountSignature::Gate(ALICE, sr_sig).recover_account(&message[..]),
+            Ok(ChainAccount::Gate(ALICE))
+        );
+        assert_eq!(
+            ChainAccountSignature::Gate(ed_pair.public().0, ed_sig).recover_account(&message[..]),
+            Ok(ChainAccount::Gate(ed_pair.public().0))
+        );
+        assert_eq!(
+            ChainAccountSignature::Gate(ALICE, ed_sig).recover_account(&message[..]),
+            Err(Reason::SignatureAccountMismatch)
+        );
+        assert_eq!(
+            ChainAccountSignature::Gate(ALICE, sr_sig).recover_account(b"1:(Transfer 1 Cash)"),
+            Err(Reason::SignatureAccountMismatch)
+        );
+
+        // ecdsa and unknown schemes are not accepted
+        sr_sig[0] = 2;
+        assert_eq!(
+            ChainAccountSignature::Gate(ALICE, sr_sig).recover_account(&message[..]),
+            Err(Reason::SignatureMismatch)
+        );
+    }
+
+    // RFC 8032 ed25519 test vector 2
+    const SOL_KEY: [u8; 32] = [
+        61, 64, 23, 195, 232, 67, 137, 90, 146, 183, 10, 167, 77, 27, 126, 188, 156, 152, 44, 207,
+        46, 196, 150, 140, 192, 205, 85, 241, 42, 244, 102, 12,
+    ];
+
+    #[test]
+    fn test_solana_addresses() {
+        let sol = "586Z7H2vpX9qNhN2T4e9Utugie3ogjbxzGaMtM3E6HR5";
+
+        assert_eq!(ChainId::from_str("sol"), Ok(ChainId::Sol));
+        assert_eq!(Solana::str_to_address(sol), Ok(SOL_KEY));
+        assert_eq!(Solana::address_string(&SOL_KEY), sol);
+        assert_eq!(
+            ChainAccount::from_str(&format!("SOL:{}", sol)),
+            Ok(ChainAccount::Sol(SOL_KEY))
+        );
+        assert_eq!(
+            String::from(ChainAccount::Sol(SOL_KEY)),
+            format!("SOL:{}", sol)
+        );
+        assert_eq!(
+            ChainAsset::from_str(&format!("SOL:{}", sol)).map(|a| a.chain_id()),
+            Ok(ChainId::Sol)
+        );
+        assert_eq!(
+            Solana::str_to_address("586Z7H2vpX9qNhN2T4e9Utugie3ogjbx"),
+            Err(Reason::BadAddress)
+        );
+        assert_eq!(Solana::str_to_address("0x3d40"), Err(Reason::BadAddress));
+    }
+
+    #[test]
+    fn test_solana_signatures() {
+        let signature = hex_literal::hex!("92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00");
+        let chain_signature = ChainSignature::Sol(signature);
+
+        assert_eq!(chain_signature.chain_id(), ChainId::Sol);
+        assert_eq!(
+            chain_signature.verify(&[0x72], ChainAccount::Sol(SOL_KEY)),
+            Ok(())
+        );
+        assert_eq!(
+            chain_signature.verify(&[0x73], ChainAccount::Sol(SOL_KEY)),
+            Err(Reason::SignatureMismatch)
+        );
+        assert_eq!(
+            chain_signature.verify(&[0x72], ChainAccount::Dot(SOL_KEY)),
+            Err(Reason::SignatureMismatch)
+        );
+
+        let message = b"0:(Extract 100 Cash Sol:586Z7H2vpX9qNhN2T4e9Utugie3ogjbxzGaMtM3E6HR5)";
+        let trx_signature = hex_literal::hex!("5e4a62b7c6d793af78f588353a81646826a1c2847854b48798d2b27f9ac3e3745436a465361f61cbbb9e3100c1b1e1efd6b05d3c39e9cbdd8fb064061775a309");
+
+        assert_eq!(
+            ChainAccountSignature::Sol(SOL_KEY, trx_signature).recover_account(&message[..]),
+            Ok(ChainAccount::Sol(SOL_KEY))
+        );
+        assert_eq!(
+            ChainAccountSignature::Sol([1; 32], trx_signature).recover_account(&message[..]),
+            Err(Reason::SignatureAccountMismatch)
+        );
+    }
+    #[test]
+    fn test_tezos_addresses() {
+        let tz1 = "tz1KjLa4hxghcRgtK6i8BgPTXathEV66JaSk";
+        let tz2 = "tz28QYYHJY74YBKkbGkxrKMi5A9hkNZzdwGm";
+        let tz3 = "tz3LRMVcCQEbvon41XqDrGp5tX3DXCiw6Ge3";
+        let kt1 = "KT1WN8coMkJHbdjqUeSQM5FEerxRgu1st3tf";
+
+        assert_eq!(ChainId::from_str("tez"), Ok(ChainId::Tez));
+        let mut account = [1; 21];
+        for (tag, address) in [tz1, tz2, tz3].iter().enumerate() {
+            account[0] = tag as u8;
+            assert_eq!(Tezos::str_to_address(address), Ok(account));
+            assert_eq!(Tezos::address_string(&account), *address);
+            assert_eq!(
+                ChainAccount::from_str(&format!("TEZ:{}", address)),
+                Ok(ChainAccount::Tez(account))
+            );
+            assert_eq!(
+                String::from(ChainAccount::Tez(account)),
+                format!("TEZ:{}", address)
+            );
+        }
+        assert_eq!(Tezos::str_to_address(kt1), Err(Reason::BadAddress));
+        assert_eq!(
+            ChainAsset::from_str(&format!("TEZ:{}", kt1)),
+            Ok(ChainAsset::Tez([238; 20]))
+        );
+        assert_eq!(
+            ChainAsset::from_str(&format!("TEZ:{}", tz1)),
+            Err(Reason::BadAddress)
+        );
+        assert_eq!(
+            String::from(ChainAsset::Tez([238; 20])),
+            format!("TEZ:{}", kt1)
+        );
+        assert_eq!(ChainAccount::Tez([0; 21]).chain_id(), ChainId::Tez);
+        assert_eq!(ChainAsset::Tez([238; 20]).chain_id(), ChainId::Tez);
+        assert_eq!(
+            Tezos::str_to_address("tz1KjLa4hxghcRgtK6i8BgPTXathEV66JaSm"),
+            Err(Reason::BadAddress)
+        );
+    }
+
+    #[test]
+    fn test_tezos_signatures() {
+        let chain_signature = ChainSignature::Tez([0; 65]);
+
+        assert_eq!(chain_signature.chain_id(), ChainId::Tez);
+        assert_eq!(
+            chain_signature.recover(b"hello"),
+            Err(Reason::NotImplemented)
+        );
+        assert_eq!(
+            ChainAccountSignature::Tez([0; 21], [0; 65]).recover_account(b"hello"),
+            Err(Reason::NotImplemented)
+        );
+        assert_eq!(
+            ChainSignature::Gate([0; 65]).recover(b"hello"),
+            Err(Reason::NotImplemented)
+        );
+        assert_eq!(ChainId::Tez.signer_address(), Err(Reason::NotImplemented));
+    }
+
+    #[test]
+    fn test_matic_addresses() {
+        let matic = "0xfc04833ca66b7d6b4f540d4c2544228f64a25ac2";
+        let address = Ethereum::str_to_address(matic).unwrap();
+
+        assert_eq!(ChainId::from_str("matic"), Ok(ChainId::Matic));
+        assert_eq!(
+            ChainAccount::from_str(&format!("MATIC:{}", matic)),
+            Ok(ChainAccount::Matic(address))
+        );
+        assert_eq!(
+            String::from(ChainAccount::Matic(address)),
+            format!("MATIC:{}", matic)
+        );
+        assert_eq!(
+            String::from(ChainAsset::Matic(address)),
+            format!("MATIC:{}", matic)
+        );
+        assert_eq!(ChainAsset::Matic(address).chain_id(), ChainId::Matic);
+        assert_ne!(ChainAccount::Matic(address), ChainAccount::Eth(address));
+        assert_eq!(
+            ChainId::Matic.evm_account(address),
+            Ok(ChainAccount::Matic(address))
+        );
+        assert_eq!(
+            ChainId::Matic.evm_asset(address),
+            Ok(ChainAsset::Matic(address))
+        );
+        assert_eq!(ChainId::Dot.evm_asset(address), Err(Reason::InvalidChain));
+        assert!(ChainId::Matic.is_evm());
+        assert!(!ChainId::Sol.is_evm());
+    }
+
+    #[test]
+    fn test_matic_signatures() {
+        let chain_signature = ChainSignature::Matic([0; 65]);
+
+        assert_eq!(chain_signature.chain_id(), ChainId::Matic);
+        assert_eq!(
+            ChainAccountSignature::Matic([1; 20], [0; 65]).to_chain_signature(),
+            chain_signature
+        );
+        assert_eq!(
+            ChainId::Matic.hash_bytes(b"hello"),
+            ChainHash::Matic(<Ethereum as Chain>::hash_bytes(b"hello"))
+        );
+
+        // an ethereum signature is never accepted for the same account on matic
+        assert_eq!(
+            ChainSignature::Eth([0; 65]).verify(b"hello", ChainAccount::Matic([1; 20])),
+            Err(Reason::SignatureMismatch)
+        );
+    }
+
+    #[test]
+    fn test_evm_chains() {
+        for evm_chain in EVM_CHAINS.iter() {
+            assert_eq!(evm_chain.chain_id.evm_chain(), Ok(evm_chain));
+        }
+        assert_eq!(ChainId::Eth.evm_chain().unwrap().cursor_name, "ethereum");
+        assert_eq!(
+            ChainId::Matic.evm_chain().unwrap().message_prefix,
+            b"Matic:"
+        );
+        assert_eq!(ChainId::Dot.evm_chain(), Err(Reason::InvalidChain));
+    }
+}
@@ -13,30 +13,33 @@ use frame_support::traits::UnfilteredDispatchable;
 use frame_support::{
     sp_runtime::traits::Convert,
     storage::{
-        IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
+        with_transaction, IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap,
+        StorageMap, StorageValue, TransactionOutcome,
     },
 };
 
 use pallet_oracle::types::Price;
 
 use crate::{
-    chains::{ChainAccount, ChainAsset, ChainHash, ChainId},
+    chains::{ChainAccount, ChainAsset, ChainId},
     events::ChainLogEvent,
-    factor::Factor,
+    factor::{BigUint, Factor},
     internal, log,
     params::{MIN_TX_VALUE, TRANSFER_FEE},
     portfolio::Portfolio,
-    rates::APR,
+    rates::{InterestRateModel, APR},
     reason::{MathError, Reason},
     types::{
-        AssetAmount, AssetBalance, AssetIndex, AssetInfo, AssetQuantity, Balance, CashIndex,
-        CashPrincipal, CashPrincipalAmount, CashQuantity, GovernanceResult, NoticeId, Quantity,
-        Timestamp, USDQuantity, Units, ValidatorIdentity, CASH,
+        AssetAmount, AssetBalance, AssetIndex, AssetInfo, AssetQuantity, Balance, Bips, CashIndex,
+        CashPrincipal, CashPrincipalAmount, CashQuantity, GovernanceResult, MinerShares, NoticeId,
+        Quantity, Timestamp, USDQuantity, Uint, Units, ValidatorIdentity, CASH,
     },
-    AssetBalances, AssetsWithNonZeroBalance, BorrowIndices, CashPrincipals, CashYield,
-    CashYieldNext, ChainCashPrincipals, Config, Event, GlobalCashIndex, LastBlockTimestamp,
-    LastIndices, LastMinerSharePrincipal, LastYieldCashIndex, LastYieldTimestamp, Miner, Module,
-    SupplyIndices, SupportedAssets, TotalBorrowAssets, TotalCashPrincipal, TotalSupplyAssets,
+    AssetBalances, AssetsWithNonZeroBalance, BorrowIndices, BorrowRateRemainders, BorrowRates,
+    CashPrincipals, CashYield, CashYieldNext, ChainCashPrincipals, CloseFactor, Config, Event,
+    GlobalCashIndex, GlobalLiquidationIncentive, LastBlockTimestamp, LastIndices,
+    LastMinerSharePrincipal, LastYieldCashIndex, LastYieldTimestamp, Miner, Module,
+    SignatureQuorum, Spreads, SupplyIndices, SupportedAssets, TotalBorrowAssets,
+    TotalCashPrincipal, TotalReservePrincipal, TotalSupplyAssets,
 };
 
 #[macro_export]
@@ -103,9 +106,104 @@ pub fn get_utilization<T: Config>(asset: ChainAsset) -> Result<Factor, Reason> {
 pub fn get_rates<T: Config>(asset: ChainAsset) -> Result<(APR, APR), Reason> {
     let info = SupportedAssets::get(asset).ok_or(Reason::AssetNotSupported)?;
     let utilization = get_utilization::<T>(asset)?;
+    let shares = get_total_shares(&info, Spreads::get(asset))?;
+    let (current_rate, _) = BorrowRates::get(asset);
     Ok(info
         .rate_model
-        .get_rates(utilization, APR::ZERO, info.miner_shares)?)
+        .get_rates(utilization, current_rate, shares)?)
+}
+
+/// Return the next borrow rate to store for an asset with an adaptive rate model, if any,
+///  along with the remainder to carry into the next adjustment.
+///
+/// The rate is adjusted for the time since the last block, and the remainder keeps the adjustments
+///  too small to move the rate in a single block from being lost, so the rate moves at the model's speed.
+fn get_borrow_rate_update<T: Config>(
+    asset: ChainAsset,
+    info: &AssetInfo,
+    now: Timestamp,
+) -> Result<Option<(APR, Timestamp, Uint)>, Reason> {
+    match info.rate_model {
+        InterestRateModel::Adaptive { .. } => {
+            let utilization = get_utilization::<T>(asset)?;
+            let (current_rate, last_adjusted) = BorrowRates::get(asset);
+            let dt = if last_adjusted == 0 {
+                0
+            } else {
+                now.checked_sub(last_adjusted)
+                    .ok_or(Reason::TimeTravelNotAllowed)?
+            };
+            let (rate, remainder) = info.rate_model.get_adapted_borrow_rate(
+                utilization,
+                current_rate,
+                BorrowRateRemainders::get(asset),
+                dt,
+            )?;
+            Ok(Some((rate, now, remainder)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Return the fraction of borrower interest not paid to suppliers, i.e. the miner shares plus the spread.
+pub fn get_total_shares(info: &AssetInfo, spread: Bips) -> Result<MinerShares, MathError> {
+    let spread_shares = Factor::from_fraction(spread, 10_000)?;
+    Ok(Factor(
+        info.miner_shares
+            .0
+            .checked_add(spread_shares.0)
+            .ok_or(MathError::Overflow)?,
+    ))
+}
+
+/// Return the liquidation incentive, as a multiplier on the value of seized collateral.
+pub fn get_liquidation_incentive<T: Config>() -> Result<Factor, MathError> {
+    let incentive = Factor::from_fraction(GlobalLiquidationIncentive::get(), 10_000)?;
+    Ok(Factor(
+        Factor::ONE
+            .0
+            .checked_add(incentive.0)
+            .ok_or(MathError::Overflow)?,
+    ))
+}
+
+/// Return the most of a debt which may be repaid in a single liquidation.
+///
+/// This is limited by the close factor, unless the remainder would be too small to liquidate.
+pub fn get_max_liquidation<T: Config>(debt: Quantity) -> Result<Quantity, Reason> {
+    let close_factor = Factor::from_fraction(CloseFactor::get(), 10_000)?;
+    let max_close = debt.mul_factor(close_factor)?;
+    let remainder = Quantity::new(debt.value - max_close.value, debt.units);
+    if get_value::<T>(remainder)? < MIN_TX_VALUE {
+        Ok(debt)
+    } else {
+        Ok(max_close)
+    }
+}
+
+/// Check that a liquidation does not repay more of the debt than it is allowed to.
+fn check_close_factor<T: Config>(debt: Quantity, amount: Quantity) -> Result<(), Reason> {
+    require!(amount.value <= debt.value, Reason::LiquidateTooMuch);
+    require!(
+        amount.value <= get_max_liquidation::<T>(debt)?.value,
+        Reason::CloseFactorExceeded
+    );
+    Ok(())
+}
+
+/// Check that borrowing more of an asset does not bring its total borrows past the borrow cap.
+fn check_borrow_cap(
+    asset: AssetInfo,
+    borrow_amount: AssetQuantity,
+    total_borrow_new: AssetAmount,
+) -> Result<(), Reason> {
+    if borrow_amount.value > 0 && asset.borrow_cap > 0 {
+        require!(
+            total_borrow_new <= asset.borrow_cap,
+            Reason::BorrowCapExceeded
+        );
+    }
+    Ok(())
 }
 
 /// Return the current total borrow and total supply balances for the asset.
@@ -140,8 +238,7 @@ pub fn passes_validation_threshold(
 ) -> bool {
     // Intersection is taken for the situation when some of the signers are not currently active validators
     let valid_signers: Vec<_> = validators.intersection(&signers).collect();
-    // Using ceil(2 * validators.len() / 3)
-    valid_signers.len() >= (2 * validators.len() + 3 - 1) / 3
+    valid_signers.len() >= SignatureQuorum::get().required(validators.len())
 }
 
 // XXX use Balances instead of raw balances everywhere and put all fns on types?
@@ -262,15 +359,15 @@ fn withdraw_and_borrow_principal(
 }
 
 fn get_chain_account(chain: String, recipient: [u8; 32]) -> Result<ChainAccount, Reason> {
-    match &chain.to_ascii_uppercase()[..] {
-        "ETH" => {
-            let mut eth_recipient: [u8; 20] = [0; 20];
-            eth_recipient[..].clone_from_slice(&recipient[0..20]);
+    let chain_id = match &chain.to_ascii_uppercase()[..] {
+        "ETH" => ChainId::Eth,
+        "MATIC" => ChainId::Matic,
+        _ => return Err(Reason::InvalidChain),
+    };
+    let mut evm_recipient: [u8; 20] = [0; 20];
+    evm_recipient[..].clone_from_slice(&recipient[0..20]);
 
-            Ok(ChainAccount::Eth(eth_recipient))
-        }
-        _ => Err(Reason::InvalidChain),
-    }
+    chain_id.evm_account(evm_recipient)
 }
 
 // Protocol interface //
@@ -279,58 +376,78 @@ pub fn apply_chain_event_internal<T: Config>(event: ChainLogEvent) -> Result<(),
     log!("apply_chain_event_internal(event): {:?}", &event);
 
     match event {
-        ChainLogEvent::Eth(eth_event) => match eth_event.event {
-            ethereum_client::events::EthereumEvent::Lock {
-                asset,
-                sender,
-                chain,
-                recipient,
-                amount,
-            } => lock_internal::<T>(
-                get_asset::<T>(ChainAsset::Eth(asset))?,
-                ChainAccount::Eth(sender),
-                get_chain_account(chain, recipient)?,
-                get_quantity::<T>(ChainAsset::Eth(asset), amount)?,
-            ),
-
-            ethereum_client::events::EthereumEvent::LockCash {
-                sender,
-                chain,
-                recipient,
-                principal,
-                ..
-            } => internal::lock::lock_cash_principal_internal::<T>(
-                ChainAccount::Eth(sender),
-                get_chain_account(chain, recipient)?,
-                CashPrincipalAmount(principal),
-            ),
-
-            ethereum_client::events::EthereumEvent::ExecuteProposal {
-                title: _title,
-                extrinsics,
-            } => dispatch_extrinsics_internal::<T>(extrinsics),
-
-            ethereum_client::events::EthereumEvent::ExecTrxRequest {
-                account,
-                trx_request,
-            } => internal::exec_trx_request::exec_trx_request::<T>(
-                &trx_request[..],
-                ChainAccount::Eth(account),
-                None,
-            ),
-
-            ethereum_client::events::EthereumEvent::NoticeInvoked {
-                era_id,
-                era_index,
-                notice_hash,
-                result,
-            } => internal::notices::handle_notice_invoked::<T>(
-                ChainId::Eth,
-                NoticeId(era_id, era_index),
-                ChainHash::Eth(notice_hash),
-                result,
-            ),
-        },
+        ChainLogEvent::Eth(eth_event) => {
+            apply_evm_event_internal::<T>(ChainId::Eth, eth_event.event)
+        }
+        ChainLogEvent::Matic(matic_event) => {
+            apply_evm_event_internal::<T>(ChainId::Matic, matic_event.event)
+        }
+    }
+}
+
+/// Apply an event from the starport on an EVM chain, whose senders and assets are on that chain.
+fn apply_evm_event_internal<T: Config>(
+    chain_id: ChainId,
+    event: ethereum_client::events::EthereumEvent,
+) -> Result<(), Reason> {
+    match event {
+        ethereum_client::events::EthereumEvent::Lock {
+            asset,
+            sender,
+            chain,
+            recipient,
+            amount,
+        } => lock_internal::<T>(
+            get_asset::<T>(chain_id.evm_asset(asset)?)?,
+            chain_id.evm_account(sender)?,
+            get_chain_account(chain, recipient)?,
+            get_quantity::<T>(chain_id.evm_asset(asset)?, amount)?,
+        ),
+
+        ethereum_client::events::EthereumEvent::LockCash {
+            sender,
+            chain,
+            recipient,
+            principal,
+            ..
+        } => internal::lock::lock_cash_principal_internal::<T>(
+            chain_id.evm_account(sender)?,
+            get_chain_account(chain, recipient)?,
+            CashPrincipalAmount(principal),
+        ),
+
+        // Governance only executes proposals coming from the Ethereum starport
+        ethereum_client::events::EthereumEvent::ExecuteProposal {
+            title: _title,
+            extrinsics,
+        } => {
+            require!(chain_id == ChainId::Eth, Reason::InvalidChain);
+            dispatch_extrinsics_internal::<T>(extrinsics)
+        }
+
+        ethereum_client::events::EthereumEvent::ExecTrxRequest {
+            account,
+            trx_request,
+        } => internal::exec_trx_request::exec_trx_request::<T>(
+            &trx_request[..],
+            chain_id.evm_account(account)?,
+            None,
+        ),
+
+        ethereum_client::events::EthereumEvent::NoticeInvoked {
+            era_id,
+            era_index,
+            notice_hash,
+            result,
+        } => internal::notices::handle_notice_invoked::<T>(
+            chain_id,
+            NoticeId(era_id, era_index),
+            chain_id.evm_hash(notice_hash)?,
+            result,
+        ),
+
+        // Logs the validators couldn't decode still get recorded, so they aren't lost silently
+        ethereum_client::events::EthereumEvent::Unknown { .. } => Err(Reason::UndecodableEvent),
     }
 }
 
@@ -434,10 +551,14 @@ pub fn extract_internal<T: Config>(
     holder: ChainAccount,
     recipient: ChainAccount,
     amount: AssetQuantity,
+    liquidity_check: LiquidityCheck,
 ) -> Result<(), Reason> {
+    // Gateway accounts hold balances natively, there is nothing to extract to
+    require!(recipient.chain_id() != ChainId::Gate, Reason::InvalidChain);
     require_min_tx_value!(get_value::<T>(amount)?);
     require!(
-        has_liquidity_to_reduce_asset::<T>(holder, asset, amount)?,
+        liquidity_check == LiquidityCheck::Deferred
+            || has_liquidity_to_reduce_asset::<T>(holder, asset, amount)?,
         Reason::InsufficientLiquidity
     );
 
@@ -453,6 +574,7 @@ pub fn extract_internal<T: Config>(
     )?;
     let total_borrow_new =
         add_amount_to_raw(TotalBorrowAssets::get(asset.asset), holder_borrow_amount)?;
+    check_borrow_cap(asset, holder_borrow_amount, total_borrow_new)?;
 
     let (cash_principal_post, last_index_post) = effect_of_asset_interest_internal(
         asset,
@@ -466,6 +588,9 @@ pub fn extract_internal<T: Config>(
         Reason::InsufficientTotalFunds
     );
 
+    // Queue the notice first, it fails without effects if the recipient chain can't receive it
+    internal::notices::dispatch_extraction_notice::<T>(asset.asset, recipient, amount)?;
+
     LastIndices::insert(asset.asset, holder, last_index_post);
     CashPrincipals::insert(holder, cash_principal_post);
     TotalSupplyAssets::insert(asset.asset, total_supply_new);
@@ -473,8 +598,6 @@ pub fn extract_internal<T: Config>(
 
     set_asset_balance_internal::<T>(asset.asset, holder, holder_asset_new);
 
-    internal::notices::dispatch_extraction_notice::<T>(asset.asset, recipient, amount);
-
     <Module<T>>::deposit_event(Event::Extract(asset.asset, holder, recipient, amount.value));
 
     Ok(())
@@ -484,13 +607,17 @@ pub fn extract_cash_principal_internal<T: Config>(
     holder: ChainAccount,
     recipient: ChainAccount,
     principal: CashPrincipalAmount,
+    liquidity_check: LiquidityCheck,
 ) -> Result<(), Reason> {
+    // Gateway accounts hold balances natively, there is nothing to extract to
+    require!(recipient.chain_id() != ChainId::Gate, Reason::InvalidChain);
     let index: CashIndex = GlobalCashIndex::get();
     let amount = index.cash_quantity(principal)?;
 
     require_min_tx_value!(get_value::<T>(amount)?);
     require!(
-        has_liquidity_to_reduce_cash::<T>(holder, amount)?,
+        liquidity_check == LiquidityCheck::Deferred
+            || has_liquidity_to_reduce_cash::<T>(holder, amount)?,
         Reason::InsufficientLiquidity
     );
 
@@ -505,12 +632,13 @@ pub fn extract_cash_principal_internal<T: Config>(
         add_principal_amounts(TotalCashPrincipal::get(), holder_borrow_principal)?;
     let holder_cash_principal_new = holder_cash_principal.sub_amount(principal)?;
 
+    // Queue the notice first, it fails without effects if the recipient chain can't receive it
+    internal::notices::dispatch_cash_extraction_notice::<T>(recipient, principal)?;
+
     ChainCashPrincipals::insert(chain_id, chain_cash_principal_new);
     CashPrincipals::insert(holder, holder_cash_principal_new);
     TotalCashPrincipal::put(total_cash_principal_new);
 
-    internal::notices::dispatch_cash_extraction_notice::<T>(recipient, principal);
-
     <Module<T>>::deposit_event(Event::ExtractCash(holder, recipient, principal, index));
 
     Ok(())
@@ -521,6 +649,7 @@ pub fn transfer_internal<T: Config>(
     sender: ChainAccount,
     recipient: ChainAccount,
     amount: AssetQuantity,
+    liquidity_check: LiquidityCheck,
 ) -> Result<(), Reason> {
     let miner = get_some_miner::<T>();
     let index = GlobalCashIndex::get();
@@ -528,7 +657,8 @@ pub fn transfer_internal<T: Config>(
     require!(sender != recipient, Reason::SelfTransfer);
     require_min_tx_value!(get_value::<T>(amount)?);
     require!(
-        has_liquidity_to_reduce_asset_with_fee::<T>(sender, asset, amount, TRANSFER_FEE)?,
+        liquidity_check == LiquidityCheck::Deferred
+            || has_liquidity_to_reduce_asset_with_fee::<T>(sender, asset, amount, TRANSFER_FEE)?,
         Reason::InsufficientLiquidity
     );
 
@@ -562,6 +692,7 @@ pub fn transfer_internal<T: Config>(
         recipient_repay_amount,
         Reason::RepayTooMuch,
     )?;
+    check_borrow_cap(asset, sender_borrow_amount, total_borrow_new)?;
     let total_cash_principal_new = sub_principal_amounts(
         add_principal_amounts(TotalCashPrincipal::get(), sender_borrow_principal)?,
         miner_repay_principal,
@@ -610,6 +741,7 @@ pub fn transfer_cash_principal_internal<T: Config>(
     sender: ChainAccount,
     recipient: ChainAccount,
     principal: CashPrincipalAmount,
+    liquidity_check: LiquidityCheck,
 ) -> Result<(), Reason> {
     let miner = get_some_miner::<T>();
     let index: CashIndex = GlobalCashIndex::get();
@@ -618,7 +750,8 @@ pub fn transfer_cash_principal_internal<T: Config>(
     require!(sender != recipient, Reason::SelfTransfer);
     require_min_tx_value!(get_value::<T>(amount)?);
     require!(
-        has_liquidity_to_reduce_cash::<T>(sender, amount.add(TRANSFER_FEE)?)?,
+        liquidity_check == LiquidityCheck::Deferred
+            || has_liquidity_to_reduce_cash::<T>(sender, amount.add(TRANSFER_FEE)?)?,
         Reason::InsufficientLiquidity
     );
 
@@ -661,14 +794,17 @@ pub fn liquidate_internal<T: Config>(
     liquidator: ChainAccount,
     borrower: ChainAccount,
     amount: AssetQuantity,
+    liquidity_check: LiquidityCheck,
 ) -> Result<(), Reason> {
     require!(borrower != liquidator, Reason::SelfTransfer);
     require!(asset != collateral_asset, Reason::InKindLiquidation);
     require_min_tx_value!(get_value::<T>(amount)?);
 
-    let liquidation_incentive = Factor::from_nominal("1.08"); // XXX spec first
+    let liquidation_incentive = get_liquidation_incentive::<T>()?;
     let liquidator_asset = AssetBalances::get(asset.asset, liquidator);
     let borrower_asset = AssetBalances::get(asset.asset, borrower);
+    check_close_factor::<T>(asset.as_quantity(neg_balance(borrower_asset)), amount)?;
+
     let liquidator_collateral_asset = AssetBalances::get(collateral_asset.asset, liquidator);
     let borrower_collateral_asset = AssetBalances::get(collateral_asset.asset, borrower);
     let seize_amount = amount
@@ -680,20 +816,21 @@ pub fn liquidate_internal<T: Config>(
         )?;
 
     require!(
-        has_liquidity_to_reduce_asset_with_added_collateral::<T>(
-            liquidator,
-            asset,
-            amount,
-            collateral_asset,
-            seize_amount,
-        )?,
+        liquidity_check == LiquidityCheck::Deferred
+            || has_liquidity_to_reduce_asset_with_added_collateral::<T>(
+                liquidator,
+                asset,
+                amount,
+                collateral_asset,
+                seize_amount,
+            )?,
         Reason::InsufficientLiquidity
     );
 
     let (borrower_repay_amount, _borrower_supply_amount) =
-        repay_and_supply_amount(liquidator_asset, amount);
+        repay_and_supply_amount(borrower_asset, amount);
     let (liquidator_withdraw_amount, liquidator_borrow_amount) =
-        withdraw_and_borrow_amount(borrower_asset, amount);
+        withdraw_and_borrow_amount(liquidator_asset, amount);
     let (borrower_collateral_withdraw_amount, _borrower_collateral_borrow_amount) =
         withdraw_and_borrow_amount(borrower_collateral_asset, seize_amount);
     let (liquidator_collateral_repay_amount, liquidator_collateral_supply_amount) =
@@ -719,6 +856,7 @@ pub fn liquidate_internal<T: Config>(
         borrower_repay_amount,
         Reason::RepayTooMuch,
     )?;
+    check_borrow_cap(asset, liquidator_borrow_amount, total_borrow_new)?;
     let total_collateral_supply_new = sub_amount_from_raw(
         add_amount_to_raw(
             TotalSupplyAssets::get(collateral_asset.asset),
@@ -814,6 +952,7 @@ pub fn liquidate_cash_principal_internal<T: Config>(
     liquidator: ChainAccount,
     borrower: ChainAccount,
     principal: CashPrincipalAmount,
+    liquidity_check: LiquidityCheck,
 ) -> Result<(), Reason> {
     let index = GlobalCashIndex::get();
     let amount = index.cash_quantity(principal)?;
@@ -821,9 +960,14 @@ pub fn liquidate_cash_principal_internal<T: Config>(
     require!(borrower != liquidator, Reason::SelfTransfer);
     require_min_tx_value!(get_value::<T>(amount)?);
 
-    let liquidation_incentive = Factor::from_nominal("1.08"); // XXX spec first
+    let liquidation_incentive = get_liquidation_incentive::<T>()?;
     let liquidator_cash_principal = CashPrincipals::get(liquidator);
     let borrower_cash_principal = CashPrincipals::get(borrower);
+    check_close_factor::<T>(
+        index.cash_quantity(borrower_cash_principal.amount_repayable()?)?,
+        amount,
+    )?;
+
     let liquidator_collateral_asset = AssetBalances::get(collateral_asset.asset, liquidator);
     let borrower_collateral_asset = AssetBalances::get(collateral_asset.asset, borrower);
     let seize_amount = amount
@@ -835,12 +979,13 @@ pub fn liquidate_cash_principal_internal<T: Config>(
         )?;
 
     require!(
-        has_liquidity_to_reduce_cash_with_added_collateral::<T>(
-            liquidator,
-            amount,
-            collateral_asset,
-            seize_amount,
-        )?,
+        liquidity_check == LiquidityCheck::Deferred
+            || has_liquidity_to_reduce_cash_with_added_collateral::<T>(
+                liquidator,
+                amount,
+                collateral_asset,
+                seize_amount,
+            )?,
         Reason::InsufficientLiquidity
     );
 
@@ -939,15 +1084,18 @@ pub fn liquidate_cash_collateral_internal<T: Config>(
     liquidator: ChainAccount,
     borrower: ChainAccount,
     amount: AssetQuantity,
+    liquidity_check: LiquidityCheck,
 ) -> Result<(), Reason> {
     let index = GlobalCashIndex::get();
 
     require!(borrower != liquidator, Reason::SelfTransfer);
     require_min_tx_value!(get_value::<T>(amount)?);
 
-    let liquidation_incentive = Factor::from_nominal("1.08"); // XXX spec first
+    let liquidation_incentive = get_liquidation_incentive::<T>()?;
     let liquidator_asset = AssetBalances::get(asset.asset, liquidator);
     let borrower_asset = AssetBalances::get(asset.asset, borrower);
+    check_close_factor::<T>(asset.as_quantity(neg_balance(borrower_asset)), amount)?;
+
     let liquidator_cash_principal = CashPrincipals::get(liquidator);
     let borrower_cash_principal = CashPrincipals::get(borrower);
     let seize_amount = amount
@@ -957,19 +1105,20 @@ pub fn liquidate_cash_collateral_internal<T: Config>(
     let seize_principal = index.cash_principal_amount(seize_amount)?;
 
     require!(
-        has_liquidity_to_reduce_asset_with_added_cash::<T>(
-            liquidator,
-            asset,
-            amount,
-            seize_amount
-        )?,
+        liquidity_check == LiquidityCheck::Deferred
+            || has_liquidity_to_reduce_asset_with_added_cash::<T>(
+                liquidator,
+                asset,
+                amount,
+                seize_amount
+            )?,
         Reason::InsufficientLiquidity
     );
 
     let (borrower_repay_amount, _borrower_supply_amount) =
-        repay_and_supply_amount(liquidator_asset, amount);
+        repay_and_supply_amount(borrower_asset, amount);
     let (liquidator_withdraw_amount, liquidator_borrow_amount) =
-        withdraw_and_borrow_amount(borrower_asset, amount);
+        withdraw_and_borrow_amount(liquidator_asset, amount);
     let (borrower_collateral_withdraw_principal, _borrower_collateral_borrow_principal) =
         withdraw_and_borrow_principal(borrower_cash_principal, seize_principal);
     let (liquidator_collateral_repay_principal, _liquidator_collateral_supply_principal) =
@@ -996,6 +1145,7 @@ pub fn liquidate_cash_collateral_internal<T: Config>(
         borrower_repay_amount,
         Reason::RepayTooMuch,
     )?;
+    check_borrow_cap(asset, liquidator_borrow_amount, total_borrow_new)?;
     let total_cash_principal_new = sub_principal_amounts(
         TotalCashPrincipal::get(),
         liquidator_collateral_repay_principal,
@@ -1042,6 +1192,37 @@ pub fn liquidate_cash_collateral_internal<T: Config>(
 
 // Liquidity Checks //
 
+/// Whether a protocol operation checks the holder's liquidity itself, or leaves it to the caller.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LiquidityCheck {
+    Immediate,
+    Deferred,
+}
+
+/// Runs the operations of a batch with deferred liquidity checks, all-or-nothing.
+///
+/// The holder's liquidity is checked once all operations have succeeded, and any failure
+///  reverts every change made by the batch.
+pub fn batch_internal<T: Config>(
+    holder: ChainAccount,
+    operations: impl FnOnce(LiquidityCheck) -> Result<(), Reason>,
+) -> Result<(), Reason> {
+    with_transaction(|| {
+        let result = operations(LiquidityCheck::Deferred).and_then(|()| {
+            require!(
+                get_liquidity::<T>(holder)?.value >= 0,
+                Reason::InsufficientLiquidity
+            );
+            Ok(())
+        });
+
+        match result {
+            Ok(()) => TransactionOutcome::Commit(Ok(())),
+            Err(err) => TransactionOutcome::Rollback(Err(err)),
+        }
+    })
+}
+
 /// Calculates if an account will remain solvent after reducing asset by amount.
 pub fn has_liquidity_to_reduce_asset<T: Config>(
     account: ChainAccount,
@@ -1212,6 +1393,10 @@ pub fn on_initialize_internal<T: Config>(
     if now == 0 {
         return Err(Reason::TimeTravelNotAllowed);
     }
+
+    // Trip or reset any price circuit breakers before anything uses the prices
+    pallet_oracle::oracle::update_price_breakers::<T>(now);
+
     if last_yield_timestamp == 0 || last_block_timestamp == 0 {
         // this is the first time we have seen a valid time, set it for LastYield and LastBlock
         if last_yield_timestamp == 0 {
@@ -1225,15 +1410,14 @@ pub fn on_initialize_internal<T: Config>(
         return Ok(0);
     }
 
-    // Iterate through listed assets, summing the CASH principal they generated/paid last block
-    let dt_since_last_block = now
-        .checked_sub(last_block_timestamp)
-        .ok_or(Reason::TimeTravelNotAllowed)?;
+    // Iterate through listed assets, summing the CASH principal they generated/paid since last accrued
+    require!(now >= last_block_timestamp, Reason::TimeTravelNotAllowed);
     let dt_since_last_yield = now
         .checked_sub(last_yield_timestamp)
         .ok_or(Reason::TimeTravelNotAllowed)?;
     let mut cash_principal_supply_increase = CashPrincipalAmount::ZERO;
     let mut cash_principal_borrow_increase = CashPrincipalAmount::ZERO;
+    let mut cash_principal_reserve_increase = CashPrincipalAmount::ZERO;
 
     let last_block_cash_index = GlobalCashIndex::get();
     let last_yield_cash_index = LastYieldCashIndex::get();
@@ -1241,15 +1425,38 @@ pub fn on_initialize_internal<T: Config>(
     let price_cash = get_price::<T>(CASH)?;
 
     let mut asset_updates: Vec<(ChainAsset, AssetIndex, AssetIndex)> = Vec::new();
+    let mut rate_updates: Vec<(ChainAsset, (APR, Timestamp, Uint))> = Vec::new();
+    let mut deferred_accruals: Vec<(ChainAsset, Timestamp)> = Vec::new();
     for (asset, asset_info) in SupportedAssets::iter() {
         let (asset_cost, asset_yield) = crate::core::get_rates::<T>(asset)?;
+        if let Some(rate_update) = get_borrow_rate_update::<T>(asset, &asset_info, now)? {
+            rate_updates.push((asset, rate_update));
+        }
+        let accrued_at = DeferredAccrualTimestamps::get(asset).unwrap_or(last_block_timestamp);
         let asset_units = asset_info.units();
-        let price_asset = get_price::<T>(asset_units)?;
+        let price_asset = match get_price::<T>(asset_units) {
+            Ok(price) => price,
+            Err(Reason::OracleError(err)) => {
+                // Do not accrue interest on this asset against an unhealthy price, but keep accruing
+                //  on the others, the first block with a healthy price accrues it for the whole time
+                log!(
+                    "Deferring interest accrual on {:?} while its price is unhealthy: {:?}",
+                    asset,
+                    err
+                );
+                deferred_accruals.push((asset, accrued_at));
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        let dt_since_accrual = now
+            .checked_sub(accrued_at)
+            .ok_or(Reason::TimeTravelNotAllowed)?;
         let price_ratio = ratio(price_asset, price_cash)?;
         let cash_borrow_principal_per_asset = last_block_cash_index
-            .cash_principal_per_asset(asset_cost.simple(dt_since_last_block)?, price_ratio)?;
+            .cash_principal_per_asset(asset_cost.simple(dt_since_accrual)?, price_ratio)?;
         let cash_hold_principal_per_asset = last_block_cash_index
-            .cash_principal_per_asset(asset_yield.simple(dt_since_last_block)?, price_ratio)?;
+            .cash_principal_per_asset(asset_yield.simple(dt_since_accrual)?, price_ratio)?;
 
         let supply_index = SupplyIndices::get(&asset);
         let borrow_index = BorrowIndices::get(&asset);
@@ -1260,8 +1467,17 @@ pub fn on_initialize_internal<T: Config>(
         let borrow_asset = Quantity::new(TotalBorrowAssets::get(asset), asset_units);
         cash_principal_supply_increase = cash_principal_supply_increase
             .add(cash_hold_principal_per_asset.cash_principal_amount(supply_asset)?)?;
-        cash_principal_borrow_increase = cash_principal_borrow_increase
-            .add(cash_borrow_principal_per_asset.cash_principal_amount(borrow_asset)?)?;
+        let asset_borrow_increase =
+            cash_borrow_principal_per_asset.cash_principal_amount(borrow_asset)?;
+        cash_principal_borrow_increase =
+            cash_principal_borrow_increase.add(asset_borrow_increase)?;
+        cash_principal_reserve_increase =
+            cash_principal_reserve_increase.add(CashPrincipalAmount(
+                BigUint::from_uint(asset_borrow_increase.0)
+                    .mul_uint(Spreads::get(&asset))
+                    .div_uint(10_000)?
+                    .to_uint()?,
+            ))?;
 
         asset_updates.push((asset.clone(), supply_index_new, borrow_index_new));
     }
@@ -1279,8 +1495,11 @@ pub fn on_initialize_internal<T: Config>(
     }
     let cash_index_new = last_yield_cash_index.increment(increment)?; // XXX
     let total_cash_principal_new = total_cash_principal.add(cash_principal_borrow_increase)?;
-    let miner_share_principal =
+    let non_supplier_principal =
         cash_principal_borrow_increase.sub(cash_principal_supply_increase)?;
+    let reserve_principal = min(cash_principal_reserve_increase, non_supplier_principal);
+    let miner_share_principal = non_supplier_principal.sub(reserve_principal)?;
+    let total_reserve_principal_new = TotalReservePrincipal::get().add(reserve_principal)?;
 
     let last_miner = get_some_miner::<T>(); // Miner not yet set for this block, so this is "last miner"
     let last_miner_share_principal = LastMinerSharePrincipal::get();
@@ -1300,10 +1519,21 @@ pub fn on_initialize_internal<T: Config>(
     for (asset, new_supply_index, new_borrow_index) in asset_updates.drain(..) {
         SupplyIndices::insert(asset.clone(), new_supply_index);
         BorrowIndices::insert(asset, new_borrow_index);
+        DeferredAccrualTimestamps::remove(asset);
+    }
+
+    for (asset, accrued_at) in deferred_accruals.drain(..) {
+        DeferredAccrualTimestamps::insert(asset, accrued_at);
+    }
+
+    for (asset, (rate, adjusted_at, remainder)) in rate_updates.drain(..) {
+        BorrowRates::insert(asset, (rate, adjusted_at));
+        BorrowRateRemainders::insert(asset, remainder);
     }
 
     GlobalCashIndex::put(cash_index_new);
     TotalCashPrincipal::put(total_cash_principal_new);
+    TotalReservePrincipal::put(total_reserve_principal_new);
     LastMinerSharePrincipal::put(miner_share_principal);
     LastBlockTimestamp::put(now);
 
@@ -1492,12 +1722,65 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_get_borrow_rate_update() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let asset = Eth;
+            let model = InterestRateModel::Adaptive {
+                min_rate: 100.into(),
+                max_rate: 2000.into(),
+                target_utilization: Factor::from_nominal("0.8"),
+                adjustment_speed: APR::from_nominal("1"),
+            };
+            let asset_info = AssetInfo {
+                rate_model: InterestRateModel::new_kink(0, 1000, Factor::from_nominal("0.9"), 2000),
+                ..AssetInfo::minimal(asset, ETH)
+            };
+
+            // 90% utilization
+            SupportedAssets::insert(&asset, asset_info);
+            TotalSupplyAssets::insert(&asset, 100);
+            TotalBorrowAssets::insert(&asset, 90);
+
+            // switching to an adaptive model starts from the previous rate
+            assert_ok!(CashModule::set_rate_model(Origin::root(), asset, model));
+            assert_eq!(BorrowRates::get(asset), (1000.into(), 0));
+
+            let asset_info = SupportedAssets::get(asset).unwrap();
+            let now = 1_000;
+            assert_eq!(
+                get_borrow_rate_update::<Test>(asset, &asset_info, now)?,
+                Some((1000.into(), now, 0))
+            );
+
+            // not enough time to move the rate, so the adjustment is carried in the remainder
+            BorrowRates::insert(asset, (APR::from(1000), now));
+            let (rate, adjusted_at, remainder) =
+                get_borrow_rate_update::<Test>(asset, &asset_info, now + 1)?.unwrap();
+            assert_eq!((rate, adjusted_at), (1000.into(), now + 1));
+            assert!(remainder > 0);
+
+            let later = now + MILLISECONDS_PER_YEAR / 2;
+            assert_eq!(
+                get_borrow_rate_update::<Test>(asset, &asset_info, later)?,
+                Some((1500.into(), later, 0))
+            );
+
+            let (borrow_rate, _supply_rate) = crate::core::get_rates::<Test>(asset)?;
+            assert_eq!(borrow_rate, 1000.into());
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_has_liquidity_to_reduce_cash() -> Result<(), Reason> {
         const BAT: Units = Units::from_ticker_str("BAT", 18);
         let asset = ChainAsset::from_str("Eth:0x0d8775f648430679a709e98d2b0cb6250d2887ef")?;
         let asset_info = AssetInfo {
-            liquidity_factor: LiquidityFactor::from_nominal("0.6543"),
+            collateral_factor: LiquidityFactor::from_nominal("0.6543"),
+            borrow_factor: LiquidityFactor::from_nominal("0.6543"),
             ..AssetInfo::minimal(asset, BAT)
         };
 
@@ -1521,6 +1804,214 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_check_close_factor() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            pallet_oracle::Prices::insert(
+                ETH.ticker,
+                Price::from_nominal(ETH.ticker, "2000").value,
+            );
+
+            let debt = Quantity::from_nominal("10", ETH);
+            assert_eq!(
+                get_max_liquidation::<Test>(debt)?,
+                Quantity::from_nominal("5", ETH)
+            );
+            assert_eq!(
+                check_close_factor::<Test>(debt, Quantity::from_nominal("5", ETH)),
+                Ok(())
+            );
+            assert_eq!(
+                check_close_factor::<Test>(debt, Quantity::from_nominal("6", ETH)),
+                Err(Reason::CloseFactorExceeded)
+            );
+            assert_eq!(
+                check_close_factor::<Test>(debt, Quantity::from_nominal("11", ETH)),
+                Err(Reason::LiquidateTooMuch)
+            );
+
+            // the remainder would be dust, so the debt may be closed entirely
+            let dust_debt = Quantity::from_nominal("0.0008", ETH);
+            assert_eq!(get_max_liquidation::<Test>(dust_debt)?, dust_debt);
+            assert_eq!(check_close_factor::<Test>(dust_debt, dust_debt), Ok(()));
+
+            CloseFactor::put(10_000);
+            assert_eq!(get_max_liquidation::<Test>(debt)?, debt);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_liquidate_internal_close_factor() -> Result<(), Reason> {
+        const BAT: Units = Units::from_ticker_str("BAT", 18);
+        let eth = ChainAsset::Eth([238; 20]);
+        let eth_info = AssetInfo::minimal(eth, ETH);
+        let bat = ChainAsset::Eth([1; 20]);
+        let bat_info = AssetInfo::minimal(bat, BAT);
+
+        new_test_ext().execute_with(|| {
+            let liquidator = ChainAccount::Eth([1; 20]);
+            let borrower = ChainAccount::Eth([2; 20]);
+            pallet_oracle::Prices::insert(
+                ETH.ticker,
+                Price::from_nominal(ETH.ticker, "2000").value,
+            );
+            AssetBalances::insert(&eth, &borrower, Balance::from_nominal("-10", ETH).value);
+
+            assert_eq!(
+                liquidate_internal::<Test>(
+                    eth_info,
+                    bat_info,
+                    liquidator,
+                    borrower,
+                    Quantity::from_nominal("6", ETH),
+                    LiquidityCheck::Immediate
+                ),
+                Err(Reason::CloseFactorExceeded)
+            );
+            assert_eq!(
+                liquidate_cash_collateral_internal::<Test>(
+                    eth_info,
+                    liquidator,
+                    borrower,
+                    Quantity::from_nominal("11", ETH),
+                    LiquidityCheck::Immediate
+                ),
+                Err(Reason::LiquidateTooMuch)
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_liquidate_internal_borrow_cap() -> Result<(), Reason> {
+        const BAT: Units = Units::from_ticker_str("BAT", 18);
+        let eth = ChainAsset::Eth([238; 20]);
+        let eth_info = AssetInfo {
+            collateral_factor: LiquidityFactor::from_nominal("0.8"),
+            borrow_factor: LiquidityFactor::from_nominal("0.8"),
+            borrow_cap: Quantity::from_nominal("5", ETH).value,
+            ..AssetInfo::minimal(eth, ETH)
+        };
+        let bat = ChainAsset::Eth([1; 20]);
+        let bat_info = AssetInfo {
+            collateral_factor: LiquidityFactor::from_nominal("0.8"),
+            borrow_factor: LiquidityFactor::from_nominal("0.8"),
+            ..AssetInfo::minimal(bat, BAT)
+        };
+
+        new_test_ext().execute_with(|| {
+            let liquidator = ChainAccount::Eth([1; 20]);
+            let borrower = ChainAccount::Eth([2; 20]);
+            pallet_oracle::Prices::insert(
+                ETH.ticker,
+                Price::from_nominal(ETH.ticker, "2000").value,
+            );
+            pallet_oracle::Prices::insert(BAT.ticker, Price::from_nominal(BAT.ticker, "1").value);
+            SupportedAssets::insert(&eth, eth_info);
+            SupportedAssets::insert(&bat, bat_info);
+            CashPrincipals::insert(&liquidator, CashPrincipal::from_nominal("100000"));
+            AssetBalances::insert(&eth, &borrower, Balance::from_nominal("-10", ETH).value);
+            AssetBalances::insert(&bat, &borrower, Balance::from_nominal("100000", BAT).value);
+            TotalBorrowAssets::insert(&eth, Quantity::from_nominal("10", ETH).value);
+            TotalSupplyAssets::insert(&bat, Quantity::from_nominal("100000", BAT).value);
+
+            // the liquidator has no eth, so they must borrow it to repay, past the cap
+            assert_eq!(
+                liquidate_internal::<Test>(
+                    eth_info,
+                    bat_info,
+                    liquidator,
+                    borrower,
+                    Quantity::from_nominal("5", ETH),
+                    LiquidityCheck::Immediate
+                ),
+                Err(Reason::BorrowCapExceeded)
+            );
+            assert_eq!(
+                liquidate_cash_collateral_internal::<Test>(
+                    eth_info,
+                    liquidator,
+                    borrower,
+                    Quantity::from_nominal("5", ETH),
+                    LiquidityCheck::Immediate
+                ),
+                Err(Reason::BorrowCapExceeded)
+            );
+
+            // but may once the cap allows it, moving the debt to the liquidator
+            let eth_info = AssetInfo {
+                borrow_cap: Quantity::from_nominal("10", ETH).value,
+                ..eth_info
+            };
+            SupportedAssets::insert(&eth, eth_info);
+            liquidate_internal::<Test>(
+                eth_info,
+                bat_info,
+                liquidator,
+                borrower,
+                Quantity::from_nominal("5", ETH),
+                LiquidityCheck::Immediate,
+            )?;
+            assert_eq!(
+                AssetBalances::get(&eth, &liquidator),
+                Balance::from_nominal("-5", ETH).value
+            );
+            assert_eq!(
+                AssetBalances::get(&eth, &borrower),
+                Balance::from_nominal("-5", ETH).value
+            );
+            assert_eq!(
+                TotalBorrowAssets::get(&eth),
+                Quantity::from_nominal("10", ETH).value
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_borrow_rate_does_not_bank_time_at_target() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            let asset = Eth;
+            let asset_info = AssetInfo {
+                rate_model: InterestRateModel::Adaptive {
+                    min_rate: 100.into(),
+                    max_rate: 2000.into(),
+                    target_utilization: Factor::from_nominal("0.8"),
+                    adjustment_speed: APR::from_nominal("1"),
+                },
+                ..AssetInfo::minimal(asset, ETH)
+            };
+            SupportedAssets::insert(&asset, asset_info);
+            TotalSupplyAssets::insert(&asset, 100);
+            TotalBorrowAssets::insert(&asset, 80);
+
+            // half a year at the target leaves the rate alone, but uses up the time
+            let start = 1_000;
+            let later = start + MILLISECONDS_PER_YEAR / 2;
+            BorrowRates::insert(asset, (APR::from(1000), start));
+            assert_eq!(
+                get_borrow_rate_update::<Test>(asset, &asset_info, later)?,
+                Some((1000.into(), later, 0))
+            );
+            BorrowRates::insert(asset, (APR::from(1000), later));
+
+            // so a single block off target only moves the rate by a single block's worth
+            TotalBorrowAssets::insert(&asset, 90);
+            let next_block = later + 6_000;
+            let (rate, adjusted_at, remainder) =
+                get_borrow_rate_update::<Test>(asset, &asset_info, next_block)?.unwrap();
+            assert_eq!(rate, 1000.into());
+            assert_eq!(adjusted_at, next_block);
+            assert!(remainder > 0);
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_on_initialize() {
         new_test_ext().execute_with(|| {
@@ -1590,6 +2081,142 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_on_initialize_defers_accrual_on_unhealthy_price() {
+        new_test_ext().execute_with(|| {
+            let asset = Eth;
+            let asset_info = AssetInfo {
+                rate_model: InterestRateModel::new_kink(0, 2500, Factor::from_nominal("0.5"), 5000),
+                miner_shares: MinerShares::from_nominal("0.02"),
+                ..AssetInfo::minimal(asset, ETH)
+            };
+            let healthy_asset = Uni;
+            let healthy_asset_info = AssetInfo {
+                rate_model: InterestRateModel::new_kink(0, 2500, Factor::from_nominal("0.5"), 5000),
+                ..AssetInfo::minimal(healthy_asset, UNI)
+            };
+            let last_yield_timestamp = 10;
+            let halfway = last_yield_timestamp + MILLISECONDS_PER_YEAR / 8;
+            let now = last_yield_timestamp + MILLISECONDS_PER_YEAR / 4;
+
+            SupportedAssets::insert(&asset, asset_info);
+            SupportedAssets::insert(&healthy_asset, healthy_asset_info);
+            GlobalCashIndex::put(CashIndex::from_nominal("1.123"));
+            LastYieldCashIndex::put(CashIndex::from_nominal("1.123"));
+            SupplyIndices::insert(&asset, AssetIndex::from_nominal("1234"));
+            BorrowIndices::insert(&asset, AssetIndex::from_nominal("1345"));
+            BorrowIndices::insert(&healthy_asset, AssetIndex::from_nominal("1345"));
+            TotalSupplyAssets::insert(asset.clone(), asset_info.as_quantity_nominal("300").value);
+            TotalBorrowAssets::insert(asset.clone(), asset_info.as_quantity_nominal("150").value);
+            TotalSupplyAssets::insert(
+                healthy_asset.clone(),
+                healthy_asset_info.as_quantity_nominal("300").value,
+            );
+            TotalBorrowAssets::insert(
+                healthy_asset.clone(),
+                healthy_asset_info.as_quantity_nominal("150").value,
+            );
+            TotalCashPrincipal::put(CashPrincipalAmount::from_nominal("450000"));
+            LastBlockTimestamp::put(last_yield_timestamp);
+            pallet_oracle::Prices::insert(
+                asset_info.ticker,
+                1450_000000 as pallet_oracle::types::AssetPrice,
+            );
+            pallet_oracle::Prices::insert(
+                healthy_asset_info.ticker,
+                20_000000 as pallet_oracle::types::AssetPrice,
+            );
+            pallet_oracle::PriceBreakers::insert(
+                asset_info.ticker,
+                pallet_oracle::error::OracleError::ImplausiblePrice,
+            );
+
+            assert_eq!(
+                on_initialize_internal::<Test>(halfway, last_yield_timestamp, last_yield_timestamp),
+                Ok(0)
+            );
+            assert_eq!(LastBlockTimestamp::get(), halfway);
+            assert_eq!(BorrowIndices::get(&asset), AssetIndex::from_nominal("1345"));
+            assert_eq!(
+                DeferredAccrualTimestamps::get(&asset),
+                Some(last_yield_timestamp)
+            );
+            assert!(BorrowIndices::get(&healthy_asset) > AssetIndex::from_nominal("1345"));
+            assert_eq!(DeferredAccrualTimestamps::get(&healthy_asset), None);
+
+            // once the price is healthy again, the interest since it was deferred is accrued
+            pallet_oracle::PriceBreakers::remove(asset_info.ticker);
+            assert_eq!(
+                on_initialize_internal::<Test>(
+                    now,
+                    last_yield_timestamp,
+                    LastBlockTimestamp::get()
+                ),
+                Ok(0)
+            );
+            assert_eq!(LastBlockTimestamp::get(), now);
+            assert_eq!(
+                BorrowIndices::get(&asset),
+                AssetIndex::from_nominal("1425.699020480854853072")
+            );
+            assert_eq!(DeferredAccrualTimestamps::get(&asset), None);
+        });
+    }
+
+    #[test]
+    fn test_on_initialize_with_spread() {
+        new_test_ext().execute_with(|| {
+            let asset = Eth;
+            let asset_info = AssetInfo {
+                rate_model: InterestRateModel::new_kink(0, 2500, Factor::from_nominal("0.5"), 5000),
+                miner_shares: MinerShares::from_nominal("0.02"),
+                ..AssetInfo::minimal(asset, ETH)
+            };
+            let last_yield_timestamp = 10;
+            let now = last_yield_timestamp + MILLISECONDS_PER_YEAR / 4; // 3 months go by
+
+            SupportedAssets::insert(&asset, asset_info);
+            Spreads::insert(&asset, 1000); // 10% of borrower interest to the protocol
+            GlobalCashIndex::put(CashIndex::from_nominal("1.123"));
+            LastYieldCashIndex::put(CashIndex::from_nominal("1.123"));
+            SupplyIndices::insert(&asset, AssetIndex::from_nominal("1234"));
+            BorrowIndices::insert(&asset, AssetIndex::from_nominal("1345"));
+            TotalSupplyAssets::insert(asset.clone(), asset_info.as_quantity_nominal("300").value);
+            TotalBorrowAssets::insert(asset.clone(), asset_info.as_quantity_nominal("150").value);
+            CashYield::put(APR::from_nominal("0.24"));
+            TotalCashPrincipal::put(CashPrincipalAmount::from_nominal("450000"));
+            pallet_oracle::Prices::insert(
+                asset_info.ticker,
+                1450_000000 as pallet_oracle::types::AssetPrice,
+            ); // $1450 eth
+
+            let result =
+                on_initialize_internal::<Test>(now, last_yield_timestamp, last_yield_timestamp);
+            assert_eq!(result, Ok(0u64));
+
+            // borrowers pay the same, suppliers earn less, the difference goes to reserves
+            assert_eq!(
+                BorrowIndices::get(&asset),
+                AssetIndex::from_nominal("1425.699020480854853072")
+            );
+            assert!(
+                SupplyIndices::get(&asset) < AssetIndex::from_nominal("1273.542520035618878005")
+            );
+            assert_eq!(
+                TotalCashPrincipal::get(),
+                CashPrincipalAmount::from_nominal("462104.853072")
+            );
+            assert_eq!(
+                TotalReservePrincipal::get(),
+                CashPrincipalAmount::from_nominal("1210.485307")
+            );
+            // the miner share is unaffected (modulo rounding)
+            let miner_share_principal = LastMinerSharePrincipal::get();
+            assert!(miner_share_principal > CashPrincipalAmount::from_nominal("242.09"));
+            assert!(miner_share_principal < CashPrincipalAmount::from_nominal("242.11"));
+        });
+    }
+
     #[test]
     fn test_on_initialize_next_yield_progression() {
         new_test_ext().execute_with(|| {
@@ -1775,4 +2402,98 @@ mod tests {
             assert_eq!(AssetBalances::get(asset3, account), nonzero_balance);
         });
     }
+
+    #[test]
+    fn test_apply_matic_events() {
+        new_test_ext().execute_with(|| {
+            let asset = ChainAsset::Matic([1; 20]);
+            SupportedAssets::insert(&asset, AssetInfo::minimal(asset, ETH));
+            let matic_event = |event| {
+                ChainLogEvent::Matic(ethereum_client::EthereumLogEvent {
+                    block_hash: [3; 32],
+                    block_number: 3858223,
+                    transaction_index: 0,
+                    log_index: 0,
+                    event,
+                })
+            };
+
+            assert_ok!(apply_chain_event_internal::<Test>(matic_event(
+                ethereum_client::EthereumEvent::Lock {
+                    asset: [1; 20],
+                    sender: [3; 20],
+                    chain: String::from("MATIC"),
+                    recipient: [2; 32],
+                    amount: 10,
+                }
+            )));
+            assert_eq!(AssetBalances::get(asset, ChainAccount::Matic([2; 20])), 10);
+
+            // the recipient can be on another evm chain than the sender
+            assert_ok!(apply_chain_event_internal::<Test>(matic_event(
+                ethereum_client::EthereumEvent::Lock {
+                    asset: [1; 20],
+                    sender: [3; 20],
+                    chain: String::from("ETH"),
+                    recipient: [2; 32],
+                    amount: 5,
+                }
+            )));
+            assert_eq!(AssetBalances::get(asset, ChainAccount::Eth([2; 20])), 5);
+
+            // but the asset must be supported on the chain the event comes from
+            assert_eq!(
+                apply_chain_event_internal::<Test>(ChainLogEvent::Eth(
+                    ethereum_client::EthereumLogEvent {
+                        block_hash: [3; 32],
+                        block_number: 3858223,
+                        transaction_index: 0,
+                        log_index: 0,
+                        event: ethereum_client::EthereumEvent::Lock {
+                            asset: [1; 20],
+                            sender: [3; 20],
+                            chain: String::from("MATIC"),
+                            recipient: [2; 32],
+                            amount: 5,
+                        },
+                    }
+                )),
+                Err(Reason::AssetNotSupported)
+            );
+
+            assert_eq!(
+                apply_chain_event_internal::<Test>(matic_event(
+                    ethereum_client::EthereumEvent::ExecuteProposal {
+                        title: String::new(),
+                        extrinsics: vec![],
+                    }
+                )),
+                Err(Reason::InvalidChain)
+            );
+        });
+    }
+
+    #[test]
+    fn test_apply_unknown_event() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                apply_chain_event_internal::<Test>(ChainLogEvent::Eth(
+                    ethereum_client::EthereumLogEvent {
+                        block_hash: [3; 32],
+                        block_number: 3858223,
+                        transaction_index: 0,
+                        log_index: 0,
+                        event: ethereum_client::EthereumEvent::Unknown {
+                            topics: vec![String::from(
+                                "0x0000000000000000000000000000000000000000000000000000000000000001"
+                            )],
+                            data: String::from("0x"),
+                            error: ethereum_client::events::EventError::UnknownEventTopic([0; 32]),
+                        },
+                    }
+                )),
+                Err(Reason::UndecodableEvent)
+            );
+        });
+    }
 }
@@ -0,0 +1,181 @@
+use codec::alloc::string::String;
+use our_std::{convert::TryInto, vec::Vec};
+use sp_runtime::traits::Zero;
+use trx_request::TrxRequest;
+
+use crate::{
+    chains::{Chain, Ethereum},
+    types::Nonce,
+    Config,
+};
+
+/// The EIP-712 domain name under which trx requests are signed.
+pub const DOMAIN_NAME: &str = "Compound Gateway";
+
+/// The EIP-712 domain version under which trx requests are signed.
+pub const DOMAIN_VERSION: &str = "1";
+
+// The domain salt is the genesis hash of the chain, so requests can't be replayed on another chain
+const DOMAIN_TYPE: &str = "EIP712Domain(string name,string version,bytes32 salt)";
+
+const EXTRACT_TYPE: &str = "Extract(uint256 nonce,string amount,string asset,string recipient)";
+const TRANSFER_TYPE: &str = "Transfer(uint256 nonce,string amount,string asset,string recipient)";
+const LIQUIDATE_TYPE: &str = "Liquidate(uint256 nonce,string amount,string borrowedAsset,string collateralAsset,string borrower)";
+const BATCH_TYPE: &str = "Batch(uint256 nonce,string[] requests)";
+
+fn keccak(data: &[u8]) -> [u8; 32] {
+    <Ethereum as Chain>::hash_bytes(data)
+}
+
+fn encode_uint(value: Nonce) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[28..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+fn encode_string(value: String) -> [u8; 32] {
+    keccak(value.as_bytes())
+}
+
+fn hash_words(words: &[[u8; 32]]) -> [u8; 32] {
+    keccak(&words.concat())
+}
+
+/// The genesis hash of the running chain, which salts the EIP-712 domain.
+/// Block hashes read as zero when missing, which is never a usable salt.
+pub fn genesis_hash<T: Config>() -> Option<[u8; 32]> {
+    frame_system::Module::<T>::block_hash(T::BlockNumber::zero())
+        .as_ref()
+        .try_into()
+        .ok()
+        .filter(|hash| *hash != [0u8; 32])
+}
+
+/// The EIP-712 domain separator for a chain with the given genesis hash.
+pub fn domain_separator(genesis_hash: [u8; 32]) -> [u8; 32] {
+    hash_words(&[
+        keccak(DOMAIN_TYPE.as_bytes()),
+        keccak(DOMAIN_NAME.as_bytes()),
+        keccak(DOMAIN_VERSION.as_bytes()),
+        genesis_hash,
+    ])
+}
+
+/// The EIP-712 struct hash of a trx request signed with the given nonce.
+///
+/// Amounts, assets and accounts are encoded as strings in their canonical trx request form,
+///  which is what a wallet displays to the signer.
+pub fn hash_struct(request: &TrxRequest, nonce: Nonce) -> [u8; 32] {
+    match request {
+        TrxRequest::Extract(amount, asset, account) => hash_words(&[
+            keccak(EXTRACT_TYPE.as_bytes()),
+            encode_uint(nonce),
+            encode_string(format!("{}", amount)),
+            encode_string(format!("{}", asset)),
+            encode_string(format!("{}", account)),
+        ]),
+        TrxRequest::Transfer(amount, asset, account) => hash_words(&[
+            keccak(TRANSFER_TYPE.as_bytes()),
+            encode_uint(nonce),
+            encode_string(format!("{}", amount)),
+            encode_string(format!("{}", asset)),
+            encode_string(format!("{}", account)),
+        ]),
+        TrxRequest::Liquidate(amount, borrowed_asset, collateral_asset, borrower) => hash_words(&[
+            keccak(LIQUIDATE_TYPE.as_bytes()),
+            encode_uint(nonce),
+            encode_string(format!("{}", amount)),
+            encode_string(format!("{}", borrowed_asset)),
+            encode_string(format!("{}", collateral_asset)),
+            encode_string(format!("{}", borrower)),
+        ]),
+        TrxRequest::Batch(requests) => hash_words(&[
+            keccak(BATCH_TYPE.as_bytes()),
+            encode_uint(nonce),
+            hash_words(
+                &requests
+                    .iter()
+                    .map(|request| encode_string(format!("{}", request)))
+                    .collect::<Vec<_>>(),
+            ),
+        ]),
+    }
+}
+
+/// The message signed for a trx request as EIP-712 typed data.
+///
+/// This is `0x1901 ‖ domainSeparator ‖ hashStruct(request)`, whose keccak256 hash is the signed digest.
+pub fn typed_data(request: &TrxRequest, nonce: Nonce, genesis_hash: [u8; 32]) -> Vec<u8> {
+    [
+        &[0x19, 0x01][..],
+        &domain_separator(genesis_hash)[..],
+        &hash_struct(request, nonce)[..],
+    ]
+    .concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{new_test_ext, Test};
+    use frame_support::storage::StorageMap;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_domain_separator() {
+        assert_eq!(
+            keccak(DOMAIN_TYPE.as_bytes()),
+            hex!("599a80fcaa47b95e2323ab4d34d34e0cc9feda4b843edafcc30c7bdf60ea15bf")
+        );
+        assert_eq!(
+            domain_separator([0; 32]),
+            hex!("0c35c8b0ce7f3e12b44536cddf8ffb1bcf5a01c13d6ed8aba27c123f0a198fa3")
+        );
+        assert_ne!(domain_separator([0; 32]), domain_separator([69; 32]));
+    }
+
+    #[test]
+    fn test_genesis_hash() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(genesis_hash::<Test>(), Some([1; 32]));
+
+            <frame_system::BlockHash<Test>>::remove(0);
+            assert_eq!(genesis_hash::<Test>(), None);
+        });
+    }
+
+    #[test]
+    fn test_typed_data_extract() {
+        let request = trx_request::parse_request(
+            "(Extract 50000000 Cash Eth:0xfc04833Ca66b7D6B4F540d4C2544228f64a25ac2)",
+        )
+        .unwrap();
+
+        assert_eq!(
+            hash_struct(&request, 0),
+            hex!("17f350365c7c224a2b636e0c1b54d96dd05147ffd6337c2a51b3eb990e7a0f22")
+        );
+        assert_eq!(
+            keccak(&typed_data(&request, 0, [0; 32])),
+            hex!("694e80920b16317e52d3624d1d070972da6044bbbe6bc8ccb8b0010ba800a1bd")
+        );
+        assert_ne!(hash_struct(&request, 0), hash_struct(&request, 1));
+    }
+
+    #[test]
+    fn test_typed_data_batch() {
+        let request = trx_request::parse_request(
+            "(Batch (Extract 1.5 ETH Eth:0x0101010101010101010101010101010101010101) (Transfer 2 Cash Eth:0x0101010101010101010101010101010101010101))",
+        )
+        .unwrap();
+
+        assert_eq!(
+            hash_struct(&request, 3),
+            hex!("5d05606f80f08c1b57cd14a9d4a6780342b589433315f423eeb71b892a37cfcf")
+        );
+        assert_eq!(
+            keccak(&typed_data(&request, 3, [69; 32])),
+            hex!("c8f7142336ca451396fa53b740ed260952308ac13d45e0da099ea594de360641")
+        );
+    }
+}
@@ -1,10 +1,13 @@
 use crate::chains::{eth, ChainId, ChainSignature};
 use crate::log;
+use crate::params::{
+    ETH_FINALIZATION_BLOCKS, ETH_LOGS_BATCH_SIZE, ETH_LOGS_MAX_BATCHES, ETH_LOGS_MAX_BLOCK_SPAN,
+};
 use crate::reason::Reason;
 use crate::types::SignersSet;
 use codec::alloc::string::String;
 use codec::{Decode, Encode};
-use ethereum_client::{EthereumClientError, EthereumLogEvent};
+use ethereum_client::{Endpoint, EthereumClientError, EthereumLogEvent, HttpOptions, LogsPaging};
 use our_std::{vec::Vec, RuntimeDebug};
 
 use types_derive::Types;
@@ -14,12 +17,14 @@ extern crate ethereum_client;
 #[derive(RuntimeDebug, Types)]
 pub struct EventInfo {
     pub latest_eth_block: u64,
+    pub latest_eth_block_hash: Option<eth::BlockHash>,
     pub events: Vec<(ChainLogId, ChainLogEvent)>,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum ChainLogId {
     Eth(eth::BlockNumber, eth::LogIndex),
+    Matic(eth::BlockNumber, eth::LogIndex),
 }
 
 impl ChainLogId {
@@ -28,6 +33,9 @@ impl ChainLogId {
             ChainLogId::Eth(block_number, log_index) => {
                 format!("Eth({},{})", block_number, log_index)
             }
+            ChainLogId::Matic(block_number, log_index) => {
+                format!("Matic({},{})", block_number, log_index)
+            }
         }
     }
 }
@@ -35,12 +43,14 @@ impl ChainLogId {
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum ChainLogEvent {
     Eth(EthereumLogEvent),
+    Matic(EthereumLogEvent),
 }
 
 impl ChainLogEvent {
     pub fn chain_id(&self) -> ChainId {
         match self {
             ChainLogEvent::Eth(_) => ChainId::Eth,
+            ChainLogEvent::Matic(_) => ChainId::Matic,
         }
     }
 
@@ -72,55 +82,200 @@ pub enum EventError {
     StarportAddressInvalid,
     EthereumClientError(EthereumClientError),
     ErrorDecodingHex,
+    /// More RPC URLs must agree on logs than there are URLs configured.
+    RpcQuorumUnreachable,
+}
+
+/// The RPC endpoints and starport address used to fetch events from an EVM chain.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct StarportConfig {
+    /// The endpoints requests fail over between, in order of preference.
+    pub endpoints: Vec<Endpoint>,
+    pub starport_address: String,
+    /// The block the starport was deployed in, where fetching events starts.
+    pub deploy_block: u64,
+    /// The number of endpoints which must return identical logs before events are signed.
+    pub quorum: usize,
+    /// The limits on paging through the logs of the starport.
+    pub paging: LogsPaging,
+}
+
+impl StarportConfig {
+    /// Build the config from a comma separated list of RPC URLs, each only sent the headers meant for it.
+    fn new(
+        rpc_urls: Vec<u8>,
+        starport_address: Vec<u8>,
+        deploy_block: u64,
+        quorum: usize,
+        paging: LogsPaging,
+        http_config: runtime_interfaces::HttpConfig,
+    ) -> Result<Self, EventError> {
+        let endpoints: Vec<Endpoint> = String::from_utf8(rpc_urls)
+            .map_err(|_| EventError::EthRpcUrlInvalid)?
+            .split(',')
+            .map(|rpc_url| rpc_url.trim())
+            .filter(|rpc_url| !rpc_url.is_empty())
+            .map(|rpc_url| {
+                let options = HttpOptions::new(
+                    http_config.get_timeout_ms(),
+                    http_config.get_retries(),
+                    http_config.get_headers(rpc_url),
+                );
+                Endpoint::new(rpc_url, options)
+            })
+            .collect();
+        if endpoints.is_empty() {
+            return Err(EventError::EthRpcUrlMissing);
+        }
+        if quorum > endpoints.len() {
+            return Err(EventError::RpcQuorumUnreachable);
+        }
+        Ok(StarportConfig {
+            endpoints,
+            starport_address: String::from_utf8(starport_address)
+                .map_err(|_| EventError::StarportAddressInvalid)?,
+            deploy_block,
+            quorum,
+            paging,
+        })
+    }
 }
 
-// XXX does this belong here? all very eth specific...
-//  whats the separate with internal?
-/// Fetch all latest Starport events for the offchain worker.
-pub fn fetch_eth_events(from_block: String) -> Result<EventInfo, EventError> {
-    // Get a validator config from runtime-interfaces pallet
-    // Use config to get an address for interacting with Ethereum JSON RPC client
+/// Get the starport config for an EVM chain from the chain spec and validator config.
+///
+/// A chain is only followed once the validator has an RPC URL for it and the chain spec
+///  has a starport address, which every validator has for Ethereum by default.
+pub fn get_starport_config(chain_id: ChainId) -> Result<Option<StarportConfig>, EventError> {
+    let name = match chain_id.evm_chain() {
+        Ok(evm_chain) => evm_chain.config_name,
+        Err(_) => return Ok(None),
+    };
     let config = runtime_interfaces::config_interface::get();
-    let eth_rpc_url = runtime_interfaces::validator_config_interface::get_eth_rpc_url()
-        .ok_or(EventError::EthRpcUrlMissing)?;
-    let eth_rpc_url = String::from_utf8(eth_rpc_url).map_err(|_| EventError::EthRpcUrlInvalid)?;
-    let eth_starport_address = String::from_utf8(config.get_eth_starport_address())
-        .map_err(|_| EventError::StarportAddressInvalid)?;
+    let starport_address = config.get_starport_address(name);
+    match runtime_interfaces::validator_config_interface::get_evm_rpc_url(name) {
+        Some(rpc_url) if !starport_address.is_empty() => {
+            let quorum = runtime_interfaces::validator_config_interface::get_evm_rpc_quorum(name)
+                .unwrap_or(1);
+            Ok(Some(StarportConfig::new(
+                rpc_url,
+                starport_address,
+                config.get_starport_deploy_block(name),
+                quorum as usize,
+                get_logs_paging(name),
+                runtime_interfaces::validator_config_interface::get_evm_rpc_http_config(name),
+            )?))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Get the limits on paging through the logs of the named EVM chain,
+///  using the defaults for any the validator doesn't configure.
+fn get_logs_paging(name: &str) -> LogsPaging {
+    let max_block_span =
+        runtime_interfaces::validator_config_interface::get_evm_rpc_logs_block_span(name);
+    let batch_size =
+        runtime_interfaces::validator_config_interface::get_evm_rpc_logs_batch_size(name);
+    LogsPaging {
+        max_block_span: max_block_span.unwrap_or(ETH_LOGS_MAX_BLOCK_SPAN),
+        batch_size: batch_size.map_or(ETH_LOGS_BATCH_SIZE, |size| size as usize),
+        max_batches: ETH_LOGS_MAX_BATCHES,
+    }
+}
+
+/// Fetch the hash of the canonical block at the given height on an EVM chain,
+///  once as many endpoints agree on it as must agree on logs.
+pub fn fetch_block_hash(
+    starport: &StarportConfig,
+    block_number: u64,
+) -> Result<eth::BlockHash, EventError> {
+    ethereum_client::fetch_block_hash_agreed(&starport.endpoints, block_number, starport.quorum)
+        .map_err(EventError::EthereumClientError)
+}
+
+/// Identify a log from the starport on an EVM chain as an event of that chain.
+fn evm_chain_log(chain_id: ChainId, log: EthereumLogEvent) -> Option<(ChainLogId, ChainLogEvent)> {
+    match chain_id {
+        ChainId::Eth => Some((
+            ChainLogId::Eth(log.block_number, log.log_index),
+            ChainLogEvent::Eth(log),
+        )),
+        ChainId::Matic => Some((
+            ChainLogId::Matic(log.block_number, log.log_index),
+            ChainLogEvent::Matic(log),
+        )),
+        _ => None,
+    }
+}
 
+/// Fetch finalized events from the starport on an EVM chain for the offchain worker.
+///
+/// Only blocks at least `ETH_FINALIZATION_BLOCKS` behind the head are considered,
+///  and logs are paged through a limited number of blocks at a time, so a validator
+///  with an empty cache catches up over several runs instead of scanning from genesis at once.
+/// The last block fetched is returned with its hash, so the range can be checked for reorgs later.
+pub fn fetch_evm_events(
+    chain_id: ChainId,
+    starport: &StarportConfig,
+    from_block: u64,
+) -> Result<EventInfo, EventError> {
+    let rpc_urls: Vec<&str> = starport
+        .endpoints
+        .iter()
+        .map(|endpoint| &endpoint.url[..])
+        .collect();
     log!(
-        "eth_rpc_url={}, starport_address={}",
-        eth_rpc_url,
-        eth_starport_address,
+        "chain={:?}, rpc_urls={:?}, starport_address={}",
+        chain_id,
+        rpc_urls,
+        starport.starport_address,
     );
 
-    // Fetch the latest available ethereum block number
-    let latest_eth_block = ethereum_client::fetch_latest_block(&eth_rpc_url)
-        .map_err(EventError::EthereumClientError)?;
+    // Fetch the latest available block number
+    let latest_eth_block =
+        ethereum_client::with_failover(&starport.endpoints, ethereum_client::fetch_latest_block)
+            .map_err(EventError::EthereumClientError)?;
+
+    // Only consider blocks which are deep enough to be considered final
+    let finalized_eth_block = latest_eth_block.saturating_sub(ETH_FINALIZATION_BLOCKS as u64);
+
+    if from_block > finalized_eth_block {
+        log!(
+            "No newly finalized blocks (from={}, finalized={})",
+            from_block,
+            finalized_eth_block
+        );
+        return Ok(EventInfo {
+            latest_eth_block: from_block.saturating_sub(1),
+            latest_eth_block_hash: None,
+            events: vec![],
+        });
+    }
 
-    // Build parameters set for fetching starport events
-    let fetch_events_request = format!(
-        r#"{{"address": "{}", "fromBlock": "{}", "toBlock": "{}"}}"#,
-        eth_starport_address,
+    // Fetch events using ethereum_client, as far towards the finalized block as we get,
+    //  and only once enough endpoints agree on them
+    let page = ethereum_client::fetch_and_decode_logs_agreed(
+        &starport.endpoints,
+        &starport.starport_address,
         from_block,
-        encode_block_hex(latest_eth_block)
-    );
+        finalized_eth_block,
+        starport.paging,
+        starport.quorum,
+    )
+    .map_err(EventError::EthereumClientError)?;
 
-    // Fetch events using ethereum_client
-    let logs = ethereum_client::fetch_and_decode_logs(&eth_rpc_url, vec![&fetch_events_request])
-        .map_err(EventError::EthereumClientError)?;
+    // Remember which block we fetched up to, so we can tell if it gets reorged
+    let fetched_eth_block_hash = fetch_block_hash(starport, page.to_block)?;
 
-    let events = logs
+    let events = page
+        .logs
         .into_iter()
-        .map(|log| {
-            (
-                ChainLogId::Eth(log.block_number, log.log_index),
-                ChainLogEvent::Eth(log),
-            )
-        })
+        .filter_map(|log| evm_chain_log(chain_id, log))
         .collect();
 
     Ok(EventInfo {
-        latest_eth_block,
+        latest_eth_block: page.to_block,
+        latest_eth_block_hash: Some(fetched_eth_block_hash),
         events,
     })
 }
@@ -131,39 +286,138 @@ pub fn encode_block_hex(block_number: u64) -> String {
 
 #[cfg(test)]
 pub mod tests {
-    use crate::{tests::*, *};
+    use crate::{
+        params::{ETH_LOGS_BATCH_SIZE, ETH_LOGS_MAX_BATCHES, ETH_LOGS_MAX_BLOCK_SPAN},
+        tests::*,
+        *,
+    };
     use our_std::convert::*;
     use sp_core::offchain::testing;
 
+    const STARPORT_ADDRESS: &str = "0xbbde1662bC3ED16aA8C618c9833c801F3543B587";
+
+    const RPC_URL_A: &str = "https://eth-a.example.com";
+    const RPC_URL_B: &str = "https://eth-b.example.com";
+    const RPC_URL_C: &str = "https://eth-c.example.com";
+
+    fn rpc_call(method: &str, params: &str, response: Vec<u8>) -> testing::PendingRequest {
+        let given_eth_rpc_url =
+            runtime_interfaces::validator_config_interface::get_evm_rpc_url("eth").unwrap();
+        rpc_call_to(
+            &String::from_utf8(given_eth_rpc_url).unwrap(),
+            method,
+            params,
+            response,
+        )
+    }
+
+    pub fn rpc_call_to(
+        uri: &str,
+        method: &str,
+        params: &str,
+        response: Vec<u8>,
+    ) -> testing::PendingRequest {
+        testing::PendingRequest {
+            method: "POST".into(),
+            uri: uri.into(),
+            body: format!(
+                r#"{{"jsonrpc":"2.0","method":"{}","params":[{}],"id":1}}"#,
+                method, params
+            )
+            .into_bytes(),
+            response: Some(response),
+            headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
+            sent: true,
+            ..Default::default()
+        }
+    }
+
+    fn get_logs_params(from_block: u64, to_block: u64) -> String {
+        format!(
+            r#"{{"address": "{}", "fromBlock": "{:#X}", "toBlock": "{:#X}"}}"#,
+            STARPORT_ADDRESS, from_block, to_block
+        )
+    }
+
+    /// A batch of `eth_getLogs` calls, answered with the given logs response for the first window.
+    fn get_logs_batch_call(
+        windows: &[(u64, u64)],
+        first_response: &[u8],
+    ) -> testing::PendingRequest {
+        let calls: Vec<String> = windows
+            .iter()
+            .zip(1..)
+            .map(|(&(from_block, to_block), id)| {
+                format!(
+                    r#"{{"jsonrpc":"2.0","method":"eth_getLogs","params":[{}],"id":{}}}"#,
+                    get_logs_params(from_block, to_block),
+                    id
+                )
+            })
+            .collect();
+        let responses: Vec<String> = (1..=windows.len())
+            .map(|id| match id {
+                1 => String::from_utf8(first_response.to_vec()).unwrap(),
+                _ => format!(r#"{{"jsonrpc":"2.0","id":{},"result":[]}}"#, id),
+            })
+            .collect();
+        let mut call = rpc_call("eth_getLogs", "", vec![]);
+        call.body = format!("[{}]", calls.join(",")).into_bytes();
+        call.response = Some(format!("[{}]", responses.join(",")).into_bytes());
+        call
+    }
+
+    /// The calls made by a validator with an empty cache, which pages through the logs
+    ///  from genesis and returns the given response for the first window.
     pub fn get_mockup_http_calls(events_response: Vec<u8>) -> Vec<testing::PendingRequest> {
         // Set up config values
-        let given_eth_starport_address: Vec<u8> =
-            "0xbbde1662bC3ED16aA8C618c9833c801F3543B587".into();
-        let config = runtime_interfaces::new_config(given_eth_starport_address.clone());
+        let config =
+            runtime_interfaces::new_config(vec![runtime_interfaces::StarportDeployment::new(
+                "eth",
+                STARPORT_ADDRESS,
+                0,
+            )]);
         runtime_interfaces::config_interface::set(config);
 
-        let given_eth_rpc_url =
-            runtime_interfaces::validator_config_interface::get_eth_rpc_url().unwrap();
-        return vec![
-            testing::PendingRequest{
-                method: "POST".into(),
-                uri: String::from_utf8(given_eth_rpc_url.clone()).unwrap(),
-                body: br#"{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":1}"#.to_vec(),
-                response: Some(tests::testdata::json_responses::BLOCK_NUMBER_RESPONSE.to_vec()),
-                headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
-                sent: true,
-                ..Default::default()
-            },
-            testing::PendingRequest{
-                method: "POST".into(),
-                uri: String::from_utf8(given_eth_rpc_url.clone()).unwrap(),
-                body: br#"{"jsonrpc":"2.0","method":"eth_getLogs","params":[{"address": "0xbbde1662bC3ED16aA8C618c9833c801F3543B587", "fromBlock": "earliest", "toBlock": "0xB27467"}],"id":1}"#.to_vec(),
-                response: Some(events_response.clone()),
-                headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
-                sent: true,
-                ..Default::default()
-            }
-        ];
+        let span = ETH_LOGS_MAX_BLOCK_SPAN;
+        let batch_blocks = span * ETH_LOGS_BATCH_SIZE as u64;
+        let mut calls = vec![rpc_call(
+            "eth_blockNumber",
+            "",
+            tests::testdata::json_responses::BLOCK_NUMBER_RESPONSE.to_vec(),
+        )];
+        for batch in 0..ETH_LOGS_MAX_BATCHES as u64 {
+            let windows: Vec<(u64, u64)> = (0..ETH_LOGS_BATCH_SIZE as u64)
+                .map(|i| batch * batch_blocks + i * span)
+                .map(|from_block| (from_block, from_block + span - 1))
+                .collect();
+            let first_response = match batch {
+                0 => &events_response[..],
+                _ => tests::testdata::json_responses::NO_EVENTS_RESPONSE,
+            };
+            calls.push(get_logs_batch_call(&windows, first_response));
+        }
+        calls.push(rpc_call(
+            "eth_getBlockByNumber",
+            &format!(r#""{:#X}",false"#, PAGED_ETH_BLOCK),
+            tests::testdata::json_responses::BLOCK_BY_NUMBER_RESPONSE.to_vec(),
+        ));
+        calls
+    }
+
+    /// The limits on paging through logs when the validator doesn't configure any.
+    const PAGING: ethereum_client::LogsPaging = ethereum_client::LogsPaging {
+        max_block_span: ETH_LOGS_MAX_BLOCK_SPAN,
+        batch_size: ETH_LOGS_BATCH_SIZE,
+        max_batches: ETH_LOGS_MAX_BATCHES,
+    };
+
+    /// The last block fetched by a validator with an empty cache.
+    const PAGED_ETH_BLOCK: u64 =
+        ETH_LOGS_MAX_BLOCK_SPAN * (ETH_LOGS_BATCH_SIZE * ETH_LOGS_MAX_BATCHES) as u64 - 1;
+
+    fn eth_starport() -> events::StarportConfig {
+        events::get_starport_config(ChainId::Eth).unwrap().unwrap()
     }
 
     #[test]
@@ -173,14 +427,20 @@ pub mod tests {
 
         let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
         t.execute_with(|| {
-            let events_candidate = events::fetch_eth_events("earliest".to_string());
+            let events_candidate = events::fetch_evm_events(ChainId::Eth, &eth_starport(), 0);
             assert!(events_candidate.is_ok());
             let starport_info = events_candidate.unwrap();
             let latest_eth_block = starport_info.latest_eth_block;
             let mut events = starport_info.events;
             events.reverse(); // Since we'll be popping off the end
 
-            assert_eq!(latest_eth_block, 11695207);
+            assert_eq!(latest_eth_block, PAGED_ETH_BLOCK);
+            assert_eq!(
+                starport_info.latest_eth_block_hash,
+                Some(hex_literal::hex!(
+                    "5d4b4e3bb2d5b0c0ef1e1a2a3a0bdbd6db8d1f5a3a3c2d6e1d7a8c0c9e3f2b1a"
+                ))
+            );
             assert_eq!(events.len(), 3);
             if let Some((_chain_log_id, ChainLogEvent::Eth(log))) = events.pop() {
                 assert_eq!(
@@ -224,16 +484,487 @@ pub mod tests {
 
         let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
         t.execute_with(|| {
-            let events_candidate = events::fetch_eth_events("earliest".to_string());
+            let events_candidate = events::fetch_evm_events(ChainId::Eth, &eth_starport(), 0);
             assert!(events_candidate.is_ok());
             let event_info = events_candidate.unwrap();
             let latest_eth_block = event_info.latest_eth_block;
 
-            assert_eq!(latest_eth_block, 11695207);
+            assert_eq!(latest_eth_block, PAGED_ETH_BLOCK);
+            assert_eq!(event_info.events.len(), 0);
+        });
+    }
+
+    #[test]
+    fn test_fetch_events_from_cached_block() {
+        let mut calls = get_mockup_http_calls(vec![]);
+        calls.truncate(1);
+        calls.push(rpc_call(
+            "eth_getLogs",
+            &get_logs_params(11695078, 11695177),
+            tests::testdata::json_responses::EVENTS_RESPONSE.to_vec(),
+        ));
+        calls.push(rpc_call(
+            "eth_getBlockByNumber",
+            r#""0xB27449",false"#,
+            tests::testdata::json_responses::BLOCK_BY_NUMBER_RESPONSE.to_vec(),
+        ));
+
+        let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+        t.execute_with(|| {
+            let event_info =
+                events::fetch_evm_events(ChainId::Eth, &eth_starport(), 11695078).unwrap();
+            assert_eq!(event_info.latest_eth_block, 11695177);
+            assert_eq!(event_info.events.len(), 3);
+        });
+    }
+
+    #[test]
+    fn test_fetch_events_shrinks_span_on_too_many_results() {
+        let mut calls = get_mockup_http_calls(vec![]);
+        calls.truncate(1);
+        calls.push(rpc_call(
+            "eth_getLogs",
+            &get_logs_params(11695078, 11695177),
+            br#"{"jsonrpc":"2.0","id":1,"error":{"code":-32005,"message":"query returned more than 10000 results"}}"#.to_vec(),
+        ));
+        calls.push(get_logs_batch_call(
+            &[(11695078, 11695127), (11695128, 11695177)],
+            tests::testdata::json_responses::EVENTS_RESPONSE,
+        ));
+        calls.push(rpc_call(
+            "eth_getBlockByNumber",
+            r#""0xB27449",false"#,
+            tests::testdata::json_responses::BLOCK_BY_NUMBER_RESPONSE.to_vec(),
+        ));
+
+        let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+        t.execute_with(|| {
+            let event_info =
+                events::fetch_evm_events(ChainId::Eth, &eth_starport(), 11695078).unwrap();
+            assert_eq!(event_info.latest_eth_block, 11695177);
+            assert_eq!(event_info.events.len(), 3);
+        });
+    }
+
+    /// The response of a node refusing an `eth_getLogs` call for returning too many results.
+    const TOO_MANY_RESULTS_ERROR: &str =
+        r#"{"code":-32005,"message":"query returned more than 10000 results"}"#;
+
+    #[test]
+    fn test_fetch_events_stops_before_block_with_too_many_results() {
+        let mut calls = get_mockup_http_calls(vec![]);
+        calls.truncate(1);
+        let mut call = get_logs_batch_call(
+            &[(11695078, 11695078), (11695079, 11695079)],
+            tests::testdata::json_responses::NO_EVENTS_RESPONSE,
+        );
+        call.response = Some(
+            format!(
+                r#"[{{"jsonrpc":"2.0","id":1,"result":[]}},{{"jsonrpc":"2.0","id":2,"error":{}}}]"#,
+                TOO_MANY_RESULTS_ERROR
+            )
+            .into_bytes(),
+        );
+        calls.push(call);
+        calls.push(rpc_call(
+            "eth_getBlockByNumber",
+            r#""0xB273E6",false"#,
+            tests::testdata::json_responses::BLOCK_BY_NUMBER_RESPONSE.to_vec(),
+        ));
+
+        let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+        t.execute_with(|| {
+            let starport = events::StarportConfig {
+                paging: ethereum_client::LogsPaging {
+                    max_block_span: 1,
+                    batch_size: 2,
+                    ..PAGING
+                },
+                ..eth_starport()
+            };
+            let event_info = events::fetch_evm_events(ChainId::Eth, &starport, 11695078).unwrap();
+            assert_eq!(event_info.latest_eth_block, 11695078);
             assert_eq!(event_info.events.len(), 0);
         });
     }
 
+    #[test]
+    fn test_fetch_events_fails_on_single_block_with_too_many_results() {
+        let mut calls = get_mockup_http_calls(vec![]);
+        calls.truncate(1);
+        calls.push(rpc_call(
+            "eth_getLogs",
+            &get_logs_params(11695078, 11695078),
+            format!(
+                r#"{{"jsonrpc":"2.0","id":1,"error":{}}}"#,
+                TOO_MANY_RESULTS_ERROR
+            )
+            .into_bytes(),
+        ));
+
+        let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+        t.execute_with(|| {
+            let starport = events::StarportConfig {
+                paging: ethereum_client::LogsPaging {
+                    max_block_span: 1,
+                    batch_size: 1,
+                    ..PAGING
+                },
+                ..eth_starport()
+            };
+            assert_eq!(
+                events::fetch_evm_events(ChainId::Eth, &starport, 11695078).err(),
+                Some(EventError::EthereumClientError(
+                    ethereum_client::EthereumClientError::BlockTooManyResults(11695078)
+                ))
+            );
+        });
+    }
+
+    #[test]
+    fn test_fetch_events_nothing_newly_finalized() {
+        let calls: Vec<testing::PendingRequest> =
+            get_mockup_http_calls(tests::testdata::json_responses::NO_EVENTS_RESPONSE.to_vec())
+                .into_iter()
+                .take(1)
+                .collect();
+
+        let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+        t.execute_with(|| {
+            let event_info =
+                events::fetch_evm_events(ChainId::Eth, &eth_starport(), 11695178).unwrap();
+            assert_eq!(event_info.latest_eth_block, 11695177);
+            assert_eq!(event_info.latest_eth_block_hash, None);
+            assert_eq!(event_info.events.len(), 0);
+        });
+    }
+
+    fn two_endpoints(quorum: usize) -> events::StarportConfig {
+        events::StarportConfig {
+            endpoints: vec![RPC_URL_A.into(), RPC_URL_B.into()],
+            starport_address: STARPORT_ADDRESS.into(),
+            deploy_block: 0,
+            quorum,
+            paging: PAGING,
+        }
+    }
+
+    fn get_logs_call_to(uri: &str, response: &[u8]) -> testing::PendingRequest {
+        rpc_call_to(
+            uri,
+            "eth_getLogs",
+            &get_logs_params(11695078, 11695177),
+            response.to_vec(),
+        )
+    }
+
+    #[test]
+    fn test_fetch_events_fails_over_between_endpoints() {
+        let calls = vec![
+            rpc_call_to(
+                RPC_URL_A,
+                "eth_blockNumber",
+                "",
+                b"<html>502 Bad Gateway</html>".to_vec(),
+            ),
+            rpc_call_to(
+                RPC_URL_B,
+                "eth_blockNumber",
+                "",
+                tests::testdata::json_responses::BLOCK_NUMBER_RESPONSE.to_vec(),
+            ),
+            // The failed endpoint is backing off now, so the other is tried first
+            get_logs_call_to(RPC_URL_B, tests::testdata::json_responses::EVENTS_RESPONSE),
+            rpc_call_to(
+                RPC_URL_B,
+                "eth_getBlockByNumber",
+                r#""0xB27449",false"#,
+                tests::testdata::json_responses::BLOCK_BY_NUMBER_RESPONSE.to_vec(),
+            ),
+        ];
+
+        let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+        t.execute_with(|| {
+            let event_info =
+                events::fetch_evm_events(ChainId::Eth, &two_endpoints(1), 11695078).unwrap();
+            assert_eq!(event_info.latest_eth_block, 11695177);
+            assert_eq!(event_info.events.len(), 3);
+        });
+    }
+
+    #[test]
+    fn test_fetch_events_with_agreeing_endpoints() {
+        let calls = vec![
+            rpc_call_to(
+                RPC_URL_A,
+                "eth_blockNumber",
+                "",
+                tests::testdata::json_responses::BLOCK_NUMBER_RESPONSE.to_vec(),
+            ),
+            get_logs_call_to(RPC_URL_A, tests::testdata::json_responses::EVENTS_RESPONSE),
+            get_logs_call_to(RPC_URL_B, tests::testdata::json_responses::EVENTS_RESPONSE),
+            rpc_call_to(
+                RPC_URL_A,
+                "eth_getBlockByNumber",
+                r#""0xB27449",false"#,
+                tests::testdata::json_responses::BLOCK_BY_NUMBER_RESPONSE.to_vec(),
+            ),
+            rpc_call_to(
+                RPC_URL_B,
+                "eth_getBlockByNumber",
+                r#""0xB27449",false"#,
+                tests::testdata::json_responses::BLOCK_BY_NUMBER_RESPONSE.to_vec(),
+            ),
+        ];
+
+        let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+        t.execute_with(|| {
+            let event_info =
+                events::fetch_evm_events(ChainId::Eth, &two_endpoints(2), 11695078).unwrap();
+            assert_eq!(event_info.latest_eth_block, 11695177);
+            assert_eq!(event_info.events.len(), 3);
+        });
+    }
+
+    #[test]
+    fn test_fetch_events_with_disagreeing_endpoints() {
+        let calls = vec![
+            rpc_call_to(
+                RPC_URL_A,
+                "eth_blockNumber",
+                "",
+                tests::testdata::json_responses::BLOCK_NUMBER_RESPONSE.to_vec(),
+            ),
+            get_logs_call_to(RPC_URL_A, tests::testdata::json_responses::EVENTS_RESPONSE),
+            get_logs_call_to(
+                RPC_URL_B,
+                tests::testdata::json_responses::NO_EVENTS_RESPONSE,
+            ),
+        ];
+
+        let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+        t.execute_with(|| {
+            assert_eq!(
+                events::fetch_evm_events(ChainId::Eth, &two_endpoints(2), 11695078).err(),
+                Some(EventError::EthereumClientError(
+                    ethereum_client::EthereumClientError::ProvidersDisagree
+                ))
+            );
+        });
+    }
+
+    #[test]
+    fn test_fetch_events_with_disagreeing_block_hashes() {
+        let calls = vec![
+            rpc_call_to(
+                RPC_URL_A,
+                "eth_blockNumber",
+                "",
+                tests::testdata::json_responses::BLOCK_NUMBER_RESPONSE.to_vec(),
+            ),
+            get_logs_call_to(RPC_URL_A, tests::testdata::json_responses::EVENTS_RESPONSE),
+            get_logs_call_to(RPC_URL_B, tests::testdata::json_responses::EVENTS_RESPONSE),
+            rpc_call_to(
+                RPC_URL_A,
+                "eth_getBlockByNumber",
+                r#""0xB27449",false"#,
+                tests::testdata::json_responses::BLOCK_BY_NUMBER_RESPONSE.to_vec(),
+            ),
+            rpc_call_to(
+                RPC_URL_B,
+                "eth_getBlockByNumber",
+                r#""0xB27449",false"#,
+                tests::testdata::json_responses::REORGED_BLOCK_BY_NUMBER_RESPONSE.to_vec(),
+            ),
+        ];
+
+        let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+        t.execute_with(|| {
+            // the logs agree, but the block they were fetched up to can't be checked for reorgs later
+            assert_eq!(
+                events::fetch_evm_events(ChainId::Eth, &two_endpoints(2), 11695078).err(),
+                Some(EventError::EthereumClientError(
+                    ethereum_client::EthereumClientError::ProvidersDisagree
+                ))
+            );
+        });
+    }
+
+    #[test]
+    fn test_fetch_events_with_quorum_agreeing_after_first_endpoint() {
+        let calls = vec![
+            rpc_call_to(
+                RPC_URL_A,
+                "eth_blockNumber",
+                "",
+                tests::testdata::json_responses::BLOCK_NUMBER_RESPONSE.to_vec(),
+            ),
+            get_logs_call_to(
+                RPC_URL_A,
+                tests::testdata::json_responses::NO_EVENTS_RESPONSE,
+            ),
+            get_logs_call_to(RPC_URL_B, tests::testdata::json_responses::EVENTS_RESPONSE),
+            get_logs_call_to(RPC_URL_C, tests::testdata::json_responses::EVENTS_RESPONSE),
+            rpc_call_to(
+                RPC_URL_A,
+                "eth_getBlockByNumber",
+                r#""0xB27449",false"#,
+                tests::testdata::json_responses::BLOCK_BY_NUMBER_RESPONSE.to_vec(),
+            ),
+            rpc_call_to(
+                RPC_URL_B,
+                "eth_getBlockByNumber",
+                r#""0xB27449",false"#,
+                tests::testdata::json_responses::BLOCK_BY_NUMBER_RESPONSE.to_vec(),
+            ),
+        ];
+
+        let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+        t.execute_with(|| {
+            let starport = events::StarportConfig {
+                endpoints: vec![RPC_URL_A.into(), RPC_URL_B.into(), RPC_URL_C.into()],
+                ..two_endpoints(2)
+            };
+            let event_info = events::fetch_evm_events(ChainId::Eth, &starport, 11695078).unwrap();
+            assert_eq!(event_info.latest_eth_block, 11695177);
+            assert_eq!(event_info.events.len(), 3);
+        });
+    }
+
+    #[test]
+    fn test_starport_config_rpc_urls() {
+        let starport = events::StarportConfig::new(
+            format!(" {}, {} ,", RPC_URL_A, RPC_URL_B).into(),
+            STARPORT_ADDRESS.into(),
+            0,
+            2,
+            PAGING,
+            runtime_interfaces::HttpConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(starport, two_endpoints(2));
+        assert_eq!(
+            events::StarportConfig::new(
+                " , ".into(),
+                STARPORT_ADDRESS.into(),
+                0,
+                1,
+                PAGING,
+                runtime_interfaces::HttpConfig::default()
+            ),
+            Err(EventError::EthRpcUrlMissing)
+        );
+        assert_eq!(
+            events::StarportConfig::new(
+                format!("{},{}", RPC_URL_A, RPC_URL_B).into(),
+                STARPORT_ADDRESS.into(),
+                0,
+                3,
+                PAGING,
+                runtime_interfaces::HttpConfig::default()
+            ),
+            Err(EventError::RpcQuorumUnreachable)
+        );
+    }
+
+    #[test]
+    fn test_fetch_block_hash_sends_configured_headers() {
+        let http_config = runtime_interfaces::HttpConfig::parse(
+            Some(10_000),
+            Some(2),
+            &[
+                format!("url={} Authorization: Bearer secret", RPC_URL_A),
+                format!("url={} Authorization: Bearer other", RPC_URL_B),
+            ],
+        )
+        .unwrap();
+        let starport = events::StarportConfig::new(
+            format!("{},{}", RPC_URL_A, RPC_URL_B).into(),
+            STARPORT_ADDRESS.into(),
+            0,
+            1,
+            PAGING,
+            http_config,
+        )
+        .unwrap();
+        assert_eq!(starport.endpoints[0].options.timeout_ms, 10_000);
+        assert_eq!(starport.endpoints[0].options.retries, 2);
+        assert_eq!(
+            starport.endpoints[0].options.headers,
+            vec![("Authorization".to_owned(), "Bearer secret".to_owned())]
+        );
+        assert_eq!(
+            starport.endpoints[1].options.headers,
+            vec![("Authorization".to_owned(), "Bearer other".to_owned())]
+        );
+
+        let calls = vec![testing::PendingRequest {
+            headers: vec![
+                ("Content-Type".to_owned(), "application/json".to_owned()),
+                ("Authorization".to_owned(), "Bearer secret".to_owned()),
+            ],
+            ..rpc_call_to(
+                RPC_URL_A,
+                "eth_getBlockByNumber",
+                r#""0xB27449",false"#,
+                tests::testdata::json_responses::BLOCK_BY_NUMBER_RESPONSE.to_vec(),
+            )
+        }];
+
+        let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+        t.execute_with(|| {
+            assert!(events::fetch_block_hash(&starport, 11695177).is_ok());
+        });
+    }
+
+    #[test]
+    fn test_get_starport_config() {
+        let config =
+            runtime_interfaces::new_config(vec![runtime_interfaces::StarportDeployment::new(
+                "eth",
+                "0xbbde1662bC3ED16aA8C618c9833c801F3543B587",
+                0,
+            )]);
+        runtime_interfaces::config_interface::set(config);
+
+        let starport = eth_starport();
+        assert_eq!(
+            starport.starport_address,
+            "0xbbde1662bC3ED16aA8C618c9833c801F3543B587"
+        );
+        assert_eq!(starport.deploy_block, 0);
+        assert_eq!(starport.paging, PAGING);
+        assert_eq!(events::get_starport_config(ChainId::Matic), Ok(None));
+        assert_eq!(events::get_starport_config(ChainId::Dot), Ok(None));
+    }
+
+    #[test]
+    fn test_evm_chain_log() {
+        let log = ethereum_client::EthereumLogEvent {
+            block_hash: [3; 32],
+            block_number: 3858223,
+            transaction_index: 0,
+            log_index: 2,
+            event: ethereum_client::EthereumEvent::NoticeInvoked {
+                era_id: 0,
+                era_index: 1,
+                notice_hash: [1; 32],
+                result: vec![],
+            },
+        };
+
+        assert_eq!(
+            events::evm_chain_log(ChainId::Matic, log.clone()),
+            Some((
+                ChainLogId::Matic(3858223, 2),
+                ChainLogEvent::Matic(log.clone())
+            ))
+        );
+        assert_eq!(ChainLogId::Matic(3858223, 2).show(), "Matic(3858223,2)");
+        assert_eq!(ChainLogEvent::Matic(log.clone()).chain_id(), ChainId::Matic);
+        assert_eq!(events::evm_chain_log(ChainId::Sol, log), None);
+    }
+
     #[test]
     fn test_encode_block_hex() {
         assert_eq!(events::encode_block_hex(0xb27467 + 1), "0xB27468");
@@ -1,23 +1,76 @@
-use frame_support::storage::StorageMap;
+use frame_support::storage::{StorageMap, StorageValue};
 
 use crate::{
     chains::ChainAsset,
-    core::get_asset,
+    core::{get_asset, get_rates, get_total_shares},
+    factor::Factor,
+    params::{MAX_CLOSE_FACTOR, MAX_LIQUIDATION_INCENTIVE, MAX_SPREAD},
     rates::InterestRateModel,
     reason::Reason,
-    types::{AssetInfo, LiquidityFactor},
-    Config, SupportedAssets,
+    types::{AssetAmount, AssetInfo, Bips, LiquidityFactor},
+    BorrowRateRemainders, BorrowRates, CloseFactor, Config, Event, GlobalLiquidationIncentive,
+    Module, Spreads, SupportedAssets,
 };
 
+fn check_collateral_factor(factor: LiquidityFactor) -> Result<(), Reason> {
+    require!(factor <= Factor::ONE, Reason::InvalidLiquidityFactor);
+    Ok(())
+}
+
+fn check_borrow_factor(factor: LiquidityFactor) -> Result<(), Reason> {
+    // borrows are divided by the borrow factor, so it must be non-zero
+    require!(
+        factor > Factor::ZERO && factor <= Factor::ONE,
+        Reason::InvalidLiquidityFactor
+    );
+    Ok(())
+}
+
+/// Set both the collateral and borrow factors of an asset to the same value.
 pub fn set_liquidity_factor<T: Config>(
     asset: ChainAsset,
     factor: LiquidityFactor,
 ) -> Result<(), Reason> {
     let asset_info = get_asset::<T>(asset)?;
+    check_collateral_factor(factor)?;
+    check_borrow_factor(factor)?;
+    SupportedAssets::insert(
+        &asset,
+        AssetInfo {
+            collateral_factor: factor,
+            borrow_factor: factor,
+            ..asset_info
+        },
+    );
+    Ok(())
+}
+
+pub fn set_collateral_factor<T: Config>(
+    asset: ChainAsset,
+    factor: LiquidityFactor,
+) -> Result<(), Reason> {
+    let asset_info = get_asset::<T>(asset)?;
+    check_collateral_factor(factor)?;
     SupportedAssets::insert(
         &asset,
         AssetInfo {
-            liquidity_factor: factor,
+            collateral_factor: factor,
+            ..asset_info
+        },
+    );
+    Ok(())
+}
+
+pub fn set_borrow_factor<T: Config>(
+    asset: ChainAsset,
+    factor: LiquidityFactor,
+) -> Result<(), Reason> {
+    let asset_info = get_asset::<T>(asset)?;
+    check_borrow_factor(factor)?;
+    SupportedAssets::insert(
+        &asset,
+        AssetInfo {
+            borrow_factor: factor,
             ..asset_info
         },
     );
@@ -30,6 +83,13 @@ pub fn set_rate_model<T: Config>(
 ) -> Result<(), Reason> {
     let asset_info = get_asset::<T>(asset)?;
     model.check_parameters()?;
+    // an adaptive model starts adjusting from the rate under the previous model
+    if let InterestRateModel::Adaptive { .. } = model {
+        if let Ok((borrow_rate, _)) = get_rates::<T>(asset) {
+            BorrowRates::insert(asset, (borrow_rate, 0));
+            BorrowRateRemainders::remove(asset);
+        }
+    }
     SupportedAssets::insert(
         &asset,
         AssetInfo {
@@ -40,7 +100,223 @@ pub fn set_rate_model<T: Config>(
     Ok(())
 }
 
+pub fn set_borrow_cap<T: Config>(asset: ChainAsset, cap: AssetAmount) -> Result<(), Reason> {
+    let asset_info = get_asset::<T>(asset)?;
+    SupportedAssets::insert(
+        &asset,
+        AssetInfo {
+            borrow_cap: cap,
+            ..asset_info
+        },
+    );
+
+    <Module<T>>::deposit_event(Event::SetBorrowCap(asset, cap));
+
+    Ok(())
+}
+
+pub fn set_liquidation_incentive<T: Config>(incentive: Bips) -> Result<(), Reason> {
+    require!(
+        incentive <= MAX_LIQUIDATION_INCENTIVE,
+        Reason::InvalidLiquidationIncentive
+    );
+
+    GlobalLiquidationIncentive::put(incentive);
+
+    <Module<T>>::deposit_event(Event::SetLiquidationIncentive(incentive));
+
+    Ok(())
+}
+
+pub fn set_close_factor<T: Config>(close_factor: Bips) -> Result<(), Reason> {
+    require!(
+        close_factor > 0 && close_factor <= MAX_CLOSE_FACTOR,
+        Reason::InvalidCloseFactor
+    );
+
+    CloseFactor::put(close_factor);
+
+    <Module<T>>::deposit_event(Event::SetCloseFactor(close_factor));
+
+    Ok(())
+}
+
+pub fn set_spread<T: Config>(asset: ChainAsset, spread: Bips) -> Result<(), Reason> {
+    let asset_info = get_asset::<T>(asset)?;
+    require!(spread <= MAX_SPREAD, Reason::InvalidSpread);
+    // suppliers cannot be paid a negative share of borrower interest
+    require!(
+        get_total_shares(&asset_info, spread)? <= Factor::ONE,
+        Reason::InvalidSpread
+    );
+
+    Spreads::insert(asset, spread);
+
+    <Module<T>>::deposit_event(Event::SetSpread(asset, spread));
+
+    Ok(())
+}
+
 pub fn support_asset<T: Config>(asset: ChainAsset, asset_info: AssetInfo) -> Result<(), Reason> {
     SupportedAssets::insert(&asset, asset_info);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{symbol::ETH, tests::*, types::MinerShares};
+
+    #[test]
+    fn test_set_liquidity_factors() {
+        let asset = ChainAsset::Eth([238; 20]);
+        let asset_info = AssetInfo::minimal(asset, ETH);
+        let factor = LiquidityFactor::from_nominal("0.8");
+
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                set_liquidity_factor::<Test>(asset, factor),
+                Err(Reason::AssetNotSupported)
+            );
+
+            SupportedAssets::insert(&asset, asset_info);
+            assert_eq!(set_liquidity_factor::<Test>(asset, factor), Ok(()));
+            assert_eq!(
+                SupportedAssets::get(asset),
+                Some(AssetInfo {
+                    collateral_factor: factor,
+                    borrow_factor: factor,
+                    ..asset_info
+                })
+            );
+
+            assert_eq!(
+                set_collateral_factor::<Test>(asset, LiquidityFactor::from_nominal("1.1")),
+                Err(Reason::InvalidLiquidityFactor)
+            );
+            assert_eq!(
+                set_collateral_factor::<Test>(asset, LiquidityFactor::from_nominal("0.9")),
+                Ok(())
+            );
+            assert_eq!(
+                set_borrow_factor::<Test>(asset, Factor::ZERO),
+                Err(Reason::InvalidLiquidityFactor)
+            );
+            assert_eq!(
+                set_borrow_factor::<Test>(asset, LiquidityFactor::from_nominal("0.5")),
+                Ok(())
+            );
+            assert_eq!(
+                SupportedAssets::get(asset),
+                Some(AssetInfo {
+                    collateral_factor: LiquidityFactor::from_nominal("0.9"),
+                    borrow_factor: LiquidityFactor::from_nominal("0.5"),
+                    ..asset_info
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn test_set_borrow_cap() {
+        let asset = ChainAsset::Eth([238; 20]);
+        let asset_info = AssetInfo::minimal(asset, ETH);
+
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                set_borrow_cap::<Test>(asset, 1000),
+                Err(Reason::AssetNotSupported)
+            );
+
+            SupportedAssets::insert(&asset, asset_info);
+            assert_eq!(set_borrow_cap::<Test>(asset, 1000), Ok(()));
+            assert_eq!(
+                SupportedAssets::get(asset),
+                Some(AssetInfo {
+                    borrow_cap: 1000,
+                    ..asset_info
+                })
+            );
+
+            let events_post: Vec<_> = System::events().into_iter().collect();
+            let event = events_post.into_iter().last().unwrap();
+            assert_eq!(
+                mock::Event::pallet_cash(crate::Event::SetBorrowCap(asset, 1000)),
+                event.event
+            );
+        });
+    }
+
+    #[test]
+    fn test_set_liquidation_incentive() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(GlobalLiquidationIncentive::get(), 800);
+            assert_eq!(
+                set_liquidation_incentive::<Test>(MAX_LIQUIDATION_INCENTIVE + 1),
+                Err(Reason::InvalidLiquidationIncentive)
+            );
+            assert_eq!(set_liquidation_incentive::<Test>(500), Ok(()));
+            assert_eq!(GlobalLiquidationIncentive::get(), 500);
+
+            let events_post: Vec<_> = System::events().into_iter().collect();
+            let event = events_post.into_iter().last().unwrap();
+            assert_eq!(
+                mock::Event::pallet_cash(crate::Event::SetLiquidationIncentive(500)),
+                event.event
+            );
+        });
+    }
+
+    #[test]
+    fn test_set_close_factor() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(CloseFactor::get(), 5_000);
+            assert_eq!(set_close_factor::<Test>(0), Err(Reason::InvalidCloseFactor));
+            assert_eq!(
+                set_close_factor::<Test>(MAX_CLOSE_FACTOR + 1),
+                Err(Reason::InvalidCloseFactor)
+            );
+            assert_eq!(set_close_factor::<Test>(MAX_CLOSE_FACTOR), Ok(()));
+            assert_eq!(CloseFactor::get(), MAX_CLOSE_FACTOR);
+
+            let events_post: Vec<_> = System::events().into_iter().collect();
+            let event = events_post.into_iter().last().unwrap();
+            assert_eq!(
+                mock::Event::pallet_cash(crate::Event::SetCloseFactor(MAX_CLOSE_FACTOR)),
+                event.event
+            );
+        });
+    }
+
+    #[test]
+    fn test_set_spread() {
+        let asset = ChainAsset::Eth([238; 20]);
+        let asset_info = AssetInfo {
+            miner_shares: MinerShares::from_nominal("0.5"),
+            ..AssetInfo::minimal(asset, ETH)
+        };
+
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                set_spread::<Test>(asset, 1000),
+                Err(Reason::AssetNotSupported)
+            );
+
+            SupportedAssets::insert(&asset, asset_info);
+            assert_eq!(
+                set_spread::<Test>(asset, MAX_SPREAD + 1),
+                Err(Reason::InvalidSpread)
+            );
+            assert_eq!(set_spread::<Test>(asset, 5001), Err(Reason::InvalidSpread));
+            assert_eq!(set_spread::<Test>(asset, 1000), Ok(()));
+            assert_eq!(Spreads::get(asset), 1000);
+
+            let events_post: Vec<_> = System::events().into_iter().collect();
+            let event = events_post.into_iter().last().unwrap();
+            assert_eq!(
+                mock::Event::pallet_cash(crate::Event::SetSpread(asset, 1000)),
+                event.event
+            );
+        });
+    }
+}
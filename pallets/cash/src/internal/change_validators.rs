@@ -1,4 +1,6 @@
-use frame_support::storage::{IterableStorageMap, StorageMap};
+use frame_support::storage::{
+    with_transaction, IterableStorageMap, StorageMap, TransactionOutcome,
+};
 
 use crate::{
     internal, reason::Reason, require, types::ValidatorKeys, Config, Event, Module, NextValidators,
@@ -15,6 +17,14 @@ pub fn change_validators<T: Config>(validators: Vec<ValidatorKeys>) -> Result<()
         );
     }
 
+    // dispatch the notices before touching the validators, as a group so none are queued if any fail
+    with_transaction(|| {
+        match internal::notices::dispatch_change_authority_notice::<T>(validators.clone()) {
+            Ok(()) => TransactionOutcome::Commit(Ok(())),
+            Err(err) => TransactionOutcome::Rollback(Err(err)),
+        }
+    })?;
+
     for (id, _keys) in NextValidators::iter() {
         NextValidators::take(&id);
     }
@@ -22,9 +32,7 @@ pub fn change_validators<T: Config>(validators: Vec<ValidatorKeys>) -> Result<()
         NextValidators::insert(&keys.substrate_id, keys);
     }
 
-    <Module<T>>::deposit_event(Event::ChangeValidators(validators.clone()));
-
-    internal::notices::dispatch_change_authority_notice::<T>(validators);
+    <Module<T>>::deposit_event(Event::ChangeValidators(validators));
 
     // rotate to the currently queued session, and queue a new session with the new validators in NextValidators
     <T>::SessionInterface::rotate_session();
@@ -36,8 +44,8 @@ pub fn change_validators<T: Config>(validators: Vec<ValidatorKeys>) -> Result<()
 mod tests {
     use super::*;
     use crate::{
-        chains::*, notices::*, reason::Reason, tests::*, AccountId32, LatestNotice, NoticeStates,
-        Notices, ValidatorKeys,
+        chains::*, notices::*, reason::Reason, tests::*, types::AssetInfo, AccountId32,
+        LatestNotice, NoticeStates, Notices, SupportedAssets, ValidatorKeys,
     };
     use frame_support::storage::{IterableStorageDoubleMap, StorageDoubleMap, StorageMap};
     use mock::opaque::MockSessionKeys;
@@ -49,6 +57,8 @@ mod tests {
             let prev_keys = ValidatorKeys {
                 substrate_id: prev_substrate_id.clone(),
                 eth_address: [9; 20],
+                dot_id: [0; 32],
+                sol_id: [0; 32],
             };
 
             NextValidators::insert(prev_substrate_id, prev_keys);
@@ -58,6 +68,8 @@ mod tests {
             let val_keys = vec![ValidatorKeys {
                 substrate_id: substrate_id.clone(),
                 eth_address: eth_address.clone(),
+                dot_id: [0; 32],
+                sol_id: [0; 32],
             }];
             let session_keys = MockSessionKeys { dummy: 1u64.into() };
             assert_eq!(
@@ -116,6 +128,8 @@ mod tests {
             let vals = vec![ValidatorKeys {
                 substrate_id: substrate_id.clone(),
                 eth_address: [1; 20],
+                dot_id: [0; 32],
+                sol_id: [0; 32],
             }];
             assert_eq!(
                 change_validators::<Test>(vals.clone()),
@@ -123,4 +137,52 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn test_change_val_notice_failure_has_no_effects() {
+        new_test_ext().execute_with(|| {
+            let prev_substrate_id: AccountId32 = [8; 32].into();
+            let prev_keys = ValidatorKeys {
+                substrate_id: prev_substrate_id.clone(),
+                eth_address: [9; 20],
+                dot_id: [0; 32],
+                sol_id: [0; 32],
+            };
+            NextValidators::insert(prev_substrate_id, prev_keys.clone());
+
+            let substrate_id: AccountId32 = [2; 32].into();
+            let val_keys = vec![ValidatorKeys {
+                substrate_id: substrate_id.clone(),
+                eth_address: [1; 20],
+                dot_id: [0; 32],
+                sol_id: [0; 32],
+            }];
+            assert_eq!(
+                Ok(()),
+                Session::set_keys(
+                    frame_system::RawOrigin::Signed(substrate_id).into(),
+                    MockSessionKeys { dummy: 1u64.into() },
+                    vec![]
+                )
+            );
+
+            // the matic notice can't be built on top of a corrupt parent, after the eth notice is
+            let matic_asset = ChainAsset::Matic([1; 20]);
+            SupportedAssets::insert(&matic_asset, AssetInfo::minimal(matic_asset, ETH));
+            LatestNotice::insert(ChainId::Matic, (NoticeId(0, 0), ChainHash::Eth([0; 32])));
+            let events_pre = System::events().len();
+
+            assert_eq!(
+                change_validators::<Test>(val_keys),
+                Err(Reason::InvalidChain)
+            );
+            assert_eq!(
+                NextValidators::iter().map(|x| x.1).collect::<Vec<_>>(),
+                vec![prev_keys]
+            );
+            assert_eq!(LatestNotice::get(ChainId::Eth), None);
+            assert_eq!(NoticeStates::iter().count(), 0);
+            assert_eq!(System::events().len(), events_pre);
+        });
+    }
 }
@@ -1,4 +1,5 @@
 use codec::Encode;
+use ethereum_client::EthereumClientError;
 use frame_support::storage::{IterableStorageMap, StorageMap};
 use frame_system::offchain::SubmitTransaction;
 use our_std::collections::btree_set::BTreeSet;
@@ -8,57 +9,124 @@ use sp_runtime::offchain::{
 };
 
 use crate::{
-    chains::{Chain, Ethereum},
+    chains::{eth, Chain, ChainId, Ethereum, EVM_CHAINS},
     core::{apply_chain_event_internal, passes_validation_threshold},
-    events::{encode_block_hex, fetch_eth_events, ChainLogEvent, ChainLogId, EventState},
+    events::{
+        fetch_block_hash, fetch_evm_events, get_starport_config, ChainLogEvent, ChainLogId,
+        EventError, EventState, StarportConfig,
+    },
     log,
+    params::ETH_FINALIZATION_BLOCKS,
     reason::Reason,
     types::ValidatorSig,
     Call, Config, Event as EventT, EventStates, Module, Validators,
 };
 
-// OCW storage constants
-const OCW_STORAGE_LOCK_ETHEREUM_EVENTS: &[u8; 34] = b"cash::storage_lock_ethereum_events";
-const OCW_LATEST_CACHED_ETHEREUM_BLOCK: &[u8; 34] = b"cash::latest_cached_ethereum_block";
+/// The OCW storage keys which track fetching events from an EVM chain.
+struct EventCursorKeys {
+    lock: Vec<u8>,
+    latest_block: Vec<u8>,
+    latest_block_hash: Vec<u8>,
+}
+
+fn event_cursor_keys(chain_id: ChainId) -> Result<EventCursorKeys, Reason> {
+    let name = chain_id.evm_chain()?.cursor_name;
+    Ok(EventCursorKeys {
+        lock: format!("cash::storage_lock_{}_events", name).into_bytes(),
+        latest_block: format!("cash::latest_cached_{}_block", name).into_bytes(),
+        latest_block_hash: format!("cash::latest_cached_{}_block_hash", name).into_bytes(),
+    })
+}
 
-// XXX disambiguate whats for ethereum vs not
+/// Check that the last block we fetched events up to is still part of the canonical chain.
+/// If it's not, the cached range was reorged out from under us, and we rewind the cache
+///  so that the affected blocks are fetched (and signed) again.
+///
+/// The rewound block is saved right away, so it isn't lost if fetching the range again fails.
+/// This must only be called while holding the events lock.
+fn check_cached_block_hash(
+    keys: &EventCursorKeys,
+    starport: &StarportConfig,
+    cached_block_num: u64,
+) -> Result<u64, Reason> {
+    let s_hash = StorageValueRef::persistent(&keys.latest_block_hash);
+    if let Some(Some(cached_block_hash)) = s_hash.get::<eth::BlockHash>() {
+        let canonical_block_hash = fetch_block_hash(starport, cached_block_num).map_err(|err| {
+            log!("Error while fetching cached block hash: {:?}", err);
+            Reason::FetchError
+        })?;
+        if canonical_block_hash != cached_block_hash {
+            let rewound_block_num = cached_block_num.saturating_sub(ETH_FINALIZATION_BLOCKS as u64);
+            log!(
+                "Cached block {} was reorged ({} != {}), rewinding to block {}",
+                cached_block_num,
+                hex::encode(cached_block_hash),
+                hex::encode(canonical_block_hash),
+                rewound_block_num
+            );
+            StorageValueRef::persistent(&keys.latest_block).set(&rewound_block_num);
+            s_hash.clear();
+            return Ok(rewound_block_num);
+        }
+    }
+    Ok(cached_block_num)
+}
+
+/// Fetch and submit events from the starport on every EVM chain this validator follows.
+/// A failure on one chain doesn't hold back the others, but is still reported.
 pub fn fetch_events<T: Config>() -> Result<(), Reason> {
-    let s_info = StorageValueRef::persistent(OCW_LATEST_CACHED_ETHEREUM_BLOCK);
-
-    let from_block: String = if let Some(Some(cached_block_num)) = s_info.get::<u64>() {
-        // Ethereum block number has been cached, fetch events starting from the next after cached block
-        log!("Last cached block number: {:?}", cached_block_num);
-        encode_block_hex(cached_block_num + 1)
-    } else {
-        // Validator's cache is empty, fetch events from the earliest block with pending events
-        log!("Block number has not been cached yet");
-        // XXX TODO: Add back?
-        // let block_numbers: Vec<u32> = EthEventQueue::iter()
-        //     .filter_map(|((block_number, _log_index), status)| {
-        //         if match status {
-        //             EventStatus::<Ethereum>::Pending { .. } => true,
-        //             _ => false,
-        //         } {
-        //             Some(block_number)
-        //         } else {
-        //             None
-        //         }
-        //     })
-        //     .collect();
-        // let pending_events_block = block_numbers.iter().min();
-        // if pending_events_block.is_some() {
-        //     let events_block: u32 = *pending_events_block.unwrap();
-        //     from_block = format!("{:#X}", events_block);
-        // } else {
-        //}
-        String::from("earliest")
-    };
-
-    log!("Fetching events starting from block {:?}", from_block);
-
-    let mut lock = StorageLock::<Time>::new(OCW_STORAGE_LOCK_ETHEREUM_EVENTS);
+    let mut result = Ok(());
+    for chain_id in EVM_CHAINS.iter().map(|evm_chain| evm_chain.chain_id) {
+        let chain_result = match get_starport_config(chain_id) {
+            Ok(Some(starport)) => fetch_chain_events::<T>(chain_id, &starport),
+            Ok(None) => {
+                log!("Not following the starport on {:?}", chain_id);
+                Ok(())
+            }
+            Err(err) => {
+                log!(
+                    "Error while getting {:?} starport config: {:?}",
+                    chain_id,
+                    err
+                );
+                Err(Reason::FetchError)
+            }
+        };
+        if let Err(reason) = chain_result {
+            log!("Error while fetching {:?} events: {:?}", chain_id, reason);
+            result = result.and(Err(reason));
+        }
+    }
+    result
+}
+
+fn fetch_chain_events<T: Config>(
+    chain_id: ChainId,
+    starport: &StarportConfig,
+) -> Result<(), Reason> {
+    let keys = event_cursor_keys(chain_id)?;
+    let s_info = StorageValueRef::persistent(&keys.latest_block);
+    let s_hash = StorageValueRef::persistent(&keys.latest_block_hash);
+
+    let mut lock = StorageLock::<Time>::new(&keys.lock);
     if let Ok(_guard) = lock.try_lock() {
-        match fetch_eth_events(from_block) {
+        let from_block: u64 = if let Some(Some(cached_block_num)) = s_info.get::<u64>() {
+            // Block number has been cached, fetch events starting from the next after cached block
+            log!("Last cached block number: {:?}", cached_block_num);
+            check_cached_block_hash(&keys, starport, cached_block_num)? + 1
+        } else {
+            // Validator's cache is empty, fetch events from where the starport was deployed
+            log!("Block number has not been cached yet");
+            starport.deploy_block
+        };
+
+        log!(
+            "Fetching {:?} events starting from block {:?}",
+            chain_id,
+            from_block
+        );
+
+        match fetch_evm_events(chain_id, starport, from_block) {
             Ok(event_info) => {
                 log!("Result: {:?}", event_info);
 
@@ -68,8 +136,22 @@ pub fn fetch_events<T: Config>() -> Result<(), Reason> {
                 // Send extrinsics for all events
                 submit_events::<T>(event_info.events)?;
 
-                // Save latest block in ocw storage
+                // Save latest block (and its hash, if we fetched it) in ocw storage
                 s_info.set(&event_info.latest_eth_block);
+                if let Some(latest_eth_block_hash) = event_info.latest_eth_block_hash {
+                    s_hash.set(&latest_eth_block_hash);
+                }
+            }
+            Err(EventError::EthereumClientError(EthereumClientError::BlockTooManyResults(
+                block_num,
+            ))) => {
+                log!(
+                    "Every {:?} RPC endpoint refuses the logs of block {} for returning too many results, \
+                     an endpoint which allows more results per call is needed to fetch past it",
+                    chain_id,
+                    block_num
+                );
+                return Err(Reason::FetchError);
             }
             Err(err) => {
                 log!("Error while fetching events: {:?}", err);
@@ -180,3 +262,63 @@ pub fn receive_event<T: Config>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{events::tests::rpc_call_to, tests::*};
+
+    const RPC_URL: &str = "https://eth-a.example.com";
+
+    #[test]
+    fn test_fetch_chain_events_keeps_rewind_after_failed_fetch() {
+        let cached_block_num = 11695177;
+        let calls = vec![
+            rpc_call_to(
+                RPC_URL,
+                "eth_getBlockByNumber",
+                r#""0xB27449",false"#,
+                testdata::json_responses::BLOCK_BY_NUMBER_RESPONSE.to_vec(),
+            ),
+            rpc_call_to(
+                RPC_URL,
+                "eth_blockNumber",
+                "",
+                b"<html>502 Bad Gateway</html>".to_vec(),
+            ),
+        ];
+        let starport = StarportConfig {
+            endpoints: vec![RPC_URL.into()],
+            starport_address: String::from("0xbbde1662bC3ED16aA8C618c9833c801F3543B587"),
+            deploy_block: 0,
+            quorum: 1,
+            paging: ethereum_client::LogsPaging {
+                max_block_span: 100,
+                batch_size: 1,
+                max_batches: 1,
+            },
+        };
+
+        let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+        t.execute_with(|| {
+            let keys = event_cursor_keys(ChainId::Eth).unwrap();
+            let s_info = StorageValueRef::persistent(&keys.latest_block);
+            let s_hash = StorageValueRef::persistent(&keys.latest_block_hash);
+            s_info.set(&cached_block_num);
+            s_hash.set(&[1u8; 32]);
+
+            // the cached block was reorged, but fetching the range again fails
+            assert_eq!(
+                fetch_chain_events::<Test>(ChainId::Eth, &starport),
+                Err(Reason::FetchError)
+            );
+
+            // so the next run still fetches it again, from the rewound block
+            assert_eq!(
+                s_info.get::<u64>(),
+                Some(Some(cached_block_num - ETH_FINALIZATION_BLOCKS as u64))
+            );
+            assert_eq!(s_hash.get::<eth::BlockHash>(), None);
+        });
+    }
+}
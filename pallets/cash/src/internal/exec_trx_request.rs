@@ -1,19 +1,19 @@
 use frame_support::storage::{StorageMap, StorageValue};
 
-#[cfg(test)]
-use crate::core::get_asset;
 #[cfg(not(test))]
 use crate::core::{
-    extract_cash_principal_internal, extract_internal, get_asset,
-    liquidate_cash_collateral_internal, liquidate_cash_principal_internal, liquidate_internal,
-    transfer_cash_principal_internal, transfer_internal,
+    extract_cash_principal_internal, extract_internal, liquidate_cash_collateral_internal,
+    liquidate_cash_principal_internal, liquidate_internal, transfer_cash_principal_internal,
+    transfer_internal,
 };
 use crate::{
     chains::{ChainAccount, ChainAccountSignature},
-    log,
-    reason::Reason,
+    core::{batch_internal, get_asset, LiquidityCheck},
+    eip712, log,
+    reason::{Reason, TrxReqParseError},
     require,
     symbol::CASH,
+    trx_req::{resolve_amount, resolve_asset},
     types::{CashOrChainAsset, Nonce, Quantity},
     CashPrincipals, Config, GlobalCashIndex, Nonces,
 };
@@ -33,6 +33,29 @@ pub fn prepend_nonce(payload: &Vec<u8>, nonce: Nonce) -> Vec<u8> {
     result
 }
 
+/// Recover the sender of a request signed either as a nonce prefixed message or as EIP-712 typed data.
+pub fn recover_sender<T: Config>(
+    request: &Vec<u8>,
+    request_str: &str,
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<ChainAccount, Reason> {
+    signature
+        .recover_account(&prepend_nonce(request, nonce)[..])
+        .or_else(|err| match signature {
+            ChainAccountSignature::Eth(..) => {
+                let trx_request = trx_request::parse_request(request_str)?;
+                let genesis_hash = eip712::genesis_hash::<T>().ok_or(err)?;
+                signature.recover_typed_account(&eip712::typed_data(
+                    &trx_request,
+                    nonce,
+                    genesis_hash,
+                ))
+            }
+            _ => Err(err),
+        })
+}
+
 // XXX 2 entry points?
 pub fn exec<T: Config>(
     request: Vec<u8>,
@@ -41,7 +64,7 @@ pub fn exec<T: Config>(
 ) -> Result<(), Reason> {
     log!("exec: {}", nonce);
     let request_str: &str = str::from_utf8(&request[..]).map_err(|_| Reason::InvalidUTF8)?;
-    let sender = signature.recover_account(&prepend_nonce(&request, nonce)[..])?;
+    let sender = recover_sender::<T>(&request, request_str, signature, nonce)?;
     exec_trx_request::<T>(request_str, sender, Some(nonce))
 }
 
@@ -55,8 +78,7 @@ pub fn is_minimally_valid_trx_request<T: Config>(
     trx_request::parse_request(request_str)?;
 
     // Signature check
-    let sender = signature
-        .recover_account(&prepend_nonce(&request, nonce)[..])
+    let sender = recover_sender::<T>(&request, request_str, signature, nonce)
         .map_err(|_| Reason::SignatureAccountMismatch)?;
 
     // Nonce check
@@ -85,6 +107,36 @@ pub fn exec_trx_request<T: Config>(
         );
     }
 
+    match trx_request {
+        trx_request::TrxRequest::Batch(requests) => exec_batch::<T>(sender, requests)?,
+        request => exec_operation::<T>(sender, request, LiquidityCheck::Immediate)?,
+    }
+
+    if let Some(nonce) = nonce_opt {
+        // Update user nonce
+        Nonces::insert(sender, nonce + 1);
+    }
+
+    Ok(())
+}
+
+/// Execute each request of a batch in order, all-or-nothing, checking liquidity once at the end.
+fn exec_batch<T: Config>(
+    sender: ChainAccount,
+    requests: Vec<trx_request::TrxRequest>,
+) -> Result<(), Reason> {
+    batch_internal::<T>(sender, |liquidity_check| {
+        requests
+            .into_iter()
+            .try_for_each(|request| exec_operation::<T>(sender, request, liquidity_check))
+    })
+}
+
+fn exec_operation<T: Config>(
+    sender: ChainAccount,
+    trx_request: trx_request::TrxRequest,
+    liquidity_check: LiquidityCheck,
+) -> Result<(), Reason> {
     // XXX still controversial as we read from storage sometimes redundantly,
     //  and calculate amount from principal provided
     //  I think its ok as we should be able to cache things extremely well,
@@ -92,18 +144,19 @@ pub fn exec_trx_request<T: Config>(
     //   and its safer to use the principal -> amount than the user amount
     match trx_request {
         trx_request::TrxRequest::Extract(max_amount, asset, account) => {
-            match CashOrChainAsset::from(asset) {
-                CashOrChainAsset::Cash => match max_amount {
-                    trx_request::MaxAmount::Max => {
+            match resolve_asset::<T>(asset)? {
+                CashOrChainAsset::Cash => match resolve_amount(max_amount, CASH.decimals)? {
+                    None => {
                         let principal_amount = CashPrincipals::get(sender).amount_withdrawable()?;
                         extract_cash_principal_internal::<T>(
                             sender,
                             account.into(),
                             principal_amount,
+                            liquidity_check,
                         )?;
                     }
 
-                    trx_request::MaxAmount::Amount(amount) => {
+                    Some(amount) => {
                         let index = GlobalCashIndex::get();
                         let principal_amount =
                             index.cash_principal_amount(Quantity::new(amount, CASH))?;
@@ -111,36 +164,46 @@ pub fn exec_trx_request<T: Config>(
                             sender,
                             account.into(),
                             principal_amount,
+                            liquidity_check,
                         )?;
                     }
                 },
 
-                CashOrChainAsset::ChainAsset(chain_asset) => match max_amount {
-                    trx_request::MaxAmount::Max => {
-                        return Err(Reason::MaxForNonCashAsset);
-                    }
-                    trx_request::MaxAmount::Amount(amount) => {
-                        let asset = get_asset::<T>(chain_asset)?;
-                        let asset_amount = asset.as_quantity(amount.into());
-                        extract_internal::<T>(asset, sender, account.into(), asset_amount)?;
+                CashOrChainAsset::ChainAsset(chain_asset) => {
+                    let asset = get_asset::<T>(chain_asset)?;
+                    match resolve_amount(max_amount, asset.decimals)? {
+                        None => {
+                            return Err(Reason::MaxForNonCashAsset);
+                        }
+                        Some(amount) => {
+                            let asset_amount = asset.as_quantity(amount.into());
+                            extract_internal::<T>(
+                                asset,
+                                sender,
+                                account.into(),
+                                asset_amount,
+                                liquidity_check,
+                            )?;
+                        }
                     }
-                },
+                }
             }
         }
 
         trx_request::TrxRequest::Transfer(max_amount, asset, account) => {
-            match CashOrChainAsset::from(asset) {
-                CashOrChainAsset::Cash => match max_amount {
-                    trx_request::MaxAmount::Max => {
+            match resolve_asset::<T>(asset)? {
+                CashOrChainAsset::Cash => match resolve_amount(max_amount, CASH.decimals)? {
+                    None => {
                         let principal_amount = CashPrincipals::get(sender).amount_withdrawable()?;
                         transfer_cash_principal_internal::<T>(
                             sender,
                             account.into(),
                             principal_amount,
+                            liquidity_check,
                         )?;
                     }
 
-                    trx_request::MaxAmount::Amount(amount) => {
+                    Some(amount) => {
                         let index = GlobalCashIndex::get();
                         let principal_amount =
                             index.cash_principal_amount(Quantity::new(amount, CASH))?;
@@ -148,21 +211,30 @@ pub fn exec_trx_request<T: Config>(
                             sender,
                             account.into(),
                             principal_amount,
+                            liquidity_check,
                         )?;
                     }
                 },
 
-                CashOrChainAsset::ChainAsset(chain_asset) => match max_amount {
-                    trx_request::MaxAmount::Max => {
-                        return Err(Reason::MaxForNonCashAsset);
-                    }
-
-                    trx_request::MaxAmount::Amount(amount) => {
-                        let asset = get_asset::<T>(chain_asset)?;
-                        let asset_amount = asset.as_quantity(amount.into());
-                        transfer_internal::<T>(asset, sender, account.into(), asset_amount)?;
+                CashOrChainAsset::ChainAsset(chain_asset) => {
+                    let asset = get_asset::<T>(chain_asset)?;
+                    match resolve_amount(max_amount, asset.decimals)? {
+                        None => {
+                            return Err(Reason::MaxForNonCashAsset);
+                        }
+
+                        Some(amount) => {
+                            let asset_amount = asset.as_quantity(amount.into());
+                            transfer_internal::<T>(
+                                asset,
+                                sender,
+                                account.into(),
+                                asset_amount,
+                                liquidity_check,
+                            )?;
+                        }
                     }
-                },
+                }
             }
         }
 
@@ -172,15 +244,15 @@ pub fn exec_trx_request<T: Config>(
             trx_collateral_asset,
             borrower,
         ) => match (
-            CashOrChainAsset::from(trx_borrowed_asset),
-            CashOrChainAsset::from(trx_collateral_asset),
+            resolve_asset::<T>(trx_borrowed_asset)?,
+            resolve_asset::<T>(trx_collateral_asset)?,
         ) {
             (x, y) if x == y => Err(Reason::InKindLiquidation),
             (CashOrChainAsset::Cash, CashOrChainAsset::ChainAsset(collateral)) => {
                 let collateral_asset = get_asset::<T>(collateral)?;
-                let cash_principal_amount = match max_amount {
-                    trx_request::MaxAmount::Max => panic!("Not supported"), // TODO
-                    trx_request::MaxAmount::Amount(amount) => {
+                let cash_principal_amount = match resolve_amount(max_amount, CASH.decimals)? {
+                    None => return Err(Reason::NotImplemented), // TODO: max liquidation
+                    Some(amount) => {
                         let index = GlobalCashIndex::get();
                         index.cash_principal_amount(Quantity::new(amount, CASH))?
                     }
@@ -191,34 +263,34 @@ pub fn exec_trx_request<T: Config>(
                     sender,
                     borrower.into(),
                     cash_principal_amount,
+                    liquidity_check,
                 )
             }
             (CashOrChainAsset::ChainAsset(borrowed), CashOrChainAsset::Cash) => {
                 let borrowed_asset = get_asset::<T>(borrowed)?;
-                let borrowed_asset_amount = match max_amount {
-                    trx_request::MaxAmount::Max => panic!("Not supported"), // TODO
-                    trx_request::MaxAmount::Amount(amount) => {
-                        borrowed_asset.as_quantity(amount.into())
-                    }
-                };
+                let borrowed_asset_amount =
+                    match resolve_amount(max_amount, borrowed_asset.decimals)? {
+                        None => return Err(Reason::NotImplemented), // TODO: max liquidation
+                        Some(amount) => borrowed_asset.as_quantity(amount.into()),
+                    };
 
                 liquidate_cash_collateral_internal::<T>(
                     borrowed_asset,
                     sender,
                     borrower.into(),
                     borrowed_asset_amount,
+                    liquidity_check,
                 )
             }
 
             (CashOrChainAsset::ChainAsset(borrowed), CashOrChainAsset::ChainAsset(collateral)) => {
                 let borrowed_asset = get_asset::<T>(borrowed)?;
                 let collateral_asset = get_asset::<T>(collateral)?;
-                let borrowed_asset_amount = match max_amount {
-                    trx_request::MaxAmount::Max => panic!("Not supported"), // TODO
-                    trx_request::MaxAmount::Amount(amount) => {
-                        borrowed_asset.as_quantity(amount.into())
-                    }
-                };
+                let borrowed_asset_amount =
+                    match resolve_amount(max_amount, borrowed_asset.decimals)? {
+                        None => return Err(Reason::NotImplemented), // TODO: max liquidation
+                        Some(amount) => borrowed_asset.as_quantity(amount.into()),
+                    };
 
                 liquidate_internal::<T>(
                     borrowed_asset,
@@ -226,15 +298,15 @@ pub fn exec_trx_request<T: Config>(
                     sender,
                     borrower.into(),
                     borrowed_asset_amount,
+                    liquidity_check,
                 )
             }
             _ => Err(Reason::InvalidLiquidation), // Probably isn't possible
         }?,
-    }
 
-    if let Some(nonce) = nonce_opt {
-        // Update user nonce
-        Nonces::insert(sender, nonce + 1);
+        trx_request::TrxRequest::Batch(_) => {
+            return Err(Reason::TrxRequestParseError(TrxReqParseError::NestedBatch))
+        }
     }
 
     Ok(())
@@ -246,6 +318,7 @@ mod mocked_core {
 
     use crate::{
         chains::{ChainAccount, ChainAsset},
+        core::LiquidityCheck,
         reason::Reason,
         types::{AssetInfo, AssetQuantity, CashPrincipalAmount},
         Config,
@@ -258,6 +331,7 @@ mod mocked_core {
         holder: ChainAccount,
         recipient: ChainAccount,
         amount: AssetQuantity,
+        _liquidity_check: LiquidityCheck,
     ) -> Result<(), Reason> {
         let latest_call_str = format!(
             "extract_internal: {:?}, {:?}, {:?}, {:?}",
@@ -277,6 +351,7 @@ mod mocked_core {
         holder: ChainAccount,
         recipient: ChainAccount,
         principal: CashPrincipalAmount,
+        _liquidity_check: LiquidityCheck,
     ) -> Result<(), Reason> {
         let latest_call_str = format!(
             "extract_cash_principal_internal: {:?}, {:?}, {:?}",
@@ -296,6 +371,7 @@ mod mocked_core {
         sender: ChainAccount,
         recipient: ChainAccount,
         amount: AssetQuantity,
+        _liquidity_check: LiquidityCheck,
     ) -> Result<(), Reason> {
         let latest_call_str = format!(
             "transfer_internal: {:?}, {:?}, {:?}, {:?}",
@@ -315,6 +391,7 @@ mod mocked_core {
         sender: ChainAccount,
         recipient: ChainAccount,
         principal: CashPrincipalAmount,
+        _liquidity_check: LiquidityCheck,
     ) -> Result<(), Reason> {
         let latest_call_str = format!(
             "transfer_cash_principal_internal: {:?}, {:?}, {:?}",
@@ -335,6 +412,7 @@ mod mocked_core {
         liquidator: ChainAccount,
         borrower: ChainAccount,
         amount: AssetQuantity,
+        _liquidity_check: LiquidityCheck,
     ) -> Result<(), Reason> {
         let latest_call_str = format!(
             "liquidate_internal: {:?}, {:?}, {:?}, {:?}, {:?}",
@@ -356,6 +434,7 @@ mod mocked_core {
         liquidator: ChainAccount,
         borrower: ChainAccount,
         principal: CashPrincipalAmount,
+        _liquidity_check: LiquidityCheck,
     ) -> Result<(), Reason> {
         let latest_call_str = format!(
             "liquidate_cash_principal_internal: {:?}, {:?}, {:?}, {:?}",
@@ -376,6 +455,7 @@ mod mocked_core {
         liquidator: ChainAccount,
         borrower: ChainAccount,
         amount: AssetQuantity,
+        _liquidity_check: LiquidityCheck,
     ) -> Result<(), Reason> {
         let latest_call_str = format!(
             "liquidate_cash_collateral_internal: {:?}, {:?}, {:?}, {:?}",
@@ -405,8 +485,10 @@ mod tests {
         chains::*, factor::*, rates::*, reason::TrxReqParseError, tests::mock::*, tests::*,
         types::*, *,
     };
+    use codec::Encode;
     use mocked_core::get_latest_call_result;
     use serial_test::serial;
+    use sp_core::Pair;
 
     fn init_eth_asset() -> Result<(), Reason> {
         let kink_rate = 105;
@@ -426,7 +508,8 @@ mod tests {
         const BAT: Units = Units::from_ticker_str("BAT", 18);
         let asset = ChainAsset::from_str("Eth:0x0d8775f648430679a709e98d2b0cb6250d2887ef")?;
         let asset_info = AssetInfo {
-            liquidity_factor: LiquidityFactor::from_nominal("0.6543"),
+            collateral_factor: LiquidityFactor::from_nominal("0.6543"),
+            borrow_factor: LiquidityFactor::from_nominal("0.6543"),
             ..AssetInfo::minimal(asset, BAT)
         };
 
@@ -471,6 +554,40 @@ mod tests {
         });
     }
 
+    #[test]
+    #[serial]
+    fn exec_trx_request_extract_decimal_symbol() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+
+            let req_str = "(Extract 1.5 ETH Eth:0x0101010101010101010101010101010101010101)";
+            let account = ChainAccount::Eth([20; 20]);
+            let nonce = Some(0);
+
+            assert_ok!(exec_trx_request::<Test>(req_str, account, nonce));
+            let actual = get_latest_call_result();
+            let expected = "extract_internal: Eth([238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238]), \
+            \"ETH:0x1414141414141414141414141414141414141414\", \
+            \"ETH:0x0101010101010101010101010101010101010101\", 1500000000000000000";
+            assert_eq!(actual, expected);
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn exec_trx_request_extract_inexact_cash() {
+        new_test_ext().execute_with(|| {
+            let req_str = "(Extract 0.0000001 CASH Eth:0x0101010101010101010101010101010101010101)";
+            let account = ChainAccount::Eth([20; 20]);
+            let nonce = Some(0);
+
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, nonce),
+                Err(Reason::InexactAmount)
+            );
+        });
+    }
+
     #[test]
     #[serial]
     fn exec_trx_transfer_internal() {
@@ -570,6 +687,100 @@ mod tests {
         });
     }
 
+    #[test]
+    #[serial]
+    fn exec_trx_max_liquidation() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+
+            let req_str = "(Liquidate Max Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+                Cash Eth:0x0101010101010101010101010101010101010101)";
+            let account = ChainAccount::Eth([20; 20]);
+            let nonce = Some(0);
+
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, nonce),
+                Err(Reason::NotImplemented)
+            );
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn exec_trx_request_signed_by_gateway_account() {
+        new_test_ext().execute_with(|| {
+            let request: Vec<u8> =
+                "(Transfer 5 Cash Gate:5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty)".into();
+            let pair = sp_core::sr25519::Pair::from_string("//Alice", None).unwrap();
+            let message = prepend_nonce(&request, 0);
+            let mut signature = [0u8; 65];
+            signature.copy_from_slice(
+                &sp_runtime::MultiSignature::from(pair.sign(&message[..])).encode()[..],
+            );
+            let sender = ChainAccount::Gate(pair.public().0);
+
+            assert_ok!(exec::<Test>(
+                request.clone(),
+                ChainAccountSignature::Gate(pair.public().0, signature),
+                0
+            ));
+            let actual = get_latest_call_result();
+            let expected = "transfer_cash_principal_internal: \
+            \"GATE:5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY\", \
+            \"GATE:5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty\", 5";
+            assert_eq!(actual, expected);
+            assert_eq!(Nonces::get(sender), 1);
+
+            // the signature is only good for the nonce it was made for
+            assert_eq!(
+                exec::<Test>(
+                    request,
+                    ChainAccountSignature::Gate(pair.public().0, signature),
+                    1
+                ),
+                Err(Reason::SignatureAccountMismatch)
+            );
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn exec_trx_request_signed_as_typed_data() {
+        new_test_ext().execute_with(|| {
+            let req_str = "(Transfer 5 Cash Eth:0x0101010101010101010101010101010101010101)";
+            let request: Vec<u8> = req_str.into();
+            let trx_request = trx_request::parse_request(req_str).unwrap();
+            let genesis_hash = eip712::genesis_hash::<Test>().unwrap();
+            let eth_address = <Ethereum as Chain>::signer_address().unwrap();
+            let eth_key_id =
+                runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+            let sign = |nonce, genesis_hash| {
+                runtime_interfaces::keyring_interface::sign_one(
+                    eip712::typed_data(&trx_request, nonce, genesis_hash),
+                    eth_key_id.clone(),
+                )
+                .unwrap()
+            };
+
+            assert_ok!(exec::<Test>(
+                request.clone(),
+                ChainAccountSignature::Eth(eth_address, sign(0, genesis_hash)),
+                0
+            ));
+            assert_eq!(Nonces::get(ChainAccount::Eth(eth_address)), 1);
+
+            // typed data signed for another chain is not accepted
+            assert_eq!(
+                exec::<Test>(
+                    request,
+                    ChainAccountSignature::Eth(eth_address, sign(1, [69; 32])),
+                    1
+                ),
+                Err(Reason::SignatureAccountMismatch)
+            );
+        });
+    }
+
     #[test]
     #[serial]
     fn exec_trx_request_wrong_nonce() {
@@ -598,4 +809,66 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    #[serial]
+    fn exec_trx_request_batch() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+
+            let req_str = "(Batch \
+                (Extract 3 CASH Eth:0x0101010101010101010101010101010101010101) \
+                (Transfer 5 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+                    Eth:0x0101010101010101010101010101010101010101))";
+            let account = ChainAccount::Eth([20; 20]);
+            let nonce = Some(0);
+
+            assert_ok!(exec_trx_request::<Test>(req_str, account, nonce));
+            let actual = get_latest_call_result();
+            let expected = "transfer_internal: Eth([238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238]), \
+            \"ETH:0x1414141414141414141414141414141414141414\", \
+            \"ETH:0x0101010101010101010101010101010101010101\", 5";
+            assert_eq!(actual, expected);
+            assert_eq!(Nonces::get(account), 1);
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn exec_trx_request_batch_failed_operation() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+
+            let req_str = "(Batch \
+                (Extract 3 CASH Eth:0x0101010101010101010101010101010101010101) \
+                (Extract Max Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+                    Eth:0x0101010101010101010101010101010101010101))";
+            let account = ChainAccount::Eth([20; 20]);
+            let nonce = Some(0);
+
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, nonce),
+                Err(Reason::MaxForNonCashAsset)
+            );
+            assert_eq!(Nonces::get(account), 0);
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn exec_trx_request_batch_insufficient_liquidity() {
+        new_test_ext().execute_with(|| {
+            let req_str = "(Batch (Extract 3 CASH Eth:0x0101010101010101010101010101010101010101))";
+            let account = ChainAccount::Eth([20; 20]);
+            let nonce = Some(0);
+
+            CashPrincipals::insert(account, CashPrincipal::from_nominal("-1"));
+
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, nonce),
+                Err(Reason::InsufficientLiquidity)
+            );
+            assert_eq!(Nonces::get(account), 0);
+        });
+    }
 }
@@ -0,0 +1,309 @@
+use codec::alloc::string::String;
+use frame_support::storage::{IterableStorageDoubleMap, StorageMap};
+use frame_system::offchain::SubmitTransaction;
+use gateway_crypto::{public_key_bytes_to_eth_address, ETH_MESSAGE_PREAMBLE};
+use our_std::{vec::Vec, RuntimeDebug};
+use sp_runtime::offchain::{
+    storage::StorageValueRef,
+    storage_lock::{StorageLock, Time},
+};
+
+use crate::{
+    chains::{eth, ChainAccount, ChainAccountSignature, ChainAsset},
+    core::{get_asset, get_liquidation_incentive, get_max_liquidation, get_price},
+    internal::exec_trx_request::prepend_nonce,
+    log,
+    params::LIQUIDATION_POLL_INTERVAL_BLOCKS,
+    portfolio::Portfolio,
+    reason::Reason,
+    symbol::CASH,
+    types::{CashOrChainAsset, Quantity, USDQuantity},
+    AssetBalances, AssetsWithNonZeroBalance, Call, CashPrincipals, Config, GlobalCashIndex, Nonces,
+};
+
+// OCW storage constants
+const OCW_STORAGE_LOCK_LIQUIDATION_KEEPER: &[u8; 37] = b"cash::storage_lock_liquidation_keeper";
+const OCW_LATEST_LIQUIDATION_BLOCK_NUMBER: &[u8; 42] =
+    b"cash::latest_liquidation_poll_block_number";
+
+/// The most valuable liquidation available against an underwater account.
+#[derive(Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct LiquidationCandidate {
+    pub borrower: ChainAccount,
+    pub borrowed: CashOrChainAsset,
+    pub collateral: CashOrChainAsset,
+    /// The amount of the borrowed asset to repay.
+    pub amount: Quantity,
+    /// The value of the repayment, which the liquidation incentive is proportional to.
+    pub value: USDQuantity,
+}
+
+impl LiquidationCandidate {
+    /// Get the trx request which performs the liquidation.
+    pub fn to_trx_request(&self) -> Result<String, Reason> {
+        Ok(format!(
+            "(Liquidate {} {} {} {})",
+            trx_request::Decimal {
+                digits: self.amount.value,
+                decimals: self.amount.units.decimals,
+            },
+            trx_asset(self.borrowed)?,
+            trx_asset(self.collateral)?,
+            trx_account(self.borrower)?
+        ))
+    }
+}
+
+fn trx_asset(asset: CashOrChainAsset) -> Result<String, Reason> {
+    match asset {
+        CashOrChainAsset::Cash => Ok(String::from("Cash")),
+        CashOrChainAsset::ChainAsset(ChainAsset::Eth(address)) => {
+            Ok(format!("Eth:0x{}", hex::encode(address)))
+        }
+        _ => Err(Reason::NotImplemented),
+    }
+}
+
+fn trx_account(account: ChainAccount) -> Result<String, Reason> {
+    match account {
+        ChainAccount::Eth(address) => Ok(format!("Eth:0x{}", hex::encode(address))),
+        _ => Err(Reason::NotImplemented),
+    }
+}
+
+/// Get the debt of an account in an asset, as seen by the liquidation close factor check.
+fn get_debt<T: Config>(
+    borrower: ChainAccount,
+    borrowed: CashOrChainAsset,
+) -> Result<Quantity, Reason> {
+    match borrowed {
+        CashOrChainAsset::Cash => Ok(GlobalCashIndex::get()
+            .cash_quantity(CashPrincipals::get(borrower).amount_repayable()?)?),
+        CashOrChainAsset::ChainAsset(asset) => {
+            let balance = AssetBalances::get(asset, borrower);
+            let debt = if balance < 0 {
+                balance.unsigned_abs()
+            } else {
+                0
+            };
+            Ok(get_asset::<T>(asset)?.as_quantity(debt))
+        }
+    }
+}
+
+/// Find the most valuable liquidation of an account, if it is underwater.
+///
+/// The liquidator is paid in proportion to the value repaid, which is limited by the
+/// largest borrow (subject to the close factor) and by the largest collateral position
+/// (discounted by the incentive).
+pub fn find_liquidation<T: Config>(
+    borrower: ChainAccount,
+) -> Result<Option<LiquidationCandidate>, Reason> {
+    let portfolio = Portfolio::from_storage::<T>(borrower)?;
+    if portfolio.get_liquidity::<T>()?.value >= 0 {
+        return Ok(None);
+    }
+
+    let mut positions = vec![(CashOrChainAsset::Cash, portfolio.cash)];
+    for (info, balance) in portfolio.positions {
+        positions.push((CashOrChainAsset::ChainAsset(info.asset), balance));
+    }
+
+    let mut borrowed = None;
+    let mut collateral = None;
+    for (asset, balance) in positions {
+        let price = get_price::<T>(balance.units)?;
+        let value = Quantity::new(balance.value.unsigned_abs(), balance.units).mul_price(price)?;
+        if balance.value < 0 {
+            match borrowed {
+                Some((_, _, max)) if value <= max => (),
+                _ => borrowed = Some((asset, price, value)),
+            }
+        } else if balance.value > 0 {
+            match collateral {
+                Some((_, max)) if value <= max => (),
+                _ => collateral = Some((asset, value)),
+            }
+        }
+    }
+
+    match (borrowed, collateral) {
+        (Some((borrowed, price, borrowed_value)), Some((collateral, collateral_value))) => {
+            let seizable_value = collateral_value.div_factor(get_liquidation_incentive::<T>()?)?;
+            let units = match borrowed {
+                CashOrChainAsset::Cash => CASH,
+                CashOrChainAsset::ChainAsset(asset) => get_asset::<T>(asset)?.units(),
+            };
+            let amount = our_std::cmp::min(
+                our_std::cmp::min(borrowed_value, seizable_value).div_price(price, units)?,
+                get_max_liquidation::<T>(get_debt::<T>(borrower, borrowed)?)?,
+            );
+            if amount.value == 0 {
+                return Ok(None);
+            }
+            Ok(Some(LiquidationCandidate {
+                borrower,
+                borrowed,
+                collateral,
+                amount,
+                value: amount.mul_price(price)?,
+            }))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Sign and submit a liquidation trx request from the liquidator account.
+fn submit_liquidation<T: Config>(
+    key_id: Vec<u8>,
+    liquidator: eth::Address,
+    candidate: LiquidationCandidate,
+) -> Result<(), Reason> {
+    let request: Vec<u8> = candidate.to_trx_request()?.into_bytes();
+    let nonce = Nonces::get(ChainAccount::Eth(liquidator));
+
+    // sign the request as a user would, using the eth personal message convention
+    let message = prepend_nonce(&request, nonce);
+    let mut eth_message = ETH_MESSAGE_PREAMBLE.to_vec();
+    eth_message.extend_from_slice(format!("{}", message.len()).as_bytes());
+    eth_message.extend_from_slice(&message[..]);
+    let signature = runtime_interfaces::keyring_interface::sign_one(eth_message, key_id)?;
+
+    log!("Submitting liquidation {:?}", candidate);
+    let call = Call::exec_trx_request(
+        request,
+        ChainAccountSignature::Eth(liquidator, signature),
+        nonce,
+    );
+    SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+        .map_err(|()| Reason::FailedToSubmitExtrinsic)
+}
+
+/// Scan accounts with positions and liquidate the most valuable underwater one, if the liquidation keeper is enabled.
+pub fn process_liquidations<T: Config>(block_number: T::BlockNumber) -> Result<(), Reason> {
+    // the keeper only runs if a liquidator account is configured
+    let key_id = match runtime_interfaces::validator_config_interface::get_liquidator_account() {
+        Some(key_id) => key_id,
+        None => return Ok(()),
+    };
+
+    let mut lock = StorageLock::<Time>::new(OCW_STORAGE_LOCK_LIQUIDATION_KEEPER);
+    if lock.try_lock().is_err() {
+        // working in another thread, no big deal
+        return Ok(());
+    }
+
+    // check to see if it is time to poll or not
+    let latest_poll_block_number_storage =
+        StorageValueRef::persistent(OCW_LATEST_LIQUIDATION_BLOCK_NUMBER);
+    if let Some(Some(latest_poll_block_number)) =
+        latest_poll_block_number_storage.get::<T::BlockNumber>()
+    {
+        let poll_interval_blocks =
+            <T as frame_system::Config>::BlockNumber::from(LIQUIDATION_POLL_INTERVAL_BLOCKS);
+        if block_number - latest_poll_block_number < poll_interval_blocks {
+            return Ok(());
+        }
+    }
+
+    let public_key = runtime_interfaces::keyring_interface::get_public_key(key_id.clone())?;
+    let liquidator = public_key_bytes_to_eth_address(&public_key);
+
+    // entries are grouped by account, so each account's entries are adjacent
+    let mut borrowers: Vec<ChainAccount> = AssetsWithNonZeroBalance::iter()
+        .map(|(account, _, _)| account)
+        .filter(|account| *account != ChainAccount::Eth(liquidator))
+        .collect();
+    borrowers.dedup();
+
+    let mut best: Option<LiquidationCandidate> = None;
+    for borrower in borrowers {
+        match find_liquidation::<T>(borrower) {
+            Ok(Some(candidate)) => match best {
+                Some(max) if candidate.value <= max.value => (),
+                _ => best = Some(candidate),
+            },
+            Ok(None) => (),
+            Err(err) => log!("Unable to check liquidity of {:?}: {:?}", borrower, err),
+        }
+    }
+
+    latest_poll_block_number_storage.set(&block_number);
+
+    // only one at a time, since each request must use the next nonce
+    match best {
+        Some(candidate) => submit_liquidation::<T>(key_id, liquidator, candidate),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        symbol::{Units, USD},
+        tests::*,
+        types::{AssetInfo, Balance, LiquidityFactor},
+        CloseFactor, SupportedAssets, TotalBorrowAssets, TotalSupplyAssets,
+    };
+    use pallet_oracle::types::Price;
+
+    const USDC: Units = Units::from_ticker_str("USDC", 6);
+
+    #[test]
+    fn test_find_liquidation() {
+        let borrower = ChainAccount::Eth([1; 20]);
+        let eth = ChainAsset::Eth([238; 20]);
+        let usdc = ChainAsset::Eth([1; 20]);
+        let eth_info = AssetInfo {
+            collateral_factor: LiquidityFactor::from_nominal("0.8"),
+            borrow_factor: LiquidityFactor::from_nominal("0.8"),
+            ..AssetInfo::minimal(eth, ETH)
+        };
+        let usdc_info = AssetInfo {
+            collateral_factor: LiquidityFactor::from_nominal("0.8"),
+            borrow_factor: LiquidityFactor::from_nominal("0.8"),
+            ..AssetInfo::minimal(usdc, USDC)
+        };
+
+        new_test_ext().execute_with(|| {
+            SupportedAssets::insert(&eth, eth_info);
+            SupportedAssets::insert(&usdc, usdc_info);
+            pallet_oracle::Prices::insert(ETH.ticker, Price::from_nominal(ETH.ticker, "2000").value);
+            pallet_oracle::Prices::insert(USDC.ticker, Price::from_nominal(USDC.ticker, "1").value);
+
+            // 1 ETH of collateral against 1800 USDC borrowed
+            let eth_balance = Balance::from_nominal("1", ETH).value;
+            let usdc_balance = Balance::from_nominal("-1800", USDC).value;
+            AssetBalances::insert(&eth, &borrower, eth_balance);
+            AssetBalances::insert(&usdc, &borrower, usdc_balance);
+            AssetsWithNonZeroBalance::insert(&borrower, &eth, ());
+            AssetsWithNonZeroBalance::insert(&borrower, &usdc, ());
+            TotalSupplyAssets::insert(&eth, eth_balance as u128);
+            TotalBorrowAssets::insert(&usdc, usdc_balance.unsigned_abs());
+
+            let candidate = find_liquidation::<Test>(borrower).unwrap().unwrap();
+
+            // the collateral covers the debt with the incentive, but the close factor limits it
+            assert_eq!(candidate.borrowed, CashOrChainAsset::ChainAsset(usdc));
+            assert_eq!(candidate.collateral, CashOrChainAsset::ChainAsset(eth));
+            assert_eq!(candidate.value, Quantity::from_nominal("900", USD));
+            assert_eq!(candidate.amount, Quantity::from_nominal("900", USDC));
+            assert_eq!(
+                candidate.to_trx_request(),
+                Ok(String::from("(Liquidate 900.000000 Eth:0x0101010101010101010101010101010101010101 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)"))
+            );
+            assert!(trx_request::parse_request(&candidate.to_trx_request().unwrap()).is_ok());
+
+            // with a full close factor, all of the debt may be repaid
+            CloseFactor::put(10_000);
+            let candidate = find_liquidation::<Test>(borrower).unwrap().unwrap();
+            assert_eq!(candidate.value, Quantity::from_nominal("1800", USD));
+            assert_eq!(candidate.amount, Quantity::from_nominal("1800", USDC));
+
+            // a healthy account has nothing to liquidate
+            AssetBalances::insert(&usdc, &borrower, Balance::from_nominal("-1000", USDC).value);
+            assert_eq!(find_liquidation::<Test>(borrower), Ok(None));
+        });
+    }
+}
@@ -0,0 +1,308 @@
+use codec::Decode;
+use frame_support::{
+    storage::{IterableStorageMap, StorageValue},
+    traits::Get,
+    weights::Weight,
+};
+use pallet_oracle::ticker::Ticker;
+
+use crate::{
+    chains::{Chain, ChainAsset, Ethereum},
+    log,
+    rates::InterestRateModel,
+    symbol::Symbol,
+    types::{AssetAmount, AssetInfo, Decimals, LiquidityFactor, MinerShares, ValidatorKeys},
+    Config, NextValidators, SessionInterface, StorageVersion, SubstrateId, SupportedAssets,
+    Validators,
+};
+
+/// The current version of the storage layout, set at genesis and after migrating.
+pub const STORAGE_VERSION: u32 = 3;
+
+/// The layout of `AssetInfo` before borrow caps.
+#[derive(Decode)]
+struct AssetInfoV0 {
+    asset: ChainAsset,
+    decimals: Decimals,
+    liquidity_factor: LiquidityFactor,
+    rate_model: InterestRateModel,
+    miner_shares: MinerShares,
+    supply_cap: AssetAmount,
+    symbol: Symbol,
+    ticker: Ticker,
+}
+
+impl From<AssetInfoV0> for AssetInfo {
+    /// Assets get a borrow cap of zero, which doesn't limit borrowing them,
+    /// and equal collateral and borrow factors.
+    fn from(old: AssetInfoV0) -> Self {
+        AssetInfo {
+            asset: old.asset,
+            decimals: old.decimals,
+            collateral_factor: old.liquidity_factor,
+            borrow_factor: old.liquidity_factor,
+            rate_model: old.rate_model,
+            miner_shares: old.miner_shares,
+            supply_cap: old.supply_cap,
+            borrow_cap: 0,
+            symbol: old.symbol,
+            ticker: old.ticker,
+        }
+    }
+}
+
+/// The layout of `AssetInfo` with borrow caps, but a single liquidity factor.
+#[derive(Decode)]
+struct AssetInfoV1 {
+    asset: ChainAsset,
+    decimals: Decimals,
+    liquidity_factor: LiquidityFactor,
+    rate_model: InterestRateModel,
+    miner_shares: MinerShares,
+    supply_cap: AssetAmount,
+    borrow_cap: AssetAmount,
+    symbol: Symbol,
+    ticker: Ticker,
+}
+
+impl From<AssetInfoV1> for AssetInfo {
+    /// Assets get equal collateral and borrow factors.
+    fn from(old: AssetInfoV1) -> Self {
+        AssetInfo {
+            asset: old.asset,
+            decimals: old.decimals,
+            collateral_factor: old.liquidity_factor,
+            borrow_factor: old.liquidity_factor,
+            rate_model: old.rate_model,
+            miner_shares: old.miner_shares,
+            supply_cap: old.supply_cap,
+            borrow_cap: old.borrow_cap,
+            symbol: old.symbol,
+            ticker: old.ticker,
+        }
+    }
+}
+
+/// The layout of `ValidatorKeys` before explicit Polkadot and Solana keys.
+#[derive(Decode)]
+struct ValidatorKeysV1 {
+    substrate_id: SubstrateId,
+    eth_address: <Ethereum as Chain>::Address,
+}
+
+impl ValidatorKeysV1 {
+    /// Validators keep signing for Polkadot with the sr25519 key of their substrate id,
+    /// and sign for Solana with the ed25519 grandpa key of their session, which signs Solana notices.
+    ///
+    /// A validator without a grandpa key gets an empty Solana key, and can't sign Solana notices
+    /// until it's set through change validators.
+    fn migrate<T: Config>(self) -> ValidatorKeys {
+        let substrate_id: [u8; 32] = self.substrate_id.clone().into();
+        let sol_id = <T>::SessionInterface::next_grandpa_key(self.substrate_id.clone());
+        ValidatorKeys {
+            substrate_id: self.substrate_id,
+            eth_address: self.eth_address,
+            dot_id: substrate_id,
+            sol_id: sol_id.unwrap_or([0; 32]),
+        }
+    }
+}
+
+/// Run any storage migrations needed to reach the current storage version.
+pub fn on_runtime_upgrade<T: Config>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+    match StorageVersion::get() {
+        0 => weight += migrate_asset_infos::<T, AssetInfoV0>(),
+        1 => weight += migrate_asset_infos::<T, AssetInfoV1>(),
+        _ => (),
+    }
+    if StorageVersion::get() < 3 {
+        weight += migrate_validator_keys::<T>();
+    }
+    weight
+}
+
+/// Migrate each asset from the given older layout of `AssetInfo` to the current one.
+fn migrate_asset_infos<T: Config, Old: Decode + Into<AssetInfo>>() -> Weight {
+    let mut count: Weight = 0;
+    SupportedAssets::translate::<Old, _>(|_asset, old| {
+        count += 1;
+        Some(old.into())
+    });
+    StorageVersion::put(2);
+
+    log!("Migrated asset info of {} assets", count);
+    T::DbWeight::get().reads_writes(count, count + 1)
+}
+
+/// Add explicit Polkadot and Solana keys to the current and next validators.
+fn migrate_validator_keys<T: Config>() -> Weight {
+    let mut count: Weight = 0;
+    Validators::translate::<ValidatorKeysV1, _>(|_id, old| {
+        count += 1;
+        Some(old.migrate::<T>())
+    });
+    NextValidators::translate::<ValidatorKeysV1, _>(|_id, old| {
+        count += 1;
+        Some(old.migrate::<T>())
+    });
+    StorageVersion::put(3);
+
+    log!("Migrated keys of {} validators", count);
+    T::DbWeight::get().reads_writes(count, count + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::*, types::Factor};
+    use codec::Encode;
+    use frame_support::storage::StorageMap;
+
+    #[derive(Encode)]
+    struct EncodeAssetInfoV0 {
+        asset: ChainAsset,
+        decimals: Decimals,
+        liquidity_factor: LiquidityFactor,
+        rate_model: InterestRateModel,
+        miner_shares: MinerShares,
+        supply_cap: AssetAmount,
+        symbol: Symbol,
+        ticker: Ticker,
+    }
+
+    #[derive(Encode)]
+    struct EncodeAssetInfoV1 {
+        asset: ChainAsset,
+        decimals: Decimals,
+        liquidity_factor: LiquidityFactor,
+        rate_model: InterestRateModel,
+        miner_shares: MinerShares,
+        supply_cap: AssetAmount,
+        borrow_cap: AssetAmount,
+        symbol: Symbol,
+        ticker: Ticker,
+    }
+
+    #[derive(Encode)]
+    struct EncodeValidatorKeysV1 {
+        substrate_id: SubstrateId,
+        eth_address: <Ethereum as Chain>::Address,
+    }
+
+    #[test]
+    fn test_migrate_asset_infos_from_v0() {
+        let asset = ChainAsset::Eth([238; 20]);
+        let old = EncodeAssetInfoV0 {
+            asset,
+            decimals: ETH.decimals,
+            liquidity_factor: Factor::from_nominal("0.8"),
+            rate_model: InterestRateModel::default(),
+            miner_shares: MinerShares::default(),
+            supply_cap: 1000,
+            symbol: Symbol(ETH.ticker.0),
+            ticker: ETH.ticker,
+        };
+
+        new_test_ext().execute_with(|| {
+            StorageVersion::put(0);
+            frame_support::storage::unhashed::put_raw(
+                &SupportedAssets::hashed_key_for(asset),
+                &old.encode(),
+            );
+
+            on_runtime_upgrade::<Test>();
+
+            assert_eq!(StorageVersion::get(), STORAGE_VERSION);
+            assert_eq!(
+                SupportedAssets::get(asset),
+                Some(AssetInfo {
+                    collateral_factor: Factor::from_nominal("0.8"),
+                    borrow_factor: Factor::from_nominal("0.8"),
+                    supply_cap: 1000,
+                    borrow_cap: 0,
+                    ..AssetInfo::minimal(asset, ETH)
+                })
+            );
+
+            // running the upgrade again leaves migrated assets alone
+            on_runtime_upgrade::<Test>();
+            assert_eq!(
+                SupportedAssets::get(asset).map(|info| info.borrow_factor),
+                Some(Factor::from_nominal("0.8"))
+            );
+        });
+    }
+
+    #[test]
+    fn test_migrate_asset_infos_from_v1() {
+        let asset = ChainAsset::Eth([238; 20]);
+        let old = EncodeAssetInfoV1 {
+            asset,
+            decimals: ETH.decimals,
+            liquidity_factor: Factor::from_nominal("0.8"),
+            rate_model: InterestRateModel::default(),
+            miner_shares: MinerShares::default(),
+            supply_cap: 1000,
+            borrow_cap: 500,
+            symbol: Symbol(ETH.ticker.0),
+            ticker: ETH.ticker,
+        };
+
+        new_test_ext().execute_with(|| {
+            StorageVersion::put(1);
+            frame_support::storage::unhashed::put_raw(
+                &SupportedAssets::hashed_key_for(asset),
+                &old.encode(),
+            );
+
+            on_runtime_upgrade::<Test>();
+
+            assert_eq!(StorageVersion::get(), STORAGE_VERSION);
+            assert_eq!(
+                SupportedAssets::get(asset),
+                Some(AssetInfo {
+                    collateral_factor: Factor::from_nominal("0.8"),
+                    borrow_factor: Factor::from_nominal("0.8"),
+                    supply_cap: 1000,
+                    borrow_cap: 500,
+                    ..AssetInfo::minimal(asset, ETH)
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn test_migrate_validator_keys() {
+        let substrate_id = SubstrateId::new([3; 32]);
+        let old = EncodeValidatorKeysV1 {
+            substrate_id: substrate_id.clone(),
+            eth_address: [4; 20],
+        };
+
+        new_test_ext().execute_with(|| {
+            StorageVersion::put(2);
+            frame_support::storage::unhashed::put_raw(
+                &Validators::hashed_key_for(&substrate_id),
+                &old.encode(),
+            );
+            frame_support::storage::unhashed::put_raw(
+                &NextValidators::hashed_key_for(&substrate_id),
+                &old.encode(),
+            );
+
+            on_runtime_upgrade::<Test>();
+
+            let expected = ValidatorKeys {
+                substrate_id: substrate_id.clone(),
+                eth_address: [4; 20],
+                dot_id: [3; 32],
+                // the mock session has no grandpa keys
+                sol_id: [0; 32],
+            };
+            assert_eq!(StorageVersion::get(), STORAGE_VERSION);
+            assert_eq!(Validators::get(&substrate_id), Some(expected.clone()));
+            assert_eq!(NextValidators::get(&substrate_id), Some(expected));
+        });
+    }
+}
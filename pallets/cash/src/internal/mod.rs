@@ -2,10 +2,13 @@ pub mod assets;
 pub mod change_validators;
 pub mod events;
 pub mod exec_trx_request;
+pub mod liquidator;
 pub mod lock;
+pub mod migrations;
 pub mod miner;
 pub mod next_code;
 pub mod notices;
+pub mod quorum;
 pub mod set_yield_next;
 pub mod supply_cap;
 pub mod validate_trx;
@@ -4,7 +4,10 @@ use frame_support::storage::{
 use frame_system::offchain::SubmitTransaction;
 
 use crate::{
-    chains::{ChainAccount, ChainAsset, ChainHash, ChainId, ChainSignature, ChainSignatureList},
+    chains::{
+        ChainAccount, ChainAsset, ChainHash, ChainId, ChainSignature, ChainSignatureList,
+        EVM_CHAINS,
+    },
     log,
     notices::{
         has_signer, CashExtractionNotice, ChangeAuthorityNotice, EncodeNotice, ExtractionNotice,
@@ -16,34 +19,69 @@ use crate::{
         ValidatorKeys, APR,
     },
     AccountNotices, Call, Config, Event, LatestNotice, Module, NoticeHashes, NoticeHolds,
-    NoticeStates, Notices, Validators,
+    NoticeStates, Notices, SupportedAssets, Validators,
 };
 
 pub fn dispatch_extraction_notice<T: Config>(
     asset: ChainAsset,
     recipient: ChainAccount,
     amount: AssetQuantity,
-) {
+) -> Result<(), Reason> {
     dispatch_notice::<T>(
         recipient.chain_id(),
         Some(recipient),
         false,
         &|notice_id, parent_hash| {
-            Notice::ExtractionNotice(match (asset, recipient, parent_hash) {
-                (
-                    ChainAsset::Eth(eth_asset),
-                    ChainAccount::Eth(eth_account),
-                    ChainHash::Eth(eth_parent_hash),
-                ) => ExtractionNotice::Eth {
-                    id: notice_id,
-                    parent: eth_parent_hash,
-                    asset: eth_asset,
-                    account: eth_account,
-                    amount: amount.value,
-                },
+            Ok(Notice::ExtractionNotice(
+                match (asset, recipient, parent_hash) {
+                    (
+                        ChainAsset::Eth(eth_asset),
+                        ChainAccount::Eth(eth_account),
+                        ChainHash::Eth(eth_parent_hash),
+                    ) => ExtractionNotice::Eth {
+                        id: notice_id,
+                        parent: eth_parent_hash,
+                        asset: eth_asset,
+                        account: eth_account,
+                        amount: amount.value,
+                    },
+                    (
+                        ChainAsset::Dot(dot_asset),
+                        ChainAccount::Dot(dot_account),
+                        ChainHash::Dot(dot_parent_hash),
+                    ) => ExtractionNotice::Dot {
+                        id: notice_id,
+                        parent: dot_parent_hash,
+                        asset: dot_asset,
+                        account: dot_account,
+                        amount: amount.value,
+                    },
+                    (
+                        ChainAsset::Sol(sol_asset),
+                        ChainAccount::Sol(sol_account),
+                        ChainHash::Sol(sol_parent_hash),
+                    ) => ExtractionNotice::Sol {
+                        id: notice_id,
+                        parent: sol_parent_hash,
+                        asset: sol_asset,
+                        account: sol_account,
+                        amount: amount.value,
+                    },
+                    (
+                        ChainAsset::Matic(matic_asset),
+                        ChainAccount::Matic(matic_account),
+                        ChainHash::Matic(matic_parent_hash),
+                    ) => ExtractionNotice::Matic {
+                        id: notice_id,
+                        parent: matic_parent_hash,
+                        asset: matic_asset,
+                        account: matic_account,
+                        amount: amount.value,
+                    },
 
-                _ => panic!("XXX not implemented"), // generate these w/ macros?
-            })
+                    _ => return Err(Reason::InvalidChain),
+                },
+            ))
         },
     )
 }
@@ -51,96 +89,208 @@ pub fn dispatch_extraction_notice<T: Config>(
 pub fn dispatch_cash_extraction_notice<T: Config>(
     recipient: ChainAccount,
     principal: CashPrincipalAmount,
-) {
+) -> Result<(), Reason> {
     dispatch_notice::<T>(
         recipient.chain_id(),
         Some(recipient),
         false,
         &|notice_id, parent_hash| {
-            Notice::CashExtractionNotice(match (recipient, parent_hash) {
-                (ChainAccount::Eth(eth_account), ChainHash::Eth(eth_parent_hash)) => {
-                    CashExtractionNotice::Eth {
-                        id: notice_id,
-                        parent: eth_parent_hash,
-                        account: eth_account,
-                        principal: principal.0,
+            Ok(Notice::CashExtractionNotice(
+                match (recipient, parent_hash) {
+                    (ChainAccount::Eth(eth_account), ChainHash::Eth(eth_parent_hash)) => {
+                        CashExtractionNotice::Eth {
+                            id: notice_id,
+                            parent: eth_parent_hash,
+                            account: eth_account,
+                            principal: principal.0,
+                        }
+                    }
+                    (ChainAccount::Dot(dot_account), ChainHash::Dot(dot_parent_hash)) => {
+                        CashExtractionNotice::Dot {
+                            id: notice_id,
+                            parent: dot_parent_hash,
+                            account: dot_account,
+                            principal: principal.0,
+                        }
+                    }
+                    (ChainAccount::Sol(sol_account), ChainHash::Sol(sol_parent_hash)) => {
+                        CashExtractionNotice::Sol {
+                            id: notice_id,
+                            parent: sol_parent_hash,
+                            account: sol_account,
+                            principal: principal.0,
+                        }
+                    }
+                    (ChainAccount::Matic(matic_account), ChainHash::Matic(matic_parent_hash)) => {
+                        CashExtractionNotice::Matic {
+                            id: notice_id,
+                            parent: matic_parent_hash,
+                            account: matic_account,
+                            principal: principal.0,
+                        }
                     }
-                }
 
-                _ => panic!("XXX not implemented"), // generate these w/ macros?
-            })
+                    _ => return Err(Reason::InvalidChain),
+                },
+            ))
         },
     )
 }
 
-pub fn dispatch_supply_cap_notice<T: Config>(chain_asset: ChainAsset, cap: AssetAmount) {
+pub fn dispatch_supply_cap_notice<T: Config>(
+    chain_asset: ChainAsset,
+    cap: AssetAmount,
+) -> Result<(), Reason> {
     dispatch_notice::<T>(
         chain_asset.chain_id(),
         None,
         true,
         &|notice_id, parent_hash| {
-            Notice::SetSupplyCapNotice(match (chain_asset, parent_hash) {
-                (ChainAsset::Eth(eth_asset), ChainHash::Eth(eth_parent_hash)) => {
-                    SetSupplyCapNotice::Eth {
-                        id: notice_id,
-                        parent: eth_parent_hash,
-                        asset: eth_asset,
-                        cap,
+            Ok(Notice::SetSupplyCapNotice(
+                match (chain_asset, parent_hash) {
+                    (ChainAsset::Eth(eth_asset), ChainHash::Eth(eth_parent_hash)) => {
+                        SetSupplyCapNotice::Eth {
+                            id: notice_id,
+                            parent: eth_parent_hash,
+                            asset: eth_asset,
+                            cap,
+                        }
+                    }
+                    (ChainAsset::Dot(dot_asset), ChainHash::Dot(dot_parent_hash)) => {
+                        SetSupplyCapNotice::Dot {
+                            id: notice_id,
+                            parent: dot_parent_hash,
+                            asset: dot_asset,
+                            cap,
+                        }
+                    }
+                    (ChainAsset::Sol(sol_asset), ChainHash::Sol(sol_parent_hash)) => {
+                        SetSupplyCapNotice::Sol {
+                            id: notice_id,
+                            parent: sol_parent_hash,
+                            asset: sol_asset,
+                            cap,
+                        }
+                    }
+                    (ChainAsset::Matic(matic_asset), ChainHash::Matic(matic_parent_hash)) => {
+                        SetSupplyCapNotice::Matic {
+                            id: notice_id,
+                            parent: matic_parent_hash,
+                            asset: matic_asset,
+                            cap,
+                        }
                     }
-                }
 
-                _ => panic!("XXX not implemented"), // generate these w/ macros?
-            })
+                    _ => return Err(Reason::InvalidChain),
+                },
+            ))
         },
     )
 }
 
+/// The chains whose starports must hear about chain-wide changes, such as the cash yield or authorities.
+/// Ethereum always runs a starport, other chains join once they have a supported asset.
+pub fn starport_chains() -> Vec<ChainId> {
+    EVM_CHAINS
+        .iter()
+        .map(|evm_chain| evm_chain.chain_id)
+        .chain(vec![ChainId::Dot, ChainId::Sol])
+        .filter(|&chain_id| {
+            chain_id == ChainId::Eth
+                || SupportedAssets::iter_values().any(|info| info.asset.chain_id() == chain_id)
+        })
+        .collect()
+}
+
 pub fn dispatch_future_yield_notice<T: Config>(
     next_yield: APR,
     next_yield_index: CashIndex,
     next_yield_start: Timestamp,
-) {
-    // XXX for each chain id
-    let chain_id = ChainId::Eth;
-    dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
-        Notice::FutureYieldNotice(match parent_hash {
-            ChainHash::Eth(eth_parent_hash) => FutureYieldNotice::Eth {
-                id: notice_id,
-                parent: eth_parent_hash,
-                next_cash_yield: next_yield.0,
-                next_cash_index: next_yield_index.0,
-                next_cash_yield_start: next_yield_start,
-            },
+) -> Result<(), Reason> {
+    for chain_id in starport_chains() {
+        dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
+            Ok(Notice::FutureYieldNotice(match parent_hash {
+                ChainHash::Eth(eth_parent_hash) => FutureYieldNotice::Eth {
+                    id: notice_id,
+                    parent: eth_parent_hash,
+                    next_cash_yield: next_yield.0,
+                    next_cash_index: next_yield_index.0,
+                    next_cash_yield_start: next_yield_start,
+                },
+                ChainHash::Matic(matic_parent_hash) => FutureYieldNotice::Matic {
+                    id: notice_id,
+                    parent: matic_parent_hash,
+                    next_cash_yield: next_yield.0,
+                    next_cash_index: next_yield_index.0,
+                    next_cash_yield_start: next_yield_start,
+                },
+                ChainHash::Dot(dot_parent_hash) => FutureYieldNotice::Dot {
+                    id: notice_id,
+                    parent: dot_parent_hash,
+                    next_cash_yield: next_yield.0,
+                    next_cash_index: next_yield_index.0,
+                    next_cash_yield_start: next_yield_start,
+                },
+                ChainHash::Sol(sol_parent_hash) => FutureYieldNotice::Sol {
+                    id: notice_id,
+                    parent: sol_parent_hash,
+                    next_cash_yield: next_yield.0,
+                    next_cash_index: next_yield_index.0,
+                    next_cash_yield_start: next_yield_start,
+                },
 
-            _ => panic!("XXX not implemented"), // generate these w/ macros?
-        })
-    })
+                _ => return Err(Reason::InvalidChain),
+            }))
+        })?;
+    }
+    Ok(())
 }
 
-pub fn dispatch_change_authority_notice<T: Config>(validators: Vec<ValidatorKeys>) {
-    // XXX for each chain id
-    let chain_id = ChainId::Eth;
-    dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
-        Notice::ChangeAuthorityNotice(match parent_hash {
-            ChainHash::Eth(eth_parent_hash) => ChangeAuthorityNotice::Eth {
-                id: notice_id,
-                parent: eth_parent_hash,
-                new_authorities: validators.iter().map(|x| x.eth_address).collect::<Vec<_>>(),
-            },
+pub fn dispatch_change_authority_notice<T: Config>(
+    validators: Vec<ValidatorKeys>,
+) -> Result<(), Reason> {
+    let new_authorities = validators.iter().map(|x| x.eth_address).collect::<Vec<_>>();
+    let new_dot_authorities = validators.iter().map(|x| x.dot_id).collect::<Vec<_>>();
+    let new_sol_authorities = validators.iter().map(|x| x.sol_id).collect::<Vec<_>>();
+    for chain_id in starport_chains() {
+        dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
+            Ok(Notice::ChangeAuthorityNotice(match parent_hash {
+                ChainHash::Eth(eth_parent_hash) => ChangeAuthorityNotice::Eth {
+                    id: notice_id,
+                    parent: eth_parent_hash,
+                    new_authorities: new_authorities.clone(),
+                },
+                ChainHash::Matic(matic_parent_hash) => ChangeAuthorityNotice::Matic {
+                    id: notice_id,
+                    parent: matic_parent_hash,
+                    new_authorities: new_authorities.clone(),
+                },
+                ChainHash::Dot(dot_parent_hash) => ChangeAuthorityNotice::Dot {
+                    id: notice_id,
+                    parent: dot_parent_hash,
+                    new_authorities: new_dot_authorities.clone(),
+                },
+                ChainHash::Sol(sol_parent_hash) => ChangeAuthorityNotice::Sol {
+                    id: notice_id,
+                    parent: sol_parent_hash,
+                    new_authorities: new_sol_authorities.clone(),
+                },
 
-            _ => panic!("XXX not implemented"), // generate these w/ macros?
-        })
-    })
+                _ => return Err(Reason::InvalidChain),
+            }))
+        })?;
+    }
+    Ok(())
 }
 
 /// Add a notice to the queue and all the secondary indices.
-/// Called from effects phase and thus may not fail.
+/// Fails without any effects if the notice can't be built for the chain.
 fn dispatch_notice<T: Config>(
     chain_id: ChainId,
     maybe_recipient: Option<ChainAccount>,
     should_increment_era: bool,
-    notice_fn: &dyn Fn(NoticeId, ChainHash) -> Notice,
-) {
+    notice_fn: &dyn Fn(NoticeId, ChainHash) -> Result<Notice, Reason>,
+) -> Result<(), Reason> {
     let (latest_notice_id, parent_hash) =
         LatestNotice::get(chain_id).unwrap_or((NoticeId(0, 0), chain_id.zero_hash()));
 
@@ -151,7 +301,7 @@ fn dispatch_notice<T: Config>(
     };
 
     // Add to notices, notice states, track the latest notice and index by account
-    let notice = notice_fn(notice_id, parent_hash);
+    let notice = notice_fn(notice_id, parent_hash)?;
     let notice_hash = notice.hash();
     Notices::insert(chain_id, notice_id, &notice);
     NoticeStates::insert(chain_id, notice_id, NoticeState::pending(&notice));
@@ -168,6 +318,8 @@ fn dispatch_notice<T: Config>(
     // Deposit Notice Event
     let encoded_notice = notice.encode_notice();
     Module::<T>::deposit_event(Event::Notice(notice_id, notice, encoded_notice));
+
+    Ok(())
 }
 
 pub fn handle_notice_invoked<T: Config>(
@@ -231,6 +383,23 @@ pub fn process_notices<T: Config>(_block_number: T::BlockNumber) -> (usize, usiz
     })
 }
 
+/// Determine the account which signed a notice.
+/// Signatures which can't be recovered (i.e. sr25519, ed25519) are checked against each validator's key.
+pub fn notice_signer(signature: &ChainSignature, message: &[u8]) -> Result<ChainAccount, Reason> {
+    match signature {
+        ChainSignature::Dot(_) | ChainSignature::Sol(_) => Validators::iter()
+            .filter_map(|(_, v)| v.chain_account(signature.chain_id()))
+            .find(|account| signature.verify(message, *account).is_ok())
+            .ok_or(Reason::UnknownValidator),
+        _ => signature.recover(message),
+    }
+}
+
+/// Check whether the account is a current validator's signing account on its chain.
+pub fn is_validator(signer: ChainAccount) -> bool {
+    Validators::iter().any(|(_, v)| v.chain_account(signer.chain_id()) == Some(signer))
+}
+
 pub fn publish_signature(
     chain_id: ChainId,
     notice_id: NoticeId,
@@ -245,7 +414,7 @@ pub fn publish_signature(
         NoticeState::Pending { signature_pairs } => {
             let notice = Notices::get(chain_id, notice_id)
                 .ok_or(Reason::NoticeMissing(chain_id, notice_id))?;
-            let signer: ChainAccount = signature.recover(&notice.encode_notice())?;
+            let signer: ChainAccount = notice_signer(&signature, &notice.encode_notice())?;
             let has_signer_v = has_signer(&signature_pairs, signer);
 
             if has_signer_v {
@@ -263,11 +432,38 @@ pub fn publish_signature(
                     eth_signature_list_mut.push((eth_account, eth_sig));
                     Ok(ChainSignatureList::Eth(eth_signature_list_mut))
                 }
+                (
+                    ChainSignatureList::Dot(dot_signature_list),
+                    ChainAccount::Dot(dot_account),
+                    ChainSignature::Dot(dot_sig),
+                ) => {
+                    let mut dot_signature_list_mut = dot_signature_list.clone();
+                    dot_signature_list_mut.push((dot_account, dot_sig));
+                    Ok(ChainSignatureList::Dot(dot_signature_list_mut))
+                }
+                (
+                    ChainSignatureList::Sol(sol_signature_list),
+                    ChainAccount::Sol(sol_account),
+                    ChainSignature::Sol(sol_sig),
+                ) => {
+                    let mut sol_signature_list_mut = sol_signature_list.clone();
+                    sol_signature_list_mut.push((sol_account, sol_sig));
+                    Ok(ChainSignatureList::Sol(sol_signature_list_mut))
+                }
+                (
+                    ChainSignatureList::Matic(matic_signature_list),
+                    ChainAccount::Matic(matic_account),
+                    ChainSignature::Matic(matic_sig),
+                ) => {
+                    let mut matic_signature_list_mut = matic_signature_list.clone();
+                    matic_signature_list_mut.push((matic_account, matic_sig));
+                    Ok(ChainSignatureList::Matic(matic_signature_list_mut))
+                }
                 _ => Err(Reason::SignatureMismatch),
             }?;
 
             // Note: we currently iterate all potentially all validators to check validity
-            if !Validators::iter().any(|(_, v)| ChainAccount::Eth(v.eth_address) == signer) {
+            if !is_validator(signer) {
                 Err(Reason::UnknownValidator)?
             }
 
@@ -292,10 +488,10 @@ mod tests {
         chains::{Chain, ChainSignatureList, Ethereum},
         notices::{ExtractionNotice, Notice},
         tests::*,
-        types::ValidatorKeys,
+        types::{AssetInfo, ValidatorKeys},
     };
     use gateway_crypto::CryptoError;
-    use sp_core::crypto::AccountId32;
+    use sp_core::{crypto::AccountId32, ed25519, sr25519, Pair};
 
     /** `handle_notice_invoked` tests **/
 
@@ -637,6 +833,8 @@ mod tests {
                 ValidatorKeys {
                     substrate_id,
                     eth_address,
+                    dot_id: [0; 32],
+                    sol_id: [0; 32],
                 },
             );
 
@@ -721,9 +919,8 @@ mod tests {
                 ChainSignature::Eth(a) => a,
                 _ => panic!("invalid signature"),
             };
-            let signer = <Ethereum as Chain>::signer_address().unwrap();
             let notice_state = NoticeState::Pending {
-                signature_pairs: ChainSignatureList::Gate(vec![(signer, eth_signature)]),
+                signature_pairs: ChainSignatureList::Gate(vec![([0; 32], eth_signature)]),
             };
             NoticeStates::insert(chain_id, notice_id, notice_state);
             Notices::insert(chain_id, notice_id, notice);
@@ -794,4 +991,279 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn test_dispatch_dot_extraction_notice() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Dot;
+            let notice_id = NoticeId(0, 1);
+
+            assert_eq!(
+                dispatch_extraction_notice::<Test>(
+                    ChainAsset::Dot([1; 32]),
+                    ChainAccount::Dot([2; 32]),
+                    AssetQuantity {
+                        units: ETH,
+                        value: 100,
+                    },
+                ),
+                Ok(())
+            );
+
+            let expected_notice = Notice::ExtractionNotice(ExtractionNotice::Dot {
+                id: notice_id,
+                parent: [0; 32],
+                asset: [1; 32],
+                account: [2; 32],
+                amount: 100,
+            });
+            assert_eq!(
+                Notices::get(chain_id, notice_id),
+                Some(expected_notice.clone())
+            );
+            assert_eq!(
+                NoticeStates::get(chain_id, notice_id),
+                NoticeState::Pending {
+                    signature_pairs: ChainSignatureList::Dot(vec![]),
+                }
+            );
+            assert_eq!(
+                LatestNotice::get(chain_id),
+                Some((notice_id, expected_notice.hash()))
+            );
+            assert_eq!(
+                AccountNotices::get(ChainAccount::Dot([2; 32])),
+                vec![notice_id]
+            );
+        });
+    }
+
+    #[test]
+    fn test_dispatch_notice_unsupported_chain() {
+        new_test_ext().execute_with(|| {
+            let events_pre: Vec<_> = System::events().into_iter().collect();
+
+            assert_eq!(
+                dispatch_extraction_notice::<Test>(
+                    ChainAsset::Eth([1; 20]),
+                    ChainAccount::Dot([2; 32]),
+                    AssetQuantity {
+                        units: ETH,
+                        value: 100,
+                    },
+                ),
+                Err(Reason::InvalidChain)
+            );
+            assert_eq!(
+                dispatch_cash_extraction_notice::<Test>(
+                    ChainAccount::Tez([2; 21]),
+                    CashPrincipalAmount(100),
+                ),
+                Err(Reason::InvalidChain)
+            );
+            assert_eq!(
+                dispatch_supply_cap_notice::<Test>(ChainAsset::Gate([1; 32]), 100),
+                Err(Reason::InvalidChain)
+            );
+
+            // nothing is queued or indexed for a notice that could not be built
+            assert_eq!(LatestNotice::get(ChainId::Dot), None);
+            assert_eq!(LatestNotice::get(ChainId::Tez), None);
+            assert_eq!(LatestNotice::get(ChainId::Gate), None);
+            assert_eq!(
+                AccountNotices::get(ChainAccount::Dot([2; 32])),
+                Vec::<NoticeId>::new()
+            );
+            assert_eq!(System::events().len(), events_pre.len());
+        });
+    }
+
+    #[test]
+    fn test_dispatch_change_authority_notice_to_starport_chains() {
+        new_test_ext().execute_with(|| {
+            let validators = vec![ValidatorKeys {
+                substrate_id: AccountId32::new([0u8; 32]),
+                eth_address: [1; 20],
+                dot_id: [2; 32],
+                sol_id: [3; 32],
+            }];
+
+            assert_eq!(starport_chains(), vec![ChainId::Eth]);
+            assert_ok!(dispatch_change_authority_notice::<Test>(validators.clone()));
+            assert_eq!(LatestNotice::get(ChainId::Matic), None);
+
+            let matic_asset = ChainAsset::Matic([1; 20]);
+            SupportedAssets::insert(&matic_asset, AssetInfo::minimal(matic_asset, ETH));
+
+            assert_eq!(starport_chains(), vec![ChainId::Eth, ChainId::Matic]);
+            assert_ok!(dispatch_change_authority_notice::<Test>(validators.clone()));
+            assert_eq!(NoticeHolds::get(ChainId::Eth), Some(NoticeId(2, 0)));
+            assert_eq!(NoticeHolds::get(ChainId::Matic), Some(NoticeId(1, 0)));
+            assert_eq!(
+                Notices::get(ChainId::Matic, NoticeId(1, 0)),
+                Some(Notice::ChangeAuthorityNotice(
+                    ChangeAuthorityNotice::Matic {
+                        id: NoticeId(1, 0),
+                        parent: [0; 32],
+                        new_authorities: vec![[1; 20]],
+                    }
+                ))
+            );
+
+            let dot_asset = ChainAsset::Dot([2; 32]);
+            SupportedAssets::insert(&dot_asset, AssetInfo::minimal(dot_asset, ETH));
+            let sol_asset = ChainAsset::Sol([3; 32]);
+            SupportedAssets::insert(&sol_asset, AssetInfo::minimal(sol_asset, ETH));
+
+            assert_eq!(
+                starport_chains(),
+                vec![ChainId::Eth, ChainId::Matic, ChainId::Dot, ChainId::Sol]
+            );
+            assert_ok!(dispatch_change_authority_notice::<Test>(validators));
+            assert_eq!(
+                Notices::get(ChainId::Dot, NoticeId(1, 0)),
+                Some(Notice::ChangeAuthorityNotice(ChangeAuthorityNotice::Dot {
+                    id: NoticeId(1, 0),
+                    parent: [0; 32],
+                    new_authorities: vec![[2; 32]],
+                }))
+            );
+            assert_eq!(
+                Notices::get(ChainId::Sol, NoticeId(1, 0)),
+                Some(Notice::ChangeAuthorityNotice(ChangeAuthorityNotice::Sol {
+                    id: NoticeId(1, 0),
+                    parent: [0; 32],
+                    new_authorities: vec![[3; 32]],
+                }))
+            );
+        });
+    }
+
+    #[test]
+    fn test_publish_signature_pending_valid_dot() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Dot;
+            let notice_id = NoticeId(5, 6);
+            let notice = Notice::CashExtractionNotice(CashExtractionNotice::Dot {
+                id: NoticeId(80, 1),
+                parent: [3u8; 32],
+                account: [2; 32],
+                principal: 100,
+            });
+            let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+            let dot_signature = pair.sign(&notice.encode_notice()[..]).0;
+            let dot_account = pair.public().0;
+            NoticeStates::insert(chain_id, notice_id, NoticeState::pending(&notice));
+            Notices::insert(chain_id, notice_id, notice);
+            // the dot key is registered independently of the validator's substrate id
+            let substrate_id: AccountId32 = sr25519::Pair::from_string("//Charlie", None)
+                .unwrap()
+                .public()
+                .into();
+            Validators::insert(
+                substrate_id.clone(),
+                ValidatorKeys {
+                    substrate_id,
+                    eth_address: [0; 20],
+                    dot_id: dot_account,
+                    sol_id: [0; 32],
+                },
+            );
+
+            assert_eq!(
+                publish_signature(chain_id, notice_id, ChainSignature::Dot(dot_signature)),
+                Ok(())
+            );
+            assert_eq!(
+                NoticeStates::get(chain_id, notice_id),
+                NoticeState::Pending {
+                    signature_pairs: ChainSignatureList::Dot(vec![(dot_account, dot_signature)]),
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_publish_signature_pending_valid_sol() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Sol;
+            let notice_id = NoticeId(5, 6);
+            let notice = Notice::ExtractionNotice(ExtractionNotice::Sol {
+                id: NoticeId(80, 1),
+                parent: [3u8; 32],
+                asset: [1; 32],
+                account: [2; 32],
+                amount: 100,
+            });
+            let pair = ed25519::Pair::from_seed(&hex_literal::hex!(
+                "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb"
+            ));
+            let sol_signature = pair.sign(&notice.encode_notice()[..]).0;
+            let sol_account = pair.public().0;
+            NoticeStates::insert(chain_id, notice_id, NoticeState::pending(&notice));
+            Notices::insert(chain_id, notice_id, notice);
+            // the sol key is an ed25519 key, registered independently of the sr25519 substrate id
+            let substrate_id: AccountId32 = sr25519::Pair::from_string("//Alice", None)
+                .unwrap()
+                .public()
+                .into();
+            Validators::insert(
+                substrate_id.clone(),
+                ValidatorKeys {
+                    substrate_id,
+                    eth_address: [0; 20],
+                    dot_id: [0; 32],
+                    sol_id: sol_account,
+                },
+            );
+
+            assert_eq!(
+                publish_signature(chain_id, notice_id, ChainSignature::Sol(sol_signature)),
+                Ok(())
+            );
+            assert_eq!(
+                NoticeStates::get(chain_id, notice_id),
+                NoticeState::Pending {
+                    signature_pairs: ChainSignatureList::Sol(vec![(sol_account, sol_signature)]),
+                }
+            );
+            assert_eq!(
+                publish_signature(chain_id, notice_id, ChainSignature::Sol([0; 64])),
+                Err(Reason::UnknownValidator)
+            );
+        });
+    }
+
+    #[test]
+    fn test_publish_signature_pending_unknown_validator_dot() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Dot;
+            let notice_id = NoticeId(5, 6);
+            let notice = Notice::CashExtractionNotice(CashExtractionNotice::Dot {
+                id: NoticeId(80, 1),
+                parent: [3u8; 32],
+                account: [2; 32],
+                principal: 100,
+            });
+            let pair = sr25519::Pair::from_string("//Bob", None).unwrap();
+            let dot_signature = pair.sign(&notice.encode_notice()[..]).0;
+            NoticeStates::insert(chain_id, notice_id, NoticeState::pending(&notice));
+            Notices::insert(chain_id, notice_id, notice);
+            let substrate_id = AccountId32::new([1; 32]);
+            Validators::insert(
+                substrate_id.clone(),
+                ValidatorKeys {
+                    substrate_id,
+                    eth_address: [0; 20],
+                    dot_id: [0; 32],
+                    sol_id: [0; 32],
+                },
+            );
+
+            assert_eq!(
+                publish_signature(chain_id, notice_id, ChainSignature::Dot(dot_signature)),
+                Err(Reason::UnknownValidator)
+            );
+        });
+    }
 }
@@ -0,0 +1,46 @@
+use frame_support::storage::StorageValue;
+
+use crate::{reason::Reason, types::Quorum, Config, Event, Module, SignatureQuorum};
+
+pub fn set_signature_quorum<T: Config>(quorum: Quorum) -> Result<(), Reason> {
+    quorum.check()?;
+
+    SignatureQuorum::put(quorum);
+
+    <Module<T>>::deposit_event(Event::SetSignatureQuorum(quorum));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_set_signature_quorum_invalid() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                set_signature_quorum::<Test>(Quorum::new(3, 2)),
+                Err(Reason::InvalidQuorum)
+            );
+            assert_eq!(SignatureQuorum::get(), Quorum::SUPERMAJORITY);
+        });
+    }
+
+    #[test]
+    fn test_set_signature_quorum() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(SignatureQuorum::get(), Quorum::SUPERMAJORITY);
+            assert_eq!(set_signature_quorum::<Test>(Quorum::new(3, 4)), Ok(()));
+            assert_eq!(SignatureQuorum::get(), Quorum::new(3, 4));
+
+            let events_post: Vec<_> = System::events().into_iter().collect();
+            let set_quorum_event = events_post.into_iter().last().unwrap();
+            assert_eq!(
+                mock::Event::pallet_cash(crate::Event::SetSignatureQuorum(Quorum::new(3, 4))),
+                set_quorum_event.event
+            );
+        });
+    }
+}
@@ -67,7 +67,7 @@ pub fn set_yield_next<T: Config>(
         next_yield,
         next_yield_index,
         next_yield_start,
-    );
+    )?;
 
     Ok(())
 }
@@ -14,6 +14,8 @@ pub fn set_supply_cap<T: Config>(chain_asset: ChainAsset, cap: AssetAmount) -> R
         Reason::AssetNotSupported
     );
 
+    internal::notices::dispatch_supply_cap_notice::<T>(chain_asset, cap)?;
+
     SupportedAssets::mutate(chain_asset, |maybe_asset_info| {
         if let Some(asset_info) = maybe_asset_info {
             asset_info.supply_cap = cap;
@@ -23,8 +25,6 @@ pub fn set_supply_cap<T: Config>(chain_asset: ChainAsset, cap: AssetAmount) -> R
 
     <Module<T>>::deposit_event(Event::SetSupplyCap(chain_asset, cap));
 
-    internal::notices::dispatch_supply_cap_notice::<T>(chain_asset, cap);
-
     Ok(())
 }
 
@@ -68,12 +68,8 @@ mod tests {
 
             assert_eq!(events_pre.len() + 2, events_post.len());
 
-            let set_supply_cap_event = events_post.into_iter().next().unwrap();
-
-            assert_eq!(
-                mock::Event::pallet_cash(crate::Event::SetSupplyCap(asset, 1000)),
-                set_supply_cap_event.event
-            );
+            assert!(events_post.into_iter().any(|record| record.event
+                == mock::Event::pallet_cash(crate::Event::SetSupplyCap(asset, 1000))));
 
             let notice_state_post: Vec<(ChainId, NoticeId, NoticeState)> =
                 NoticeStates::iter().collect();
@@ -106,4 +102,21 @@ mod tests {
             );
         });
     }
+    #[test]
+    fn test_set_supply_cap_unsupported_chain() {
+        new_test_ext().execute_with(|| {
+            let asset = ChainAsset::Tez([100; 20]);
+            let asset_info = AssetInfo::minimal(asset, USD);
+            SupportedAssets::insert(asset, asset_info);
+            let events_pre: Vec<_> = System::events().into_iter().collect();
+
+            assert_eq!(
+                set_supply_cap::<Test>(asset, 1000),
+                Err(Reason::InvalidChain)
+            );
+            assert_eq!(SupportedAssets::get(asset), Some(asset_info));
+            assert_eq!(LatestNotice::get(ChainId::Tez), None);
+            assert_eq!(System::events().len(), events_pre.len());
+        });
+    }
 }
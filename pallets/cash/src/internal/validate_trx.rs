@@ -1,5 +1,5 @@
 use crate::{
-    chains::{Chain, ChainAccount, Ethereum},
+    chains::{Chain, Ethereum},
     internal,
     notices::EncodeNotice,
     params::{UNSIGNED_TXS_LONGEVITY, UNSIGNED_TXS_PRIORITY},
@@ -90,11 +90,10 @@ pub fn validate_unsigned<T: Config>(
         }
         Call::publish_signature(chain_id, notice_id, signature) => {
             let notice = Notices::get(chain_id, notice_id).ok_or(ValidationError::UnknownNotice)?;
-            let signer = signature
-                .recover(&notice.encode_notice())
+            let signer = internal::notices::notice_signer(signature, &notice.encode_notice())
                 .map_err(|_| ValidationError::InvalidSignature)?;
 
-            if Validators::iter().any(|(_, v)| ChainAccount::Eth(v.eth_address) == signer) {
+            if internal::notices::is_validator(signer) {
                 Ok(
                     ValidTransaction::with_tag_prefix("Gateway::publish_signature")
                         .priority(UNSIGNED_TXS_PRIORITY)
@@ -124,8 +123,9 @@ mod tests {
             Chain, ChainAccount, ChainAccountSignature, ChainId, ChainSignature,
             ChainSignatureList, Ethereum,
         },
+        eip712,
         events::{ChainLogEvent, ChainLogId},
-        notices::{ExtractionNotice, Notice, NoticeId, NoticeState},
+        notices::{CashExtractionNotice, ExtractionNotice, Notice, NoticeId, NoticeState},
         reason::TrxReqParseError,
         tests::*,
         types::{ValidatorKeys, ValidatorSig},
@@ -134,7 +134,7 @@ mod tests {
     use ethereum_client::{events::EthereumEvent::Lock, EthereumLogEvent};
     use frame_support::storage::StorageMap;
 
-    use sp_core::crypto::AccountId32;
+    use sp_core::{crypto::AccountId32, sr25519, Pair};
 
     #[test]
     fn test_set_miner_external() {
@@ -293,6 +293,8 @@ mod tests {
                 ValidatorKeys {
                     substrate_id,
                     eth_address,
+                    dot_id: [0; 32],
+                    sol_id: [0; 32],
                 },
             );
 
@@ -492,6 +494,144 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_exec_trx_request_matic_signature() {
+        new_test_ext().execute_with(|| {
+            let request: Vec<u8> = String::from(
+                "(Extract 50000000 Cash Eth:0xfc04833Ca66b7D6B4F540d4C2544228f64a25ac2)",
+            )
+            .as_bytes()
+            .into();
+            let eth_address = <Ethereum as Chain>::signer_address().unwrap();
+            let eth_key_id =
+                runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+            let sign = |full_request: &str| {
+                runtime_interfaces::keyring_interface::sign_one(
+                    full_request.as_bytes().into(),
+                    eth_key_id.clone(),
+                )
+                .unwrap()
+            };
+            let matic_signature_raw = sign("\x19Ethereum Signed Message:\n78Matic:0:(Extract 50000000 Cash Eth:0xfc04833Ca66b7D6B4F540d4C2544228f64a25ac2)");
+            let eth_signature_raw = sign("\x19Ethereum Signed Message:\n720:(Extract 50000000 Cash Eth:0xfc04833Ca66b7D6B4F540d4C2544228f64a25ac2)");
+
+            let exp = ValidTransaction::with_tag_prefix("Gateway::exec_trx_request")
+                .priority(100)
+                .longevity(32)
+                .and_provides((ChainAccount::Matic(eth_address), 0))
+                .propagate(true)
+                .build();
+
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::InBlock {},
+                    &Call::exec_trx_request::<Test>(
+                        request.clone(),
+                        ChainAccountSignature::Matic(eth_address, matic_signature_raw),
+                        0
+                    ),
+                ),
+                Ok(exp)
+            );
+
+            // a request signed by the ethereum account can't be replayed by the matic account
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::InBlock {},
+                    &Call::exec_trx_request::<Test>(
+                        request,
+                        ChainAccountSignature::Matic(eth_address, eth_signature_raw),
+                        0
+                    ),
+                ),
+                Err(ValidationError::InvalidTrxRequest(
+                    Reason::SignatureAccountMismatch
+                ))
+            );
+        });
+    }
+
+    #[test]
+    fn test_exec_trx_request_typed_data_signature() {
+        new_test_ext().execute_with(|| {
+            let req_str = "(Extract 50000000 Cash Eth:0xfc04833Ca66b7D6B4F540d4C2544228f64a25ac2)";
+            let request: Vec<u8> = req_str.as_bytes().into();
+            let nonce = 0;
+            let typed_data = eip712::typed_data(
+                &trx_request::parse_request(req_str).unwrap(),
+                nonce,
+                eip712::genesis_hash::<Test>().unwrap(),
+            );
+            let eth_address = <Ethereum as Chain>::signer_address().unwrap();
+            let eth_key_id =
+                runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+            let signature_raw =
+                runtime_interfaces::keyring_interface::sign_one(typed_data, eth_key_id).unwrap();
+            let signature = ChainAccountSignature::Eth(eth_address, signature_raw);
+
+            let exp = ValidTransaction::with_tag_prefix("Gateway::exec_trx_request")
+                .priority(UNSIGNED_TXS_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .and_provides((ChainAccount::Eth(eth_address), 0))
+                .propagate(true)
+                .build();
+
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::InBlock {},
+                    &Call::exec_trx_request::<Test>(request, signature, nonce),
+                ),
+                Ok(exp)
+            );
+        });
+    }
+
+    #[test]
+    fn test_exec_trx_request_dot_signature() {
+        new_test_ext().execute_with(|| {
+            let request: Vec<u8> = String::from(
+                "(Extract 50000000 Cash Dot:15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5)",
+            )
+            .as_bytes()
+            .into();
+            let nonce = 0;
+            let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+            let dot_address = pair.public().0;
+            let signature_raw = pair
+                .sign(&internal::exec_trx_request::prepend_nonce(&request, nonce)[..])
+                .0;
+
+            let signature = ChainAccountSignature::Dot(dot_address, signature_raw);
+
+            let exp = ValidTransaction::with_tag_prefix("Gateway::exec_trx_request")
+                .priority(UNSIGNED_TXS_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .and_provides((ChainAccount::Dot(dot_address), 0))
+                .propagate(true)
+                .build();
+
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::InBlock {},
+                    &Call::exec_trx_request::<Test>(request.clone(), signature, nonce),
+                ),
+                Ok(exp)
+            );
+
+            let wrong_signature = ChainAccountSignature::Dot([1; 32], signature_raw);
+
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::InBlock {},
+                    &Call::exec_trx_request::<Test>(request, wrong_signature, nonce),
+                ),
+                Err(ValidationError::InvalidTrxRequest(
+                    Reason::SignatureAccountMismatch
+                ))
+            );
+        });
+    }
+
     #[test]
     fn test_publish_signature_invalid_signature() {
         new_test_ext().execute_with(|| {
@@ -592,6 +732,60 @@ mod tests {
                 ValidatorKeys {
                     substrate_id,
                     eth_address: signer,
+                    dot_id: [0; 32],
+                    sol_id: [0; 32],
+                },
+            );
+
+            let exp = ValidTransaction::with_tag_prefix("Gateway::publish_signature")
+                .priority(UNSIGNED_TXS_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .and_provides(signature)
+                .propagate(true)
+                .build();
+
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::InBlock {},
+                    &Call::publish_signature::<Test>(chain_id, notice_id, signature),
+                ),
+                Ok(exp)
+            );
+        });
+    }
+
+    #[test]
+    fn test_publish_signature_valid_dot() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Dot;
+            let notice_id = NoticeId(5, 6);
+            let notice = Notice::CashExtractionNotice(CashExtractionNotice::Dot {
+                id: NoticeId(80, 1),
+                parent: [3u8; 32],
+                account: [2; 32],
+                principal: 100,
+            });
+            let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+            let signature = ChainSignature::Dot(pair.sign(&notice.encode_notice()[..]).0);
+            NoticeStates::insert(chain_id, notice_id, NoticeState::pending(&notice));
+            Notices::insert(chain_id, notice_id, notice);
+
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::InBlock {},
+                    &Call::publish_signature::<Test>(chain_id, notice_id, signature),
+                ),
+                Err(ValidationError::InvalidSignature)
+            );
+
+            let substrate_id = AccountId32::new(pair.public().0);
+            Validators::insert(
+                substrate_id.clone(),
+                ValidatorKeys {
+                    substrate_id,
+                    eth_address: [0; 20],
+                    dot_id: [0; 32],
+                    sol_id: [0; 32],
                 },
             );
 
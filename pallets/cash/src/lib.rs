@@ -20,14 +20,15 @@ use crate::{
     types::{
         AssetAmount, AssetBalance, AssetIndex, AssetInfo, Bips, CashIndex, CashPrincipal,
         CashPrincipalAmount, CodeHash, EncodedNotice, GovernanceResult, InterestRateModel,
-        LiquidityFactor, Nonce, Reason, SessionIndex, Timestamp, ValidatorKeys, ValidatorSig, APR,
+        LiquidityFactor, Nonce, Quorum, Reason, SessionIndex, Timestamp, Uint, ValidatorKeys,
+        ValidatorSig, APR,
     },
 };
 
 use codec::alloc::string::String;
 use frame_support::{
     decl_event, decl_module, decl_storage, dispatch,
-    sp_runtime::traits::Convert,
+    sp_runtime::traits::{Convert, OpaqueKeys},
     traits::{OnRuntimeUpgrade, StoredMap, UnfilteredDispatchable},
     weights::{DispatchClass, GetDispatchInfo, Pays, Weight},
     Parameter,
@@ -35,7 +36,7 @@ use frame_support::{
 use frame_system;
 use frame_system::{ensure_none, ensure_root, offchain::CreateSignedTransaction};
 use our_std::{collections::btree_set::BTreeSet, error, log, str, vec::Vec, Debuggable};
-use sp_core::crypto::AccountId32;
+use sp_core::crypto::{key_types, AccountId32};
 use sp_runtime::transaction_validity::{
     InvalidTransaction, TransactionSource, TransactionValidity,
 };
@@ -52,6 +53,7 @@ extern crate lazy_static;
 pub mod chains;
 pub mod converters;
 pub mod core;
+pub mod eip712;
 pub mod events;
 pub mod factor;
 pub mod internal;
@@ -124,6 +126,9 @@ decl_storage! {
         /// The current set of allowed validators, and their associated keys.
         Validators get(fn validators) : map hasher(blake2_128_concat) SubstrateId => Option<ValidatorKeys>;
 
+        /// The fraction of validators required to sign notices and accept events (defaults to 2/3).
+        SignatureQuorum get(fn signature_quorum): Quorum;
+
         /// An index to track interest earned by CASH holders and owed by CASH borrowers.
         /// Note - the implementation of Default for CashIndex returns ONE. This also provides
         /// the initial value as it is currently implemented.
@@ -136,11 +141,20 @@ decl_storage! {
         CashYield get(fn cash_yield) config(): APR;
 
         /// The liquidation incentive on seized collateral (e.g. 8% = 800 bips).
-        GlobalLiquidationIncentive get(fn liquidation_incentive): Bips;
+        GlobalLiquidationIncentive get(fn liquidation_incentive): Bips = 800;
+
+        /// The maximum fraction of a borrower's debt that may be repaid in a single liquidation (e.g. 50% = 5000 bips).
+        CloseFactor get(fn close_factor): Bips = 5_000;
 
         /// The fraction of borrower interest that is paid to the protocol (e.g. 1/10th = 1000 bips).
         Spreads get(fn spread): map hasher(blake2_128_concat) ChainAsset => Bips;
 
+        /// The borrow rate of each asset with an adaptive rate model, and the time it was last adjusted.
+        BorrowRates get(fn borrow_rate): map hasher(blake2_128_concat) ChainAsset => (APR, Timestamp);
+
+        /// The fraction of a unit each adaptive borrow rate has moved beyond its stored rate, so adjustments add up.
+        BorrowRateRemainders get(fn borrow_rate_remainder): map hasher(blake2_128_concat) ChainAsset => Uint;
+
         /// The mapping of indices to track interest owed by asset borrowers, by asset.
         BorrowIndices get(fn borrow_index): map hasher(blake2_128_concat) ChainAsset => AssetIndex;
 
@@ -153,6 +167,9 @@ decl_storage! {
         /// The total CASH principal in existence.
         TotalCashPrincipal get(fn total_cash_principal): CashPrincipalAmount;
 
+        /// The total CASH principal earned by the protocol from spreads.
+        TotalReservePrincipal get(fn total_reserve_principal): CashPrincipalAmount;
+
         /// The total amount supplied per collateral asset.
         TotalSupplyAssets get(fn total_supply_asset): map hasher(blake2_128_concat) ChainAsset => AssetAmount;
 
@@ -210,8 +227,14 @@ decl_storage! {
         /// The timestamp of the previous block or defaults to timestamp at genesis.
         LastBlockTimestamp get(fn last_block_timestamp): Timestamp;
 
+        /// The timestamp since which interest has not accrued on each asset, while its price is unhealthy.
+        DeferredAccrualTimestamps get(fn deferred_accrual_timestamp): map hasher(blake2_128_concat) ChainAsset => Option<Timestamp>;
+
         /// The cash index of the previous yield accrual point or defaults to initial cash index.
         LastYieldCashIndex get(fn last_yield_cash_index): CashIndex;
+
+        /// The version of the storage layout, used to decide which migrations to run on upgrade.
+        StorageVersion get(fn storage_version): u32;
     }
     add_extra_genesis {
         config(assets): Vec<AssetInfo>;
@@ -219,6 +242,7 @@ decl_storage! {
         build(|config| {
             Module::<T>::initialize_assets(config.assets.clone());
             Module::<T>::initialize_validators(config.validators.clone());
+            StorageVersion::put(internal::migrations::STORAGE_VERSION);
         })
     }
 }
@@ -290,9 +314,24 @@ decl_event!(
         /// A new supply cap has been set. [asset, cap]
         SetSupplyCap(ChainAsset, AssetAmount),
 
+        /// A new borrow cap has been set. [asset, cap]
+        SetBorrowCap(ChainAsset, AssetAmount),
+
         /// A new validator set has been chosen
         ChangeValidators(Vec<ValidatorKeys>),
 
+        /// A new signature quorum has been set. [quorum]
+        SetSignatureQuorum(Quorum),
+
+        /// A new liquidation incentive has been set. [incentive]
+        SetLiquidationIncentive(Bips),
+
+        /// A new close factor has been set. [close_factor]
+        SetCloseFactor(Bips),
+
+        /// A new spread has been set for an asset. [asset, spread]
+        SetSpread(ChainAsset, Bips),
+
         /// A new yield rate has been chosen
         SetYieldNext(APR, Timestamp),
 
@@ -313,6 +352,7 @@ fn check_failure<T: Config>(res: Result<(), Reason>) -> Result<(), Reason> {
 
 pub trait SessionInterface<AccountId>: frame_system::Config {
     fn has_next_keys(x: AccountId) -> bool;
+    fn next_grandpa_key(x: AccountId) -> Option<[u8; 32]>;
     fn rotate_session();
 }
 
@@ -327,6 +367,11 @@ where
         }
     }
 
+    fn next_grandpa_key(x: SubstrateId) -> Option<[u8; 32]> {
+        <pallet_session::Module<T>>::next_keys(x as T::ValidatorId)
+            .and_then(|keys| keys.get(key_types::GRANDPA))
+    }
+
     fn rotate_session() {
         <pallet_session::Module<T>>::rotate_session();
     }
@@ -392,16 +437,41 @@ fn intersection_count<T: Ord + Debuggable>(a: Vec<T>, b: Vec<T>) -> usize {
 
 fn has_requisite_signatures(notice_state: NoticeState, validators: &Vec<ValidatorKeys>) -> bool {
     let validator_count = validators.iter().count();
-    let quorum_count = validator_count; // TODO: Add a real quorum count
+    let quorum_count = SignatureQuorum::get().required(validator_count);
 
     match notice_state {
         NoticeState::Pending { signature_pairs } => match signature_pairs {
-            ChainSignatureList::Eth(signature_pairs) => {
+            ChainSignatureList::Eth(signature_pairs)
+            | ChainSignatureList::Matic(signature_pairs) => {
                 intersection_count(
                     signature_pairs.iter().map(|p| p.0).collect(),
                     validators.iter().map(|v| v.eth_address).collect(),
                 ) >= quorum_count
             }
+            ChainSignatureList::Dot(signature_pairs) => {
+                intersection_count(
+                    signature_pairs.iter().map(|p| p.0).collect(),
+                    validators
+                        .iter()
+                        .filter_map(|v| match v.chain_account(ChainId::Dot) {
+                            Some(ChainAccount::Dot(dot_id)) => Some(dot_id),
+                            _ => None,
+                        })
+                        .collect(),
+                ) >= quorum_count
+            }
+            ChainSignatureList::Sol(signature_pairs) => {
+                intersection_count(
+                    signature_pairs.iter().map(|p| p.0).collect(),
+                    validators
+                        .iter()
+                        .filter_map(|v| match v.chain_account(ChainId::Sol) {
+                            Some(ChainAccount::Sol(sol_id)) => Some(sol_id),
+                            _ => None,
+                        })
+                        .collect(),
+                ) >= quorum_count
+            }
             _ => false,
         },
         _ => false,
@@ -468,6 +538,11 @@ decl_module! {
         // Events must be initialized if they are used by the pallet.
         fn deposit_event() = default;
 
+        /// Called by substrate when the runtime is upgraded, to migrate storage.
+        fn on_runtime_upgrade() -> frame_support::weights::Weight {
+            internal::migrations::on_runtime_upgrade::<T>()
+        }
+
         /// Called by substrate on block initialization.
         /// Our initialization function is fallible, but that's not allowed.
         fn on_initialize(block: T::BlockNumber) -> frame_support::weights::Weight {
@@ -500,6 +575,13 @@ decl_module! {
             Ok(check_failure::<T>(internal::change_validators::change_validators::<T>(validators))?)
         }
 
+        /// Sets the fraction of validators required to sign notices and accept events. [Root]
+        #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
+        pub fn set_signature_quorum(origin, quorum: Quorum) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::quorum::set_signature_quorum::<T>(quorum))?)
+        }
+
         /// Sets the allowed next code hash to the given hash. [Root]
         #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
         pub fn allow_next_code_with_hash(origin, hash: CodeHash) -> dispatch::DispatchResult {
@@ -521,13 +603,55 @@ decl_module! {
             Ok(check_failure::<T>(internal::supply_cap::set_supply_cap::<T>(asset, amount))?)
         }
 
-        /// Set the liquidity factor for an asset [Root]
+        /// Sets the borrow cap for a given chain asset, or zero for no limit [Root]
+        #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
+        pub fn set_borrow_cap(origin, asset: ChainAsset, amount: AssetAmount) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_borrow_cap::<T>(asset, amount))?)
+        }
+
+        /// Set both the collateral and borrow factors for an asset [Root]
         #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
         pub fn set_liquidity_factor(origin, asset: ChainAsset, factor: LiquidityFactor) -> dispatch::DispatchResult {
             ensure_root(origin)?;
             Ok(check_failure::<T>(internal::assets::set_liquidity_factor::<T>(asset, factor))?)
         }
 
+        /// Set the collateral factor for an asset [Root]
+        #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
+        pub fn set_collateral_factor(origin, asset: ChainAsset, factor: LiquidityFactor) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_collateral_factor::<T>(asset, factor))?)
+        }
+
+        /// Set the borrow factor for an asset [Root]
+        #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
+        pub fn set_borrow_factor(origin, asset: ChainAsset, factor: LiquidityFactor) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_borrow_factor::<T>(asset, factor))?)
+        }
+
+        /// Set the liquidation incentive on seized collateral. [Root]
+        #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
+        pub fn set_liquidation_incentive(origin, incentive: Bips) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_liquidation_incentive::<T>(incentive))?)
+        }
+
+        /// Set the maximum fraction of a borrower's debt that may be repaid per liquidation. [Root]
+        #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
+        pub fn set_close_factor(origin, close_factor: Bips) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_close_factor::<T>(close_factor))?)
+        }
+
+        /// Set the fraction of borrower interest paid to the protocol for an asset. [Root]
+        #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
+        pub fn set_spread(origin, asset: ChainAsset, spread: Bips) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_spread::<T>(asset, spread))?)
+        }
+
         /// Update the interest rate model for a given asset. [Root]
         #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
         pub fn set_rate_model(origin, asset: ChainAsset, model: InterestRateModel) -> dispatch::DispatchResult {
@@ -580,6 +704,10 @@ decl_module! {
             if fail > 0 {
                 log!("offchain_worker error(s) during process notices: {:?}", failures);
             }
+
+            if let Err(e) = internal::liquidator::process_liquidations::<T>(block_number) {
+                log!("offchain_worker error during liquidation keeper: {:?}", e);
+            }
         }
 
         /// Execute a transaction request on behalf of a user
@@ -677,6 +805,17 @@ impl<T: Config> Module<T> {
     pub fn get_rates(asset: ChainAsset) -> Result<(APR, APR), Reason> {
         Ok(core::get_rates::<T>(asset)?)
     }
+
+    /// Check whether the given notice has enough signatures to be relayed to its starport.
+    pub fn is_notice_ready(chain_id: ChainId, notice_id: NoticeId) -> Result<bool, Reason> {
+        match NoticeStates::get(chain_id, notice_id) {
+            NoticeState::Missing => Err(Reason::NoticeMissing(chain_id, notice_id)),
+            notice_state => {
+                let validators: Vec<_> = Validators::iter().map(|v| v.1).collect();
+                Ok(has_requisite_signatures(notice_state, &validators))
+            }
+        }
+    }
 }
 
 impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
@@ -1,6 +1,7 @@
 use crate::{
     chains::{
         Chain, ChainAccount, ChainHash, ChainId, ChainSignature, ChainSignatureList, Ethereum,
+        Polkadot, Solana,
     },
     reason::Reason,
 };
@@ -65,6 +66,27 @@ pub enum ExtractionNotice {
         account: <Ethereum as Chain>::Address,
         amount: <Ethereum as Chain>::Amount,
     },
+    Dot {
+        id: NoticeId,
+        parent: <Polkadot as Chain>::Hash,
+        asset: <Polkadot as Chain>::Address,
+        account: <Polkadot as Chain>::Address,
+        amount: <Polkadot as Chain>::Amount,
+    },
+    Sol {
+        id: NoticeId,
+        parent: <Solana as Chain>::Hash,
+        asset: <Solana as Chain>::Address,
+        account: <Solana as Chain>::Address,
+        amount: <Solana as Chain>::Amount,
+    },
+    Matic {
+        id: NoticeId,
+        parent: <Ethereum as Chain>::Hash,
+        asset: <Ethereum as Chain>::Address,
+        account: <Ethereum as Chain>::Address,
+        amount: <Ethereum as Chain>::Amount,
+    },
 }
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
@@ -75,6 +97,24 @@ pub enum CashExtractionNotice {
         account: <Ethereum as Chain>::Address,
         principal: <Ethereum as Chain>::Amount,
     },
+    Dot {
+        id: NoticeId,
+        parent: <Polkadot as Chain>::Hash,
+        account: <Polkadot as Chain>::Address,
+        principal: <Polkadot as Chain>::Amount,
+    },
+    Sol {
+        id: NoticeId,
+        parent: <Solana as Chain>::Hash,
+        account: <Solana as Chain>::Address,
+        principal: <Solana as Chain>::Amount,
+    },
+    Matic {
+        id: NoticeId,
+        parent: <Ethereum as Chain>::Hash,
+        account: <Ethereum as Chain>::Address,
+        principal: <Ethereum as Chain>::Amount,
+    },
 }
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
@@ -86,6 +126,27 @@ pub enum FutureYieldNotice {
         next_cash_index: <Ethereum as Chain>::CashIndex,
         next_cash_yield_start: <Ethereum as Chain>::Timestamp,
     },
+    Matic {
+        id: NoticeId,
+        parent: <Ethereum as Chain>::Hash,
+        next_cash_yield: <Ethereum as Chain>::Rate,
+        next_cash_index: <Ethereum as Chain>::CashIndex,
+        next_cash_yield_start: <Ethereum as Chain>::Timestamp,
+    },
+    Dot {
+        id: NoticeId,
+        parent: <Polkadot as Chain>::Hash,
+        next_cash_yield: <Polkadot as Chain>::Rate,
+        next_cash_index: <Polkadot as Chain>::CashIndex,
+        next_cash_yield_start: <Polkadot as Chain>::Timestamp,
+    },
+    Sol {
+        id: NoticeId,
+        parent: <Solana as Chain>::Hash,
+        next_cash_yield: <Solana as Chain>::Rate,
+        next_cash_index: <Solana as Chain>::CashIndex,
+        next_cash_yield_start: <Solana as Chain>::Timestamp,
+    },
 }
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
@@ -96,6 +157,24 @@ pub enum SetSupplyCapNotice {
         asset: <Ethereum as Chain>::Address,
         cap: <Ethereum as Chain>::Amount,
     },
+    Dot {
+        id: NoticeId,
+        parent: <Polkadot as Chain>::Hash,
+        asset: <Polkadot as Chain>::Address,
+        cap: <Polkadot as Chain>::Amount,
+    },
+    Sol {
+        id: NoticeId,
+        parent: <Solana as Chain>::Hash,
+        asset: <Solana as Chain>::Address,
+        cap: <Solana as Chain>::Amount,
+    },
+    Matic {
+        id: NoticeId,
+        parent: <Ethereum as Chain>::Hash,
+        asset: <Ethereum as Chain>::Address,
+        cap: <Ethereum as Chain>::Amount,
+    },
 }
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
@@ -105,6 +184,21 @@ pub enum ChangeAuthorityNotice {
         parent: <Ethereum as Chain>::Hash,
         new_authorities: Vec<<Ethereum as Chain>::Address>,
     },
+    Matic {
+        id: NoticeId,
+        parent: <Ethereum as Chain>::Hash,
+        new_authorities: Vec<<Ethereum as Chain>::Address>,
+    },
+    Dot {
+        id: NoticeId,
+        parent: <Polkadot as Chain>::Hash,
+        new_authorities: Vec<<Polkadot as Chain>::Address>,
+    },
+    Sol {
+        id: NoticeId,
+        parent: <Solana as Chain>::Hash,
+        new_authorities: Vec<<Solana as Chain>::Address>,
+    },
 }
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
@@ -125,18 +219,33 @@ impl Notice {
         match self {
             Notice::ExtractionNotice(n) => match n {
                 ExtractionNotice::Eth { .. } => ChainId::Eth,
+                ExtractionNotice::Dot { .. } => ChainId::Dot,
+                ExtractionNotice::Sol { .. } => ChainId::Sol,
+                ExtractionNotice::Matic { .. } => ChainId::Matic,
             },
             Notice::CashExtractionNotice(n) => match n {
                 CashExtractionNotice::Eth { .. } => ChainId::Eth,
+                CashExtractionNotice::Dot { .. } => ChainId::Dot,
+                CashExtractionNotice::Sol { .. } => ChainId::Sol,
+                CashExtractionNotice::Matic { .. } => ChainId::Matic,
             },
             Notice::FutureYieldNotice(n) => match n {
                 FutureYieldNotice::Eth { .. } => ChainId::Eth,
+                FutureYieldNotice::Matic { .. } => ChainId::Matic,
+                FutureYieldNotice::Dot { .. } => ChainId::Dot,
+                FutureYieldNotice::Sol { .. } => ChainId::Sol,
             },
             Notice::SetSupplyCapNotice(n) => match n {
                 SetSupplyCapNotice::Eth { .. } => ChainId::Eth,
+                SetSupplyCapNotice::Dot { .. } => ChainId::Dot,
+                SetSupplyCapNotice::Sol { .. } => ChainId::Sol,
+                SetSupplyCapNotice::Matic { .. } => ChainId::Matic,
             },
             Notice::ChangeAuthorityNotice(n) => match n {
                 ChangeAuthorityNotice::Eth { .. } => ChainId::Eth,
+                ChangeAuthorityNotice::Matic { .. } => ChainId::Matic,
+                ChangeAuthorityNotice::Dot { .. } => ChainId::Dot,
+                ChangeAuthorityNotice::Sol { .. } => ChainId::Sol,
             },
         }
     }
@@ -151,14 +260,17 @@ pub trait EncodeNotice {
 }
 
 const ETH_CHAIN_IDENT: &'static [u8] = b"ETH:";
+const MATIC_CHAIN_IDENT: &'static [u8] = b"MATIC:";
 
+/// ABI encoding of a notice for an EVM starport, prefixed by the chain it is meant for.
 fn encode_notice_params(
+    chain_ident: &[u8],
     id: &NoticeId,
     parent: &<Ethereum as Chain>::Hash,
     signature: <Ethereum as Chain>::Hash,
     tokens: &[ethabi::Token],
 ) -> Vec<u8> {
-    let mut result: Vec<u8> = ETH_CHAIN_IDENT.to_vec();
+    let mut result: Vec<u8> = chain_ident.to_vec();
     let header_encoded = ethabi::encode(&[
         Token::Uint(id.era_id().into()),
         Token::Uint(id.era_index().into()),
@@ -172,6 +284,87 @@ fn encode_notice_params(
     result
 }
 
+const DOT_CHAIN_IDENT: &'static [u8] = b"DOT:";
+
+fn encode_dot_notice_params<P: Encode>(
+    id: &NoticeId,
+    parent: &<Polkadot as Chain>::Hash,
+    method: &[u8],
+    params: P,
+) -> Vec<u8> {
+    let mut result: Vec<u8> = DOT_CHAIN_IDENT.to_vec();
+    let header_encoded = (id.era_id(), id.era_index(), parent).encode();
+
+    result.extend_from_slice(&header_encoded[..]);
+    result.extend_from_slice(&method.encode()[..]);
+    result.extend_from_slice(&params.encode()[..]);
+    result
+}
+
+const SOL_CHAIN_IDENT: &'static [u8] = b"SOL:";
+
+/// Borsh serialization, as expected by a Solana starport, for the values carried by notices.
+trait BorshEncode {
+    fn borsh_encode(&self, out: &mut Vec<u8>);
+}
+
+impl BorshEncode for u32 {
+    fn borsh_encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+}
+
+impl BorshEncode for u64 {
+    fn borsh_encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+}
+
+impl BorshEncode for u128 {
+    fn borsh_encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+}
+
+impl BorshEncode for [u8; 32] {
+    fn borsh_encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self[..]);
+    }
+}
+
+impl BorshEncode for [u8] {
+    fn borsh_encode(&self, out: &mut Vec<u8>) {
+        (self.len() as u32).borsh_encode(out);
+        out.extend_from_slice(self);
+    }
+}
+
+impl BorshEncode for Vec<[u8; 32]> {
+    fn borsh_encode(&self, out: &mut Vec<u8>) {
+        (self.len() as u32).borsh_encode(out);
+        for item in self {
+            item.borsh_encode(out);
+        }
+    }
+}
+
+fn encode_sol_notice_params(
+    id: &NoticeId,
+    parent: &<Solana as Chain>::Hash,
+    method: &[u8],
+    params: &[&dyn BorshEncode],
+) -> Vec<u8> {
+    let mut result: Vec<u8> = SOL_CHAIN_IDENT.to_vec();
+    id.era_id().borsh_encode(&mut result);
+    id.era_index().borsh_encode(&mut result);
+    parent.borsh_encode(&mut result);
+    method.borsh_encode(&mut result);
+    for param in params {
+        param.borsh_encode(&mut result);
+    }
+    result
+}
+
 impl EncodeNotice for ExtractionNotice {
     fn encode_notice(&self) -> EncodedNotice {
         match self {
@@ -182,6 +375,24 @@ impl EncodeNotice for ExtractionNotice {
                 account,
                 amount,
             } => encode_notice_params(
+                ETH_CHAIN_IDENT,
+                id,
+                parent,
+                *UNLOCK_SIG,
+                &[
+                    Token::Address(asset.into()),
+                    Token::Uint((*amount).into()),
+                    Token::Address(account.into()),
+                ],
+            ),
+            ExtractionNotice::Matic {
+                id,
+                parent,
+                asset,
+                account,
+                amount,
+            } => encode_notice_params(
+                MATIC_CHAIN_IDENT,
                 id,
                 parent,
                 *UNLOCK_SIG,
@@ -191,6 +402,20 @@ impl EncodeNotice for ExtractionNotice {
                     Token::Address(account.into()),
                 ],
             ),
+            ExtractionNotice::Dot {
+                id,
+                parent,
+                asset,
+                account,
+                amount,
+            } => encode_dot_notice_params(id, parent, b"unlock", (asset, amount, account)),
+            ExtractionNotice::Sol {
+                id,
+                parent,
+                asset,
+                account,
+                amount,
+            } => encode_sol_notice_params(id, parent, b"unlock", &[asset, amount, account]),
         }
     }
 }
@@ -204,6 +429,7 @@ impl EncodeNotice for CashExtractionNotice {
                 account,
                 principal,
             } => encode_notice_params(
+                ETH_CHAIN_IDENT,
                 id,
                 parent,
                 *UNLOCK_CASH_SIG,
@@ -212,6 +438,33 @@ impl EncodeNotice for CashExtractionNotice {
                     Token::Uint((*principal).into()),
                 ],
             ),
+            CashExtractionNotice::Matic {
+                id,
+                parent,
+                account,
+                principal,
+            } => encode_notice_params(
+                MATIC_CHAIN_IDENT,
+                id,
+                parent,
+                *UNLOCK_CASH_SIG,
+                &[
+                    Token::Address(account.into()),
+                    Token::Uint((*principal).into()),
+                ],
+            ),
+            CashExtractionNotice::Dot {
+                id,
+                parent,
+                account,
+                principal,
+            } => encode_dot_notice_params(id, parent, b"unlockCash", (account, principal)),
+            CashExtractionNotice::Sol {
+                id,
+                parent,
+                account,
+                principal,
+            } => encode_sol_notice_params(id, parent, b"unlockCash", &[account, principal]),
         }
     }
 }
@@ -226,6 +479,24 @@ impl EncodeNotice for FutureYieldNotice {
                 next_cash_yield_start,
                 next_cash_index,
             } => encode_notice_params(
+                ETH_CHAIN_IDENT,
+                id,
+                parent,
+                *SET_FUTURE_YIELD_SIG,
+                &[
+                    Token::Uint((*next_cash_yield).into()),
+                    Token::Uint((*next_cash_index).into()),
+                    Token::Uint((*next_cash_yield_start).into()),
+                ],
+            ),
+            FutureYieldNotice::Matic {
+                id,
+                parent,
+                next_cash_yield,
+                next_cash_yield_start,
+                next_cash_index,
+            } => encode_notice_params(
+                MATIC_CHAIN_IDENT,
                 id,
                 parent,
                 *SET_FUTURE_YIELD_SIG,
@@ -235,6 +506,30 @@ impl EncodeNotice for FutureYieldNotice {
                     Token::Uint((*next_cash_yield_start).into()),
                 ],
             ),
+            FutureYieldNotice::Dot {
+                id,
+                parent,
+                next_cash_yield,
+                next_cash_yield_start,
+                next_cash_index,
+            } => encode_dot_notice_params(
+                id,
+                parent,
+                b"setFutureYield",
+                (next_cash_yield, next_cash_index, next_cash_yield_start),
+            ),
+            FutureYieldNotice::Sol {
+                id,
+                parent,
+                next_cash_yield,
+                next_cash_yield_start,
+                next_cash_index,
+            } => encode_sol_notice_params(
+                id,
+                parent,
+                b"setFutureYield",
+                &[next_cash_yield, next_cash_index, next_cash_yield_start],
+            ),
         }
     }
 }
@@ -248,11 +543,36 @@ impl EncodeNotice for SetSupplyCapNotice {
                 asset,
                 cap,
             } => encode_notice_params(
+                ETH_CHAIN_IDENT,
+                id,
+                parent,
+                *SET_SUPPLY_CAP_SIG,
+                &[Token::Address(asset.into()), Token::Uint((*cap).into())],
+            ),
+            SetSupplyCapNotice::Matic {
+                id,
+                parent,
+                asset,
+                cap,
+            } => encode_notice_params(
+                MATIC_CHAIN_IDENT,
                 id,
                 parent,
                 *SET_SUPPLY_CAP_SIG,
                 &[Token::Address(asset.into()), Token::Uint((*cap).into())],
             ),
+            SetSupplyCapNotice::Dot {
+                id,
+                parent,
+                asset,
+                cap,
+            } => encode_dot_notice_params(id, parent, b"setSupplyCap", (asset, cap)),
+            SetSupplyCapNotice::Sol {
+                id,
+                parent,
+                asset,
+                cap,
+            } => encode_sol_notice_params(id, parent, b"setSupplyCap", &[asset, cap]),
         }
     }
 }
@@ -265,6 +585,7 @@ impl EncodeNotice for ChangeAuthorityNotice {
                 parent,
                 new_authorities,
             } => encode_notice_params(
+                ETH_CHAIN_IDENT,
                 id,
                 parent,
                 *CHANGE_AUTHORITIES_SIG,
@@ -275,6 +596,32 @@ impl EncodeNotice for ChangeAuthorityNotice {
                         .collect(),
                 )],
             ),
+            ChangeAuthorityNotice::Matic {
+                id,
+                parent,
+                new_authorities,
+            } => encode_notice_params(
+                MATIC_CHAIN_IDENT,
+                id,
+                parent,
+                *CHANGE_AUTHORITIES_SIG,
+                &[Token::Array(
+                    new_authorities
+                        .iter()
+                        .map(|auth| Token::Address(auth.into()))
+                        .collect(),
+                )],
+            ),
+            ChangeAuthorityNotice::Dot {
+                id,
+                parent,
+                new_authorities,
+            } => encode_dot_notice_params(id, parent, b"changeAuthorities", new_authorities),
+            ChangeAuthorityNotice::Sol {
+                id,
+                parent,
+                new_authorities,
+            } => encode_sol_notice_params(id, parent, b"changeAuthorities", &[new_authorities]),
         }
     }
 }
@@ -295,18 +642,33 @@ pub fn default_notice_signatures(notice: &Notice) -> ChainSignatureList {
     match notice {
         Notice::ExtractionNotice(n) => match n {
             ExtractionNotice::Eth { .. } => ChainSignatureList::Eth(vec![]),
+            ExtractionNotice::Dot { .. } => ChainSignatureList::Dot(vec![]),
+            ExtractionNotice::Sol { .. } => ChainSignatureList::Sol(vec![]),
+            ExtractionNotice::Matic { .. } => ChainSignatureList::Matic(vec![]),
         },
         Notice::CashExtractionNotice(n) => match n {
             CashExtractionNotice::Eth { .. } => ChainSignatureList::Eth(vec![]),
+            CashExtractionNotice::Dot { .. } => ChainSignatureList::Dot(vec![]),
+            CashExtractionNotice::Sol { .. } => ChainSignatureList::Sol(vec![]),
+            CashExtractionNotice::Matic { .. } => ChainSignatureList::Matic(vec![]),
         },
         Notice::FutureYieldNotice(n) => match n {
             FutureYieldNotice::Eth { .. } => ChainSignatureList::Eth(vec![]),
+            FutureYieldNotice::Matic { .. } => ChainSignatureList::Matic(vec![]),
+            FutureYieldNotice::Dot { .. } => ChainSignatureList::Dot(vec![]),
+            FutureYieldNotice::Sol { .. } => ChainSignatureList::Sol(vec![]),
         },
         Notice::SetSupplyCapNotice(n) => match n {
             SetSupplyCapNotice::Eth { .. } => ChainSignatureList::Eth(vec![]),
+            SetSupplyCapNotice::Dot { .. } => ChainSignatureList::Dot(vec![]),
+            SetSupplyCapNotice::Sol { .. } => ChainSignatureList::Sol(vec![]),
+            SetSupplyCapNotice::Matic { .. } => ChainSignatureList::Matic(vec![]),
         },
         Notice::ChangeAuthorityNotice(n) => match n {
             ChangeAuthorityNotice::Eth { .. } => ChainSignatureList::Eth(vec![]),
+            ChangeAuthorityNotice::Matic { .. } => ChainSignatureList::Matic(vec![]),
+            ChangeAuthorityNotice::Dot { .. } => ChainSignatureList::Dot(vec![]),
+            ChangeAuthorityNotice::Sol { .. } => ChainSignatureList::Sol(vec![]),
         },
     }
 }
@@ -338,6 +700,17 @@ pub fn has_signer(signature_pairs: &ChainSignatureList, signer: ChainAccount) ->
         (ChainSignatureList::Eth(eth_signature_pairs), ChainAccount::Eth(eth_account)) => {
             eth_signature_pairs.iter().any(|(s, _)| s == &eth_account)
         }
+        (ChainSignatureList::Dot(dot_signature_pairs), ChainAccount::Dot(dot_account)) => {
+            dot_signature_pairs.iter().any(|(s, _)| s == &dot_account)
+        }
+        (ChainSignatureList::Sol(sol_signature_pairs), ChainAccount::Sol(sol_account)) => {
+            sol_signature_pairs.iter().any(|(s, _)| s == &sol_account)
+        }
+        (ChainSignatureList::Matic(matic_signature_pairs), ChainAccount::Matic(matic_account)) => {
+            matic_signature_pairs
+                .iter()
+                .any(|(s, _)| s == &matic_account)
+        }
         _ => false,
     }
 }
@@ -411,6 +784,235 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_encodes_dot_extraction_notice() {
+        let notice = Notice::ExtractionNotice(ExtractionNotice::Dot {
+            id: NoticeId(80, 1),
+            parent: [3u8; 32],
+            asset: [2u8; 32],
+            amount: 50,
+            account: [1u8; 32],
+        });
+
+        let expected = [
+            &b"DOT:"[..],
+            &[80, 0, 0, 0],                                     // eraId
+            &[1, 0, 0, 0],                                      // eraIndex
+            &[3; 32],                                           // parent
+            &[24, 117, 110, 108, 111, 99, 107],                 // "unlock"
+            &[2; 32],                                           // asset
+            &[50, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], // amount
+            &[1; 32],                                           // account
+        ]
+        .concat();
+        assert_eq!(notice.encode_notice(), expected);
+        assert_eq!(notice.chain_id(), ChainId::Dot);
+        assert_eq!(
+            notice.hash(),
+            ChainHash::Dot(sp_io::hashing::blake2_256(&expected[..]))
+        );
+        assert_eq!(
+            default_notice_signatures(&notice),
+            ChainSignatureList::Dot(vec![])
+        );
+    }
+
+    #[test]
+    fn test_encodes_dot_cash_extraction_notice() {
+        let notice = Notice::CashExtractionNotice(CashExtractionNotice::Dot {
+            id: NoticeId(80, 1),
+            parent: [3u8; 32],
+            account: [1u8; 32],
+            principal: 50,
+        });
+
+        let expected = [
+            &b"DOT:"[..],
+            &[80, 0, 0, 0],                                       // eraId
+            &[1, 0, 0, 0],                                        // eraIndex
+            &[3; 32],                                             // parent
+            &[40, 117, 110, 108, 111, 99, 107, 67, 97, 115, 104], // "unlockCash"
+            &[1; 32],                                             // account
+            &[50, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],   // principal
+        ]
+        .concat();
+        assert_eq!(notice.encode_notice(), expected);
+        assert_eq!(notice.chain_id(), ChainId::Dot);
+    }
+
+    #[test]
+    fn test_encodes_sol_extraction_notice() {
+        let notice = Notice::ExtractionNotice(ExtractionNotice::Sol {
+            id: NoticeId(80, 1),
+            parent: [3u8; 32],
+            asset: [2u8; 32],
+            amount: 50,
+            account: [1u8; 32],
+        });
+
+        let expected = [
+            &b"SOL:"[..],
+            &[80, 0, 0, 0],                                     // eraId
+            &[1, 0, 0, 0],                                      // eraIndex
+            &[3; 32],                                           // parent
+            &[6, 0, 0, 0, 117, 110, 108, 111, 99, 107],         // "unlock"
+            &[2; 32],                                           // asset
+            &[50, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], // amount
+            &[1; 32],                                           // account
+        ]
+        .concat();
+        assert_eq!(notice.encode_notice(), expected);
+        assert_eq!(notice.chain_id(), ChainId::Sol);
+        assert_eq!(
+            notice.hash(),
+            ChainHash::Sol(sp_io::hashing::sha2_256(&expected[..]))
+        );
+        assert_eq!(
+            default_notice_signatures(&notice),
+            ChainSignatureList::Sol(vec![])
+        );
+    }
+
+    #[test]
+    fn test_encodes_sol_set_supply_cap_notice() {
+        let notice = Notice::SetSupplyCapNotice(SetSupplyCapNotice::Sol {
+            id: NoticeId(80, 1),
+            parent: [3u8; 32],
+            asset: [2u8; 32],
+            cap: 0x0102,
+        });
+
+        let expected = [
+            &b"SOL:"[..],
+            &[80, 0, 0, 0], // eraId
+            &[1, 0, 0, 0],  // eraIndex
+            &[3; 32],       // parent
+            &[
+                12, 0, 0, 0, 115, 101, 116, 83, 117, 112, 112, 108, 121, 67, 97, 112,
+            ], // "setSupplyCap"
+            &[2; 32],       // asset
+            &[2, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], // cap
+        ]
+        .concat();
+        assert_eq!(notice.encode_notice(), expected);
+        assert_eq!(notice.chain_id(), ChainId::Sol);
+    }
+
+    #[test]
+    fn test_encodes_dot_change_authority_notice() {
+        let notice = Notice::ChangeAuthorityNotice(ChangeAuthorityNotice::Dot {
+            id: NoticeId(80, 1),
+            parent: [3u8; 32],
+            new_authorities: vec![[1u8; 32], [2u8; 32]],
+        });
+
+        let expected = [
+            &b"DOT:"[..],
+            &[80, 0, 0, 0], // eraId
+            &[1, 0, 0, 0],  // eraIndex
+            &[3; 32],       // parent
+            &[
+                68, 99, 104, 97, 110, 103, 101, 65, 117, 116, 104, 111, 114, 105, 116, 105, 101,
+                115,
+            ], // "changeAuthorities"
+            &[8],           // authorities length
+            &[1; 32],       // authority
+            &[2; 32],       // authority
+        ]
+        .concat();
+        assert_eq!(notice.encode_notice(), expected);
+        assert_eq!(notice.chain_id(), ChainId::Dot);
+        assert_eq!(
+            default_notice_signatures(&notice),
+            ChainSignatureList::Dot(vec![])
+        );
+    }
+
+    #[test]
+    fn test_encodes_sol_future_yield_notice() {
+        let notice = Notice::FutureYieldNotice(FutureYieldNotice::Sol {
+            id: NoticeId(80, 1),
+            parent: [3u8; 32],
+            next_cash_yield: 700,
+            next_cash_index: 0x0102,
+            next_cash_yield_start: 5,
+        });
+
+        let expected = [
+            &b"SOL:"[..],
+            &[80, 0, 0, 0], // eraId
+            &[1, 0, 0, 0],  // eraIndex
+            &[3; 32],       // parent
+            &[
+                14, 0, 0, 0, 115, 101, 116, 70, 117, 116, 117, 114, 101, 89, 105, 101, 108, 100,
+            ], // "setFutureYield"
+            &[188, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], // next cash yield
+            &[2, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], // next cash index
+            &[5, 0, 0, 0, 0, 0, 0, 0], // next cash yield start
+        ]
+        .concat();
+        assert_eq!(notice.encode_notice(), expected);
+        assert_eq!(notice.chain_id(), ChainId::Sol);
+        assert_eq!(
+            default_notice_signatures(&notice),
+            ChainSignatureList::Sol(vec![])
+        );
+    }
+
+    #[test]
+    fn test_has_sol_signer() {
+        let signature_pairs = ChainSignatureList::Sol(vec![([1; 32], [2; 64])]);
+
+        assert!(has_signer(&signature_pairs, ChainAccount::Sol([1; 32])));
+        assert!(!has_signer(&signature_pairs, ChainAccount::Sol([2; 32])));
+        assert!(!has_signer(&signature_pairs, ChainAccount::Dot([1; 32])));
+    }
+
+    #[test]
+    fn test_has_dot_signer() {
+        let signature_pairs = ChainSignatureList::Dot(vec![([1; 32], [2; 64])]);
+
+        assert!(has_signer(&signature_pairs, ChainAccount::Dot([1; 32])));
+        assert!(!has_signer(&signature_pairs, ChainAccount::Dot([2; 32])));
+        assert!(!has_signer(&signature_pairs, ChainAccount::Eth([1; 20])));
+    }
+
+    #[test]
+    fn test_encodes_matic_extraction_notice() {
+        let eth_notice = Notice::ExtractionNotice(ExtractionNotice::Eth {
+            id: NoticeId(80, 1),
+            parent: [3u8; 32],
+            asset: [2u8; 20],
+            amount: 50,
+            account: [1u8; 20],
+        });
+        let matic_notice = Notice::ExtractionNotice(ExtractionNotice::Matic {
+            id: NoticeId(80, 1),
+            parent: [3u8; 32],
+            asset: [2u8; 20],
+            amount: 50,
+            account: [1u8; 20],
+        });
+
+        let expected = [&b"MATIC:"[..], &eth_notice.encode_notice()[4..]].concat();
+        assert_eq!(matic_notice.encode_notice(), expected);
+        assert_eq!(matic_notice.chain_id(), ChainId::Matic);
+        assert_ne!(matic_notice.hash(), eth_notice.hash());
+        assert_eq!(
+            default_notice_signatures(&matic_notice),
+            ChainSignatureList::Matic(vec![])
+        );
+    }
+
+    #[test]
+    fn test_has_matic_signer() {
+        let signature_pairs = ChainSignatureList::Matic(vec![([1; 20], [2; 65])]);
+
+        assert!(has_signer(&signature_pairs, ChainAccount::Matic([1; 20])));
+        assert!(!has_signer(&signature_pairs, ChainAccount::Matic([2; 20])));
+        assert!(!has_signer(&signature_pairs, ChainAccount::Eth([1; 20])));
+    }
+
     #[test]
     fn test_encodes_cash_extraction_notice() -> Result<(), ethabi::Error> {
         let account = [1u8; 20];
@@ -1,9 +1,20 @@
 use crate::symbol::{CASH, USD};
-use crate::types::{Quantity, Timestamp};
+use crate::types::{Bips, Quantity, Timestamp};
 
 /// The number of blocks before an Ethereum transaction is considered final.
 pub const ETH_FINALIZATION_BLOCKS: u32 = 30; // XXX ideally dependent on tx size
 
+/// The most blocks a single `eth_getLogs` call spans when fetching starport events,
+///  unless the validator configures another span for the chain.
+pub const ETH_LOGS_MAX_BLOCK_SPAN: u64 = 10_000;
+
+/// The number of `eth_getLogs` calls sent together in a single batch request,
+///  unless the validator configures another batch size for the chain.
+pub const ETH_LOGS_BATCH_SIZE: usize = 10;
+
+/// The most batches of `eth_getLogs` calls an offchain worker sends each time it fetches events.
+pub const ETH_LOGS_MAX_BATCHES: usize = 5;
+
 /// Number of milliseconds in a year.
 pub const MILLISECONDS_PER_YEAR: Timestamp = 365 * 24 * 60 * 60 * 1000; // todo: xxx finalize this number
 
@@ -14,12 +25,24 @@ pub const MIN_NEXT_SYNC_TIME: Timestamp = 24 * 60 * 60 * 1000; // XXX
 /// Minimum value (USD) required across all protocol interactions.
 pub const MIN_TX_VALUE: Quantity = Quantity::from_nominal("1", USD);
 
+/// Maximum liquidation incentive (bips) which may be set by governance.
+pub const MAX_LIQUIDATION_INCENTIVE: Bips = 5_000;
+
+/// Maximum close factor (bips) which may be set by governance.
+pub const MAX_CLOSE_FACTOR: Bips = 10_000;
+
+/// Maximum spread (bips) which may be set for an asset by governance.
+pub const MAX_SPREAD: Bips = 10_000;
+
 /// Flat transfer fee (CASH).
 pub const TRANSFER_FEE: Quantity = Quantity::from_nominal("0.01", CASH);
 
 /// Number of blocks between HTTP requests from offchain workers to open oracle price feed.
 pub const ORACLE_POLL_INTERVAL_BLOCKS: u32 = 10;
 
+/// Number of blocks between scans for underwater accounts by the liquidation keeper.
+pub const LIQUIDATION_POLL_INTERVAL_BLOCKS: u32 = 10;
+
 // The number of blocks in between periodic sessions
 pub const SESSION_PERIOD: u32 = 14400; // Assuming 6s blocks, ~1 period per day
 
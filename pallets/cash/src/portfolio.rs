@@ -70,9 +70,9 @@ impl Portfolio {
             let price = get_price::<T>(balance.units)?;
             let worth = (*balance).mul_price(price)?;
             if worth.value >= 0 {
-                liquidity = liquidity.add(worth.mul_factor(info.liquidity_factor)?)?
+                liquidity = liquidity.add(worth.mul_factor(info.collateral_factor)?)?
             } else {
-                liquidity = liquidity.add(worth.div_factor(info.liquidity_factor)?)?
+                liquidity = liquidity.add(worth.div_factor(info.borrow_factor)?)?
             }
         }
         Ok(liquidity)
@@ -92,7 +92,8 @@ pub mod tests {
         decimals: u8,
         balance: &'static str,
         price: &'static str,
-        liquidity_factor: &'static str,
+        collateral_factor: &'static str,
+        borrow_factor: &'static str,
     }
 
     struct GetLiquidityTestCase {
@@ -111,20 +112,24 @@ pub mod tests {
             for asset_case in case.test_assets {
                 let asset = ChainAsset::Eth([asset_case.asset; 20]);
                 let decimals = asset_case.decimals;
-                let liquidity_factor = LiquidityFactor::from_nominal(asset_case.liquidity_factor);
+                let collateral_factor = LiquidityFactor::from_nominal(asset_case.collateral_factor);
+                let borrow_factor = LiquidityFactor::from_nominal(asset_case.borrow_factor);
                 let miner_shares: MinerShares = Default::default();
                 let rate_model: InterestRateModel = Default::default();
                 let supply_cap = AssetAmount::MAX;
+                let borrow_cap = 0;
                 let symbol = Symbol::new(&asset_case.ticker);
                 let ticker = Ticker::new(&asset_case.ticker);
 
                 let asset_info = AssetInfo {
                     asset,
                     decimals,
-                    liquidity_factor,
+                    collateral_factor,
+                    borrow_factor,
                     miner_shares,
                     rate_model,
                     supply_cap,
+                    borrow_cap,
                     symbol,
                     ticker,
                 };
@@ -191,7 +196,8 @@ pub mod tests {
                     decimals: 6,
                     balance: "123.123456",
                     price: "987.654321",
-                    liquidity_factor: "0.6789",
+                    collateral_factor: "0.6789",
+                    borrow_factor: "0.6789",
                 }],
                 error_message: "Singe asset supplied liquidity",
             },
@@ -206,7 +212,8 @@ pub mod tests {
                         decimals: 6,
                         balance: "123.123456",
                         price: "987.654321",
-                        liquidity_factor: "0.6789",
+                        collateral_factor: "0.6789",
+                        borrow_factor: "0.6789",
                     },
                     TestAsset {
                         asset: 2,
@@ -214,7 +221,8 @@ pub mod tests {
                         decimals: 6,
                         balance: "-12.123456",
                         price: "987.654321",
-                        liquidity_factor: "0.1450",
+                        collateral_factor: "0.1450",
+                        borrow_factor: "0.1450",
                     },
                 ],
                 error_message: "Slightly undercollateralized account",
@@ -230,7 +238,8 @@ pub mod tests {
                         decimals: 6,
                         balance: "123.123456",
                         price: "987.654321",
-                        liquidity_factor: "0.6789",
+                        collateral_factor: "0.6789",
+                        borrow_factor: "0.6789",
                     },
                     TestAsset {
                         asset: 2,
@@ -238,12 +247,39 @@ pub mod tests {
                         decimals: 6,
                         balance: "-12.123456",
                         price: "987.654321",
-                        liquidity_factor: "0.1450",
+                        collateral_factor: "0.1450",
+                        borrow_factor: "0.1450",
                     },
                 ],
                 error_message:
                     "Slightly undercollateralized by assets but with some offsetting positive cash",
             },
+            GetLiquidityTestCase {
+                cash_index: "1",
+                cash_principal: None,
+                expected_liquidity: Ok("20"),
+                test_assets: vec![
+                    TestAsset {
+                        asset: 1,
+                        ticker: "abc",
+                        decimals: 6,
+                        balance: "100",
+                        price: "1",
+                        collateral_factor: "0.8",
+                        borrow_factor: "0.5",
+                    },
+                    TestAsset {
+                        asset: 2,
+                        ticker: "def",
+                        decimals: 6,
+                        balance: "-30",
+                        price: "1",
+                        collateral_factor: "0.9",
+                        borrow_factor: "0.5",
+                    },
+                ],
+                error_message: "Supplies use the collateral factor and borrows the borrow factor",
+            },
         ]
     }
 
@@ -1,6 +1,8 @@
 /// Interest rate related calculations and utilities are concentrated here
 use codec::{Decode, Encode};
-use our_std::{consts::uint_from_string_with_decimals, Deserialize, RuntimeDebug, Serialize};
+use our_std::{
+    consts::uint_from_string_with_decimals, vec::Vec, Deserialize, RuntimeDebug, Serialize,
+};
 
 use crate::{
     factor::Factor,
@@ -21,6 +23,14 @@ pub enum RatesError {
     KinkAboveFull,
     KinkUtilizationTooHigh,
     Overflowed,
+    TooFewPoints,
+    TooManyPoints,
+    FirstPointNotZero,
+    UtilizationNotIncreasing,
+    RateDecreasing,
+    MinAboveMax,
+    TargetUtilizationOutOfBounds,
+    LastPointNotFull,
 }
 
 /// Annualized interest rate
@@ -102,6 +112,61 @@ pub fn get_utilization(supplied: AssetAmount, borrowed: AssetAmount) -> Result<F
     }
 }
 
+/// The maximum number of points in a multi-kink interest rate model.
+pub const MAX_RATE_POINTS: usize = 8;
+
+/// A point on the utilization curve of a multi-kink interest rate model.
+#[derive(Serialize, Deserialize)] // used in config
+#[derive(
+    Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, Types,
+)]
+pub struct RatePoint {
+    pub utilization: Factor,
+    pub rate: APR,
+}
+
+/// A bounded list of rate points, stored inline so that rate models remain `Copy`.
+///
+/// Encodes exactly like a `Vec<RatePoint>`.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug)]
+pub struct RatePoints {
+    len: u8,
+    points: [RatePoint; MAX_RATE_POINTS],
+}
+
+impl RatePoints {
+    pub fn new(points: &[RatePoint]) -> Result<Self, RatesError> {
+        if points.len() > MAX_RATE_POINTS {
+            return Err(RatesError::TooManyPoints);
+        }
+        let mut inline = [RatePoint::default(); MAX_RATE_POINTS];
+        inline[..points.len()].copy_from_slice(points);
+        Ok(RatePoints {
+            len: points.len() as u8,
+            points: inline,
+        })
+    }
+
+    pub fn as_slice(&self) -> &[RatePoint] {
+        &self.points[..self.len as usize]
+    }
+}
+
+impl Encode for RatePoints {
+    fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+        self.as_slice().using_encoded(f)
+    }
+}
+
+impl codec::EncodeLike for RatePoints {}
+
+impl Decode for RatePoints {
+    fn decode<I: codec::Input>(encoded: &mut I) -> Result<Self, codec::Error> {
+        let points: Vec<RatePoint> = Decode::decode(encoded)?;
+        RatePoints::new(&points).map_err(|_| "too many rate points".into())
+    }
+}
+
 /// This represents an interest rate model type and parameters.
 #[derive(Serialize, Deserialize)] // used in config
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, Types)]
@@ -112,6 +177,15 @@ pub enum InterestRateModel {
         kink_utilization: Factor,
         full_rate: APR,
     },
+    MultiKink {
+        points: RatePoints,
+    },
+    Adaptive {
+        min_rate: APR,
+        max_rate: APR,
+        target_utilization: Factor,
+        adjustment_speed: APR,
+    },
 }
 
 /// This is for convenience, we shouldn't rely on a sane default model.
@@ -143,9 +217,29 @@ impl InterestRateModel {
         }
     }
 
+    /// Create a new multi-kink model from (utilization, rate) points.
+    pub fn new_multi_kink<T, U>(points: &[(T, U)]) -> Result<InterestRateModel, RatesError>
+    where
+        T: Into<Factor> + Copy,
+        U: Into<APR> + Copy,
+    {
+        let points: Vec<RatePoint> = points
+            .iter()
+            .map(|(utilization, rate)| RatePoint {
+                utilization: (*utilization).into(),
+                rate: (*rate).into(),
+            })
+            .collect();
+        Ok(InterestRateModel::MultiKink {
+            points: RatePoints::new(&points)?,
+        })
+    }
+
     /// Check the model parameters for sanity
     ///
     /// Kink - monotonically increasing rate with a kink somewhere between 0% and 100% utilization
+    /// MultiKink - non-decreasing rate through points of increasing utilization, from 0% to 100%
+    /// Adaptive - a rate range and a target utilization somewhere between 0% and 100%
     pub fn check_parameters(self: &Self) -> Result<(), RatesError> {
         match self {
             Self::Kink {
@@ -170,6 +264,55 @@ impl InterestRateModel {
                     return Err(RatesError::KinkUtilizationTooHigh);
                 }
             }
+
+            Self::MultiKink { points } => {
+                let points = points.as_slice();
+                if points.len() < 2 {
+                    return Err(RatesError::TooFewPoints);
+                }
+
+                if points[0].utilization != Factor::ZERO {
+                    return Err(RatesError::FirstPointNotZero);
+                }
+
+                if points[points.len() - 1].utilization != Factor::ONE {
+                    return Err(RatesError::LastPointNotFull);
+                }
+
+                for point in points {
+                    if point.rate > APR::MAX {
+                        return Err(RatesError::ModelRateOutOfBounds);
+                    }
+                }
+
+                for pair in points.windows(2) {
+                    if pair[0].utilization >= pair[1].utilization {
+                        return Err(RatesError::UtilizationNotIncreasing);
+                    }
+                    if pair[0].rate > pair[1].rate {
+                        return Err(RatesError::RateDecreasing);
+                    }
+                }
+            }
+
+            Self::Adaptive {
+                min_rate,
+                max_rate,
+                target_utilization,
+                adjustment_speed: _,
+            } => {
+                if *max_rate > APR::MAX {
+                    return Err(RatesError::ModelRateOutOfBounds);
+                }
+
+                if min_rate >= max_rate {
+                    return Err(RatesError::MinAboveMax);
+                }
+
+                if *target_utilization == Factor::ZERO || *target_utilization >= Factor::ONE {
+                    return Err(RatesError::TargetUtilizationOutOfBounds);
+                }
+            }
         };
 
         Ok(())
@@ -206,12 +349,65 @@ impl InterestRateModel {
             .checked_add(kink_rate)
     }
 
+    /// The segment of a multi-kink model between two points.
+    fn segment_line(utilization: Uint, left: RatePoint, right: RatePoint) -> Option<Uint> {
+        // (utilization - left_utilization) * (right_rate - left_rate) / (right_utilization - left_utilization) + left_rate
+        utilization
+            .checked_sub(left.utilization.0)?
+            .checked_mul(right.rate.0.checked_sub(left.rate.0)?)?
+            .checked_div(right.utilization.0.checked_sub(left.utilization.0)?)?
+            .checked_add(left.rate.0)
+    }
+
+    /// Move the current rate of an adaptive model towards the target utilization over the elapsed time.
+    ///
+    /// Returns the new rate along with the remainder, the fraction of a unit it moved beyond the rate,
+    ///  scaled by one year in milliseconds times `Factor::ONE`, so short adjustments add up over time.
+    fn adapt_rate(
+        utilization: Uint,
+        current_rate: Uint,
+        remainder: Uint,
+        dt: Timestamp,
+        min_rate: Uint,
+        max_rate: Uint,
+        target_utilization: Uint,
+        adjustment_speed: Uint,
+    ) -> Option<(Uint, Uint)> {
+        let scale = Factor::ONE.0.checked_mul(MILLISECONDS_PER_YEAR as Uint)?;
+        // adjustment_speed * |utilization - target_utilization| * dt / 1 year, times the scale
+        let deviation = if utilization >= target_utilization {
+            utilization - target_utilization
+        } else {
+            target_utilization - utilization
+        };
+        let delta = adjustment_speed
+            .checked_mul(deviation)?
+            .checked_mul(dt as Uint)?;
+        let current_rate = current_rate.max(min_rate).min(max_rate);
+        let scaled_rate = current_rate
+            .checked_mul(scale)?
+            .checked_add(remainder.min(scale - 1))?;
+        let scaled_rate = if utilization >= target_utilization {
+            scaled_rate.saturating_add(delta)
+        } else {
+            scaled_rate.saturating_sub(delta)
+        };
+        match scaled_rate / scale {
+            rate if rate < min_rate => Some((min_rate, 0)),
+            rate if rate >= max_rate => Some((max_rate, 0)),
+            rate => Some((rate, scaled_rate % scale)),
+        }
+    }
+
     /// Get the borrow rate
-    /// Current rate is not used at the moment
+    ///
+    /// The current rate and the time elapsed since it was set (dt) are only used by adaptive
+    /// models, for which a zero dt just returns the current rate within the bounds of the model.
     pub fn get_borrow_rate<T: Into<APR>>(
         self: &Self,
         utilization: Factor,
-        _current_rate: T,
+        current_rate: T,
+        dt: Timestamp,
     ) -> Result<APR, RatesError> {
         match self {
             Self::Kink {
@@ -244,9 +440,86 @@ impl InterestRateModel {
                     return Ok(result.into());
                 }
             }
+
+            Self::MultiKink { points } => {
+                let points = points.as_slice();
+                if points.len() < 2 {
+                    return Err(RatesError::TooFewPoints);
+                }
+
+                // utilization may exceed 100% when reserves are negative, the rate stops at the last point
+                let utilization = utilization.min(Factor::ONE);
+
+                // find the last segment starting at or below the utilization
+                let mut index = 0;
+                while index + 2 < points.len() && utilization >= points[index + 1].utilization {
+                    index += 1;
+                }
+
+                let result = Self::segment_line(utilization.0, points[index], points[index + 1])
+                    .ok_or(RatesError::Overflowed)?;
+
+                return Ok(result.into());
+            }
+
+            Self::Adaptive {
+                min_rate,
+                max_rate,
+                target_utilization,
+                adjustment_speed,
+            } => {
+                let (result, _) = Self::adapt_rate(
+                    utilization.0,
+                    current_rate.into().0,
+                    0,
+                    dt,
+                    min_rate.0,
+                    max_rate.0,
+                    target_utilization.0,
+                    adjustment_speed.0,
+                )
+                .ok_or(RatesError::Overflowed)?;
+
+                return Ok(result.into());
+            }
         };
     }
 
+    /// Get the borrow rate like `get_borrow_rate`, carrying the remainder of an adaptive model's
+    ///  last adjustment into this one, and returning the remainder of this adjustment.
+    ///
+    /// Other models don't adjust over time, so have no remainder.
+    pub fn get_adapted_borrow_rate(
+        self: &Self,
+        utilization: Factor,
+        current_rate: APR,
+        remainder: Uint,
+        dt: Timestamp,
+    ) -> Result<(APR, Uint), RatesError> {
+        match self {
+            Self::Adaptive {
+                min_rate,
+                max_rate,
+                target_utilization,
+                adjustment_speed,
+            } => {
+                let (rate, remainder) = Self::adapt_rate(
+                    utilization.0,
+                    current_rate.0,
+                    remainder,
+                    dt,
+                    min_rate.0,
+                    max_rate.0,
+                    target_utilization.0,
+                    adjustment_speed.0,
+                )
+                .ok_or(RatesError::Overflowed)?;
+                Ok((rate.into(), remainder))
+            }
+            _ => Ok((self.get_borrow_rate(utilization, current_rate, dt)?, 0)),
+        }
+    }
+
     fn borrow_rate_to_supply_rate(
         borrow_rate: Uint,
         miner_shares: Uint,
@@ -288,7 +561,7 @@ impl InterestRateModel {
         current_rate: APR,
         miner_shares: MinerShares,
     ) -> Result<(APR, APR), RatesError> {
-        let borrow_rate = self.get_borrow_rate(utilization, current_rate)?;
+        let borrow_rate = self.get_borrow_rate(utilization, current_rate, 0)?;
         // unsafe version Borrow Rate * (1-miner shares) * utilization
         let supply_rate =
             Self::borrow_rate_to_supply_rate(borrow_rate.0, miner_shares.0, utilization.0)
@@ -481,6 +754,110 @@ mod test {
                 expected: Err(RatesError::ModelRateOutOfBounds),
                 message: "rate must be less than max rate",
             },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::new_multi_kink(&[
+                    (Factor::ZERO, 100),
+                    (Factor::from_nominal("0.5"), 200),
+                    (Factor::from_nominal("0.9"), 200),
+                    (Factor::ONE, 500),
+                ])
+                .unwrap(),
+                expected: Ok(()),
+                message: "typical multi-kink case should work well",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::new_multi_kink(&[(Factor::ZERO, 100)]).unwrap(),
+                expected: Err(RatesError::TooFewPoints),
+                message: "multi-kink needs at least two points",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::new_multi_kink(&[
+                    (Factor::from_nominal("0.1"), 100),
+                    (Factor::ONE, 500),
+                ])
+                .unwrap(),
+                expected: Err(RatesError::FirstPointNotZero),
+                message: "multi-kink must start at zero utilization",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::new_multi_kink(&[
+                    (Factor::ZERO, 100),
+                    (Factor::from_nominal("0.5"), 200),
+                    (Factor::from_nominal("0.5"), 300),
+                    (Factor::ONE, 500),
+                ])
+                .unwrap(),
+                expected: Err(RatesError::UtilizationNotIncreasing),
+                message: "multi-kink utilization must be increasing",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::new_multi_kink(&[
+                    (Factor::ZERO, 200),
+                    (Factor::from_nominal("0.5"), 100),
+                    (Factor::ONE, 500),
+                ])
+                .unwrap(),
+                expected: Err(RatesError::RateDecreasing),
+                message: "multi-kink rates must not decrease",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::new_multi_kink(&[
+                    (Factor::ZERO, 100),
+                    (Factor::from_nominal("0.9"), 500),
+                ])
+                .unwrap(),
+                expected: Err(RatesError::LastPointNotFull),
+                message: "multi-kink must end at full utilization",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::new_multi_kink(&[
+                    (Factor::ZERO, 100),
+                    (Factor::ONE, APR::MAX.0 + 1),
+                ])
+                .unwrap(),
+                expected: Err(RatesError::ModelRateOutOfBounds),
+                message: "multi-kink rates must be less than max rate",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::Adaptive {
+                    min_rate: 100.into(),
+                    max_rate: 2000.into(),
+                    target_utilization: Factor::from_nominal("0.8"),
+                    adjustment_speed: 1000.into(),
+                },
+                expected: Ok(()),
+                message: "typical adaptive case should work well",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::Adaptive {
+                    min_rate: 100.into(),
+                    max_rate: 100.into(),
+                    target_utilization: Factor::from_nominal("0.8"),
+                    adjustment_speed: 1000.into(),
+                },
+                expected: Err(RatesError::MinAboveMax),
+                message: "adaptive min rate must be below max rate",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::Adaptive {
+                    min_rate: 100.into(),
+                    max_rate: APR(APR::MAX.0 + 1),
+                    target_utilization: Factor::from_nominal("0.8"),
+                    adjustment_speed: 1000.into(),
+                },
+                expected: Err(RatesError::ModelRateOutOfBounds),
+                message: "adaptive max rate must be less than max rate",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::Adaptive {
+                    min_rate: 100.into(),
+                    max_rate: 2000.into(),
+                    target_utilization: Factor::ONE,
+                    adjustment_speed: 1000.into(),
+                },
+                expected: Err(RatesError::TargetUtilizationOutOfBounds),
+                message: "adaptive target utilization must be less than 100%",
+            },
         ]
     }
 
@@ -558,13 +935,96 @@ mod test {
                 expected: Ok(380.into()),
                 message: "rate at point between kink and full",
             },
+            InterestRateModelGetBorrowRateTestCase {
+                model: InterestRateModel::new_multi_kink(&[
+                    (Factor::ZERO, 100),
+                    (Factor::from_nominal("0.5"), 200),
+                    (Factor::from_nominal("0.9"), 200),
+                    (Factor::ONE, 500),
+                ])
+                .unwrap(),
+                utilization: Factor::from_nominal("0.25"),
+                expected: Ok(150.into()),
+                message: "multi-kink rate on the first segment",
+            },
+            InterestRateModelGetBorrowRateTestCase {
+                model: InterestRateModel::new_multi_kink(&[
+                    (Factor::ZERO, 100),
+                    (Factor::from_nominal("0.5"), 200),
+                    (Factor::from_nominal("0.9"), 200),
+                    (Factor::ONE, 500),
+                ])
+                .unwrap(),
+                utilization: Factor::from_nominal("0.7"),
+                expected: Ok(200.into()),
+                message: "multi-kink rate on a flat segment",
+            },
+            InterestRateModelGetBorrowRateTestCase {
+                model: InterestRateModel::new_multi_kink(&[
+                    (Factor::ZERO, 100),
+                    (Factor::from_nominal("0.5"), 200),
+                    (Factor::from_nominal("0.9"), 200),
+                    (Factor::ONE, 500),
+                ])
+                .unwrap(),
+                utilization: Factor::from_nominal("0.95"),
+                expected: Ok(350.into()),
+                message: "multi-kink rate on the last segment",
+            },
+            InterestRateModelGetBorrowRateTestCase {
+                model: InterestRateModel::new_multi_kink(&[
+                    (Factor::ZERO, 100),
+                    (Factor::from_nominal("0.5"), 200),
+                    (Factor::from_nominal("0.9"), 200),
+                    (Factor::ONE, 500),
+                ])
+                .unwrap(),
+                utilization: Factor::from_nominal("0.9"),
+                expected: Ok(200.into()),
+                message: "multi-kink rate at a kink is the rate of that point",
+            },
+            InterestRateModelGetBorrowRateTestCase {
+                model: InterestRateModel::new_multi_kink(&[
+                    (Factor::ZERO, 100),
+                    (Factor::from_nominal("0.5"), 200),
+                    (Factor::from_nominal("0.9"), 200),
+                    (Factor::ONE, 500),
+                ])
+                .unwrap(),
+                utilization: Factor::ONE,
+                expected: Ok(500.into()),
+                message: "multi-kink rate at full utilization is the rate of the last point",
+            },
+            InterestRateModelGetBorrowRateTestCase {
+                model: InterestRateModel::new_multi_kink(&[
+                    (Factor::ZERO, 100),
+                    (Factor::from_nominal("0.5"), 200),
+                    (Factor::from_nominal("0.9"), 200),
+                    (Factor::ONE, 500),
+                ])
+                .unwrap(),
+                utilization: Factor::from_nominal("1.1"),
+                expected: Ok(500.into()),
+                message: "multi-kink rate stops at the last point past full utilization",
+            },
+            InterestRateModelGetBorrowRateTestCase {
+                model: InterestRateModel::Adaptive {
+                    min_rate: 100.into(),
+                    max_rate: 2000.into(),
+                    target_utilization: Factor::from_nominal("0.8"),
+                    adjustment_speed: 1000.into(),
+                },
+                utilization: Factor::from_nominal("0.9"),
+                expected: Ok(100.into()),
+                message: "adaptive rate starts at its minimum without elapsed time",
+            },
         ]
     }
 
     fn test_get_borrow_rate_case(case: InterestRateModelGetBorrowRateTestCase) {
         assert_eq!(
             case.expected,
-            case.model.get_borrow_rate(case.utilization, 0),
+            case.model.get_borrow_rate(case.utilization, 0, 0),
             "{}",
             case.message
         )
@@ -577,6 +1037,126 @@ mod test {
             .for_each(test_get_borrow_rate_case)
     }
 
+    #[test]
+    fn test_adaptive_borrow_rate() {
+        let model = InterestRateModel::Adaptive {
+            min_rate: 100.into(),
+            max_rate: 2000.into(),
+            target_utilization: Factor::from_nominal("0.8"),
+            adjustment_speed: APR::from_nominal("1"),
+        };
+        let half_year = MILLISECONDS_PER_YEAR / 2;
+
+        // 10% over target for half a year moves the rate up 5% at full speed
+        assert_eq!(
+            model.get_borrow_rate(Factor::from_nominal("0.9"), 1000, half_year),
+            Ok(1500.into())
+        );
+        // 20% under target for half a year moves the rate down 10%, but no further than the minimum
+        assert_eq!(
+            model.get_borrow_rate(Factor::from_nominal("0.6"), 1000, half_year),
+            Ok(100.into())
+        );
+        // the rate stays put at the target
+        assert_eq!(
+            model.get_borrow_rate(Factor::from_nominal("0.8"), 1000, half_year),
+            Ok(1000.into())
+        );
+        // and never exceeds the maximum
+        assert_eq!(
+            model.get_borrow_rate(Factor::from_nominal("1"), 1000, MILLISECONDS_PER_YEAR * 10),
+            Ok(2000.into())
+        );
+    }
+
+    #[test]
+    fn test_adapted_borrow_rate_carries_remainder() {
+        let model = InterestRateModel::Adaptive {
+            min_rate: 100.into(),
+            max_rate: 5000.into(),
+            target_utilization: Factor::from_nominal("0.8"),
+            adjustment_speed: APR::from_nominal("1"),
+        };
+        let utilization = Factor::from_nominal("0.9");
+        let third_year = MILLISECONDS_PER_YEAR / 3;
+
+        // a third of a year moves the rate up a third of 10%, in three steps as in one
+        let (rate, remainder) = model
+            .get_adapted_borrow_rate(utilization, 1000.into(), 0, third_year)
+            .unwrap();
+        assert_eq!(rate, 1333.into());
+        assert!(remainder > 0);
+        let (rate, remainder) = model
+            .get_adapted_borrow_rate(utilization, rate, remainder, third_year)
+            .unwrap();
+        assert_eq!(rate, 1666.into());
+        assert_eq!(
+            model.get_adapted_borrow_rate(utilization, rate, remainder, third_year),
+            Ok((2000.into(), 0))
+        );
+
+        // a rate too small to see in a single block still adds up
+        let (mut rate, mut remainder) = (APR::from(1000), 0);
+        for _ in 0..10 {
+            let adapted = model
+                .get_adapted_borrow_rate(utilization, rate, remainder, 6_000)
+                .unwrap();
+            rate = adapted.0;
+            remainder = adapted.1;
+        }
+        assert_eq!(rate, 1000.into());
+        assert!(remainder > 0);
+
+        // other models have no remainder
+        assert_eq!(
+            InterestRateModel::default().get_adapted_borrow_rate(utilization, 0.into(), 5, 1),
+            InterestRateModel::default()
+                .get_borrow_rate(utilization, 0, 1)
+                .map(|rate| (rate, 0))
+        );
+    }
+
+    #[test]
+    fn test_rate_points_encoding() {
+        let points = vec![
+            RatePoint {
+                utilization: Factor::ZERO,
+                rate: 100.into(),
+            },
+            RatePoint {
+                utilization: Factor::ONE,
+                rate: 500.into(),
+            },
+        ];
+        let model = InterestRateModel::MultiKink {
+            points: RatePoints::new(&points).unwrap(),
+        };
+
+        // encodes like a vec
+        assert_eq!(RatePoints::new(&points).unwrap().encode(), points.encode());
+        assert_eq!(
+            InterestRateModel::decode(&mut &model.encode()[..]).unwrap(),
+            model
+        );
+        assert_eq!(
+            RatePoints::new(&[RatePoint::default(); MAX_RATE_POINTS + 1]),
+            Err(RatesError::TooManyPoints)
+        );
+        let too_many = vec![RatePoint::default(); MAX_RATE_POINTS + 1];
+        assert!(RatePoints::decode(&mut &too_many.encode()[..]).is_err());
+
+        // and serializes like one too
+        let json = serde_json::to_string(&model).unwrap();
+        assert_eq!(
+            json,
+            "{\"MultiKink\":{\"points\":[{\"utilization\":0,\"rate\":100},{\"utilization\":1000000000000000000,\"rate\":500}]}}"
+        );
+        assert_eq!(
+            serde_json::from_str::<InterestRateModel>(&json).unwrap(),
+            model
+        );
+    }
+
     #[test]
     fn test_compound() {
         let mut rates = vec!["0", "0.0001", "0.03", "0.1", "0.2"];
@@ -62,6 +62,17 @@ pub enum Reason {
     InvalidChain,
     PendingAuthNotice,
     ChangeValidatorsError,
+    InvalidQuorum,
+    InvalidLiquidationIncentive,
+    InvalidSpread,
+    InvalidCloseFactor,
+    CloseFactorExceeded,
+    LiquidateTooMuch,
+    BorrowCapExceeded,
+    InvalidLiquidityFactor,
+    AmbiguousSymbol,
+    InexactAmount,
+    UndecodableEvent,
 }
 
 impl From<Reason> for frame_support::dispatch::DispatchError {
@@ -119,6 +130,17 @@ impl From<Reason> for frame_support::dispatch::DispatchError {
             Reason::InvalidChain => (29, 0, "invalid chain"),
             Reason::PendingAuthNotice => (30, 0, "change auth notice is already pending"),
             Reason::ChangeValidatorsError => (31, 0, "change validators error"),
+            Reason::InvalidQuorum => (32, 0, "invalid quorum"),
+            Reason::InvalidLiquidationIncentive => (33, 0, "invalid liquidation incentive"),
+            Reason::InvalidSpread => (34, 0, "invalid spread"),
+            Reason::InvalidCloseFactor => (35, 0, "invalid close factor"),
+            Reason::CloseFactorExceeded => (36, 0, "close factor exceeded"),
+            Reason::LiquidateTooMuch => (37, 0, "liquidate too much"),
+            Reason::BorrowCapExceeded => (38, 0, "borrow cap exceeded"),
+            Reason::InvalidLiquidityFactor => (39, 0, "invalid liquidity factor"),
+            Reason::AmbiguousSymbol => (40, 0, "ambiguous symbol"),
+            Reason::InexactAmount => (41, 0, "inexact amount"),
+            Reason::UndecodableEvent => (42, 0, "undecodable event"),
         };
         frame_support::dispatch::DispatchError::Module {
             index,
@@ -202,22 +224,26 @@ pub enum TrxReqParseError {
     InvalidExpression,
     InvalidChain,
     InvalidChainAccount,
+    EmptyBatch,
+    NestedBatch,
 }
 
 impl From<trx_request::ParseError<'_>> for TrxReqParseError {
     fn from(err: trx_request::ParseError) -> Self {
-        match err {
-            trx_request::ParseError::NotImplemented => TrxReqParseError::NotImplemented,
-            trx_request::ParseError::LexError(_) => TrxReqParseError::LexError,
-            trx_request::ParseError::InvalidAmount => TrxReqParseError::InvalidAmount,
-            trx_request::ParseError::InvalidAddress => TrxReqParseError::InvalidAddress,
-            trx_request::ParseError::InvalidArgs(_, _, _) => TrxReqParseError::InvalidArgs,
-            trx_request::ParseError::UnknownFunction(_) => TrxReqParseError::UnknownFunction,
-            trx_request::ParseError::InvalidExpression => TrxReqParseError::InvalidExpression,
-            trx_request::ParseError::InvalidChain(_) => TrxReqParseError::InvalidChain,
-            trx_request::ParseError::InvalidChainAccount(_) => {
+        match err.kind {
+            trx_request::ParseErrorKind::NotImplemented => TrxReqParseError::NotImplemented,
+            trx_request::ParseErrorKind::LexError(_) => TrxReqParseError::LexError,
+            trx_request::ParseErrorKind::InvalidAmount => TrxReqParseError::InvalidAmount,
+            trx_request::ParseErrorKind::InvalidAddress => TrxReqParseError::InvalidAddress,
+            trx_request::ParseErrorKind::InvalidArgs(_, _, _) => TrxReqParseError::InvalidArgs,
+            trx_request::ParseErrorKind::UnknownFunction(_) => TrxReqParseError::UnknownFunction,
+            trx_request::ParseErrorKind::InvalidExpression => TrxReqParseError::InvalidExpression,
+            trx_request::ParseErrorKind::InvalidChain(_) => TrxReqParseError::InvalidChain,
+            trx_request::ParseErrorKind::InvalidChainAccount(_) => {
                 TrxReqParseError::InvalidChainAccount
             }
+            trx_request::ParseErrorKind::EmptyBatch => TrxReqParseError::EmptyBatch,
+            trx_request::ParseErrorKind::NestedBatch => TrxReqParseError::NestedBatch,
         }
     }
 }
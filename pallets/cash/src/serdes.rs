@@ -1,8 +1,9 @@
-use our_std::{convert::TryInto, str::FromStr};
+use our_std::{convert::TryInto, str::FromStr, vec::Vec};
 use serde::{de, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
     chains::{Chain, ChainAccount, ChainAsset, Ethereum},
+    rates::{RatePoint, RatePoints},
     symbol::Symbol,
     types::ValidatorKeys,
 };
@@ -123,6 +124,27 @@ impl Serialize for Symbol {
     }
 }
 
+// RatePoints
+
+impl<'de> de::Deserialize<'de> for RatePoints {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let points: Vec<RatePoint> = Deserialize::deserialize(de)?;
+        RatePoints::new(&points).map_err(|_| de::Error::custom("too many rate points"))
+    }
+}
+
+impl Serialize for RatePoints {
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_slice().serialize(ser)
+    }
+}
+
 // ValidatorKeys & Vec<ValidatorKeys>
 
 impl<'de> de::Deserialize<'de> for ValidatorKeys {
@@ -133,6 +155,8 @@ impl<'de> de::Deserialize<'de> for ValidatorKeys {
         enum Field {
             SubstrateId,
             EthAddress,
+            DotId,
+            SolId,
         }
         impl<'de> Deserialize<'de> for Field {
             fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
@@ -147,7 +171,7 @@ impl<'de> de::Deserialize<'de> for ValidatorKeys {
                         &self,
                         formatter: &mut our_std::fmt::Formatter,
                     ) -> our_std::fmt::Result {
-                        formatter.write_str("`substrate_id` or `eth_address`")
+                        formatter.write_str("`substrate_id`, `eth_address`, `dot_id` or `sol_id`")
                     }
 
                     fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
@@ -157,6 +181,8 @@ impl<'de> de::Deserialize<'de> for ValidatorKeys {
                         match value {
                             "substrate_id" => Ok(Field::SubstrateId),
                             "eth_address" => Ok(Field::EthAddress),
+                            "dot_id" => Ok(Field::DotId),
+                            "sol_id" => Ok(Field::SolId),
                             _ => Err(de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -180,6 +206,8 @@ impl<'de> de::Deserialize<'de> for ValidatorKeys {
             {
                 let mut substrate_id = None;
                 let mut eth_address = None;
+                let mut dot_id = None;
+                let mut sol_id = None;
                 while let Some(key) = map.next_key()? {
                     match key {
                         Field::SubstrateId => {
@@ -202,20 +230,37 @@ impl<'de> de::Deserialize<'de> for ValidatorKeys {
                                     .map_err(|_| de::Error::custom("bad eth address"))?,
                             );
                         }
+                        Field::DotId => {
+                            if dot_id.is_some() {
+                                return Err(de::Error::duplicate_field("dot_id"));
+                            }
+                            dot_id = Some(map.next_value()?);
+                        }
+                        Field::SolId => {
+                            if sol_id.is_some() {
+                                return Err(de::Error::duplicate_field("sol_id"));
+                            }
+                            sol_id = Some(map.next_value()?);
+                        }
                     }
                 }
                 let substrate_id =
                     substrate_id.ok_or_else(|| de::Error::missing_field("substrate_id"))?;
                 let eth_address =
                     eth_address.ok_or_else(|| de::Error::missing_field("eth_address"))?;
+                let dot_id = dot_id.ok_or_else(|| de::Error::missing_field("dot_id"))?;
+                let sol_id = sol_id.ok_or_else(|| de::Error::missing_field("sol_id"))?;
                 Ok(ValidatorKeys {
                     substrate_id,
                     eth_address,
+                    dot_id,
+                    sol_id,
                 })
             }
         }
 
-        const FIELDS: &'static [&'static str] = &["substrate_id", "eth_address"];
+        const FIELDS: &'static [&'static str] =
+            &["substrate_id", "eth_address", "dot_id", "sol_id"];
         de.deserialize_struct("ValidatorKeys", FIELDS, ValidatorKeysVisitor)
     }
 }
@@ -225,11 +270,15 @@ impl Serialize for ValidatorKeys {
     where
         S: Serializer,
     {
-        let mut map = ser.serialize_map(Some(2))?;
+        let mut map = ser.serialize_map(Some(4))?;
         map.serialize_key("substrate_id")?;
         map.serialize_value(&<[u8; 32]>::from(self.substrate_id.clone()))?;
         map.serialize_key("eth_address")?;
         map.serialize_value(&<Ethereum as Chain>::address_string(&self.eth_address))?;
+        map.serialize_key("dot_id")?;
+        map.serialize_value(&self.dot_id)?;
+        map.serialize_key("sol_id")?;
+        map.serialize_value(&self.sol_id)?;
         map.end()
     }
 }
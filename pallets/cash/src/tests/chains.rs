@@ -0,0 +1,235 @@
+use super::*;
+use pallet_oracle::{types::Price, Prices};
+
+const CHAIN_IDS: [ChainId; 6] = [
+    ChainId::Gate,
+    ChainId::Eth,
+    ChainId::Dot,
+    ChainId::Sol,
+    ChainId::Tez,
+    ChainId::Matic,
+];
+
+fn account_on(chain_id: ChainId, byte: u8) -> ChainAccount {
+    match chain_id {
+        ChainId::Gate => ChainAccount::Gate([byte; 32]),
+        ChainId::Eth => ChainAccount::Eth([byte; 20]),
+        ChainId::Dot => ChainAccount::Dot([byte; 32]),
+        ChainId::Sol => ChainAccount::Sol([byte; 32]),
+        ChainId::Tez => ChainAccount::Tez([byte; 21]),
+        ChainId::Matic => ChainAccount::Matic([byte; 20]),
+    }
+}
+
+fn asset_on(chain_id: ChainId, byte: u8) -> ChainAsset {
+    match chain_id {
+        ChainId::Gate => ChainAsset::Gate([byte; 32]),
+        ChainId::Eth => ChainAsset::Eth([byte; 20]),
+        ChainId::Dot => ChainAsset::Dot([byte; 32]),
+        ChainId::Sol => ChainAsset::Sol([byte; 32]),
+        ChainId::Tez => ChainAsset::Tez([byte; 20]),
+        ChainId::Matic => ChainAsset::Matic([byte; 20]),
+    }
+}
+
+fn signature_on(chain_id: ChainId) -> ChainSignature {
+    match chain_id {
+        ChainId::Gate => ChainSignature::Gate([0; 65]),
+        ChainId::Eth => ChainSignature::Eth([0; 65]),
+        ChainId::Dot => ChainSignature::Dot([0; 64]),
+        ChainId::Sol => ChainSignature::Sol([0; 64]),
+        ChainId::Tez => ChainSignature::Tez([0; 65]),
+        ChainId::Matic => ChainSignature::Matic([0; 65]),
+    }
+}
+
+// Whether we can queue notices to be executed by a starport on the chain
+fn accepts_notices(chain_id: ChainId) -> bool {
+    match chain_id {
+        ChainId::Eth | ChainId::Dot | ChainId::Sol | ChainId::Matic => true,
+        ChainId::Gate | ChainId::Tez => false,
+    }
+}
+
+fn expected_notice_result(chain_id: ChainId) -> Result<(), Reason> {
+    if accepts_notices(chain_id) {
+        Ok(())
+    } else {
+        Err(Reason::InvalidChain)
+    }
+}
+
+#[test]
+fn test_lock_on_every_chain() {
+    for &chain_id in CHAIN_IDS.iter() {
+        new_test_ext().execute_with(|| {
+            let asset_info = AssetInfo::minimal(asset_on(chain_id, 1), ETH);
+            let holder = account_on(chain_id, 2);
+            SupportedAssets::insert(&asset_info.asset, asset_info);
+
+            assert_ok!(core::lock_internal::<Test>(
+                asset_info,
+                holder,
+                holder,
+                qty!("1", ETH)
+            ));
+            assert_eq!(
+                AssetBalances::get(&asset_info.asset, &holder),
+                bal!("1", ETH).value
+            );
+        });
+    }
+}
+
+#[test]
+fn test_transfer_cash_to_every_chain() {
+    let sender = ChainAccount::Eth([1; 20]);
+
+    for &chain_id in CHAIN_IDS.iter() {
+        new_test_ext().execute_with(|| {
+            let recipient = account_on(chain_id, 2);
+            CashPrincipals::insert(&sender, CashPrincipal::from_nominal("100"));
+
+            assert_ok!(core::transfer_cash_principal_internal::<Test>(
+                sender,
+                recipient,
+                CashPrincipalAmount::from_nominal("5"),
+                LiquidityCheck::Immediate
+            ));
+            assert_eq!(
+                CashPrincipals::get(&recipient),
+                CashPrincipal::from_nominal("5")
+            );
+        });
+    }
+}
+
+#[test]
+fn test_extract_cash_to_every_chain() {
+    let holder = ChainAccount::Eth([1; 20]);
+
+    for &chain_id in CHAIN_IDS.iter() {
+        new_test_ext().execute_with(|| {
+            let recipient = account_on(chain_id, 2);
+            CashPrincipals::insert(&holder, CashPrincipal::from_nominal("100"));
+
+            assert_eq!(
+                core::extract_cash_principal_internal::<Test>(
+                    holder,
+                    recipient,
+                    CashPrincipalAmount::from_nominal("5"),
+                    LiquidityCheck::Immediate
+                ),
+                expected_notice_result(chain_id)
+            );
+
+            if !accepts_notices(chain_id) {
+                assert_eq!(
+                    CashPrincipals::get(&holder),
+                    CashPrincipal::from_nominal("100")
+                );
+                assert_eq!(ChainCashPrincipals::get(chain_id), CashPrincipalAmount(0));
+                assert_eq!(LatestNotice::get(chain_id), None);
+            }
+        });
+    }
+}
+
+#[test]
+fn test_extract_asset_to_every_chain() {
+    for &chain_id in CHAIN_IDS.iter() {
+        new_test_ext().execute_with(|| {
+            let asset_info = AssetInfo::minimal(asset_on(chain_id, 1), ETH);
+            let holder = account_on(chain_id, 2);
+            Prices::insert(ETH.ticker, Price::from_nominal(ETH.ticker, "2000.19").value);
+            SupportedAssets::insert(&asset_info.asset, asset_info);
+            CashPrincipals::insert(&holder, CashPrincipal::from_nominal("10000"));
+            assert_ok!(core::lock_internal::<Test>(
+                asset_info,
+                holder,
+                holder,
+                qty!("10", ETH)
+            ));
+
+            assert_eq!(
+                core::extract_internal::<Test>(
+                    asset_info,
+                    holder,
+                    holder,
+                    qty!("1", ETH),
+                    LiquidityCheck::Immediate
+                ),
+                expected_notice_result(chain_id)
+            );
+
+            if !accepts_notices(chain_id) {
+                assert_eq!(
+                    AssetBalances::get(&asset_info.asset, &holder),
+                    bal!("10", ETH).value
+                );
+                assert_eq!(
+                    TotalSupplyAssets::get(&asset_info.asset),
+                    qty!("10", ETH).value
+                );
+                assert_eq!(LatestNotice::get(chain_id), None);
+            }
+        });
+    }
+}
+
+#[test]
+fn test_notices_on_every_chain() {
+    for &chain_id in CHAIN_IDS.iter() {
+        new_test_ext().execute_with(|| {
+            let asset_info = AssetInfo::minimal(asset_on(chain_id, 1), ETH);
+            SupportedAssets::insert(&asset_info.asset, asset_info);
+
+            let result = CashModule::set_supply_cap(Origin::root(), asset_info.asset, 1000);
+            let last_event = System::events().into_iter().last().map(|e| e.event);
+
+            if accepts_notices(chain_id) {
+                assert_ok!(result);
+                assert_eq!(
+                    last_event,
+                    Some(mock::Event::pallet_cash(crate::Event::SetSupplyCap(
+                        asset_info.asset,
+                        1000
+                    )))
+                );
+
+                // a bogus signature is rejected rather than recorded
+                let (notice_id, _) = LatestNotice::get(chain_id).unwrap();
+                assert!(internal::notices::publish_signature(
+                    chain_id,
+                    notice_id,
+                    signature_on(chain_id)
+                )
+                .is_err());
+            } else {
+                assert_err!(result, Reason::InvalidChain);
+                assert_eq!(
+                    last_event,
+                    Some(mock::Event::pallet_cash(crate::Event::Failure(
+                        Reason::InvalidChain
+                    )))
+                );
+                assert_eq!(SupportedAssets::get(&asset_info.asset), Some(asset_info));
+                assert_eq!(LatestNotice::get(chain_id), None);
+
+                // there is never a notice to sign on these chains
+                assert_eq!(
+                    internal::notices::publish_signature(
+                        chain_id,
+                        NoticeId(0, 1),
+                        signature_on(chain_id)
+                    ),
+                    Ok(())
+                );
+                assert_eq!(
+                    signature_on(chain_id).recover(b"notice"),
+                    Err(Reason::NotImplemented)
+                );
+            }
+        });
+    }
+}
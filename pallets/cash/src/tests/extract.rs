@@ -1,5 +1,5 @@
 use super::*;
-use pallet_oracle::{types::Price, Prices};
+use pallet_oracle::{error::OracleError, types::Price, PriceBreakers, Prices};
 
 #[test]
 fn test_extract_asset_without_supply() -> Result<(), Reason> {
@@ -13,7 +13,13 @@ fn test_extract_asset_without_supply() -> Result<(), Reason> {
 
         assert_eq!(TotalSupplyAssets::get(&Eth), 0);
         assert_err!(
-            core::extract_internal::<Test>(eth, jared, max, qty!("1", ETH)),
+            core::extract_internal::<Test>(
+                eth,
+                jared,
+                max,
+                qty!("1", ETH),
+                LiquidityCheck::Immediate
+            ),
             Reason::InsufficientTotalFunds
         );
 
@@ -21,6 +27,44 @@ fn test_extract_asset_without_supply() -> Result<(), Reason> {
     })
 }
 
+#[test]
+fn test_extract_asset_past_borrow_cap() -> Result<(), Reason> {
+    let jared = ChainAccount::from_str("Eth:0x18c8F1222083997405F2E482338A4650ac02e1d6")?;
+    let max = ChainAccount::from_str("Eth:0x7f89077b122afaaf6ab50aa12e9cb46bb9a058c4")?;
+    let capped = AssetInfo {
+        borrow_cap: qty!("1", ETH).value,
+        ..eth
+    };
+
+    new_test_ext().execute_with(|| {
+        Prices::insert(ETH.ticker, Price::from_nominal(ETH.ticker, "2000.19").value);
+        SupportedAssets::insert(&Eth, capped);
+        CashPrincipals::insert(&jared, CashPrincipal::from_nominal("10000"));
+        TotalSupplyAssets::insert(&Eth, qty!("10", ETH).value);
+
+        assert_err!(
+            core::extract_internal::<Test>(
+                capped,
+                jared,
+                max,
+                qty!("2", ETH),
+                LiquidityCheck::Immediate
+            ),
+            Reason::BorrowCapExceeded
+        );
+        assert_ok!(core::extract_internal::<Test>(
+            capped,
+            jared,
+            max,
+            qty!("1", ETH),
+            LiquidityCheck::Immediate
+        ));
+        assert_eq!(TotalBorrowAssets::get(&Eth), qty!("1", ETH).value);
+
+        Ok(())
+    })
+}
+
 #[test]
 fn test_extract_internal_min_value() -> Result<(), Reason> {
     let asset = ChainAsset::Eth([238; 20]);
@@ -45,7 +89,13 @@ fn test_extract_internal_min_value() -> Result<(), Reason> {
             AccountNotices::iter().collect();
 
         assert_err!(
-            core::extract_internal::<Test>(asset_info, holder, recipient, quantity),
+            core::extract_internal::<Test>(
+                asset_info,
+                holder,
+                recipient,
+                quantity,
+                LiquidityCheck::Immediate
+            ),
             Reason::MinTxValueNotMet
         );
 
@@ -75,12 +125,62 @@ fn test_extract_internal_min_value() -> Result<(), Reason> {
     })
 }
 
+#[test]
+fn test_extract_internal_blocked_by_price_breaker() -> Result<(), Reason> {
+    let asset = ChainAsset::Eth([238; 20]);
+    let asset_info = AssetInfo {
+        collateral_factor: LiquidityFactor::from_nominal("1"),
+        borrow_factor: LiquidityFactor::from_nominal("1"),
+        ..AssetInfo::minimal(asset, ETH)
+    };
+    let holder = ChainAccount::Eth([0; 20]);
+    let recipient = ChainAccount::Eth([0; 20]);
+
+    new_test_ext().execute_with(|| {
+        SupportedAssets::insert(&asset, asset_info);
+        Prices::insert(asset_info.ticker, 100_000); // $0.10
+        PriceBreakers::insert(asset_info.ticker, OracleError::ExpiredPrice);
+        let quantity = get_quantity::<Test>(asset, 50_000_000_000_000_000_000).unwrap();
+        AssetBalances::insert(asset, holder, (quantity.value * 5) as AssetBalance);
+        AssetsWithNonZeroBalance::insert(holder, asset, ());
+        TotalSupplyAssets::insert(&asset, quantity.value * 5);
+
+        assert_err!(
+            core::extract_internal::<Test>(
+                asset_info,
+                holder,
+                recipient,
+                quantity,
+                LiquidityCheck::Immediate
+            ),
+            Reason::OracleError(OracleError::ExpiredPrice)
+        );
+
+        // repaying (locking) does not depend on the price
+        assert_ok!(core::lock_internal::<Test>(
+            asset_info, holder, holder, quantity
+        ));
+
+        PriceBreakers::remove(asset_info.ticker);
+        assert_ok!(core::extract_internal::<Test>(
+            asset_info,
+            holder,
+            recipient,
+            quantity,
+            LiquidityCheck::Immediate
+        ));
+
+        Ok(())
+    })
+}
+
 #[test]
 fn test_extract_internal_sufficient_value() -> Result<(), Reason> {
     let eth_asset = [238; 20];
     let asset = ChainAsset::Eth(eth_asset);
     let asset_info = AssetInfo {
-        liquidity_factor: LiquidityFactor::from_nominal("1"),
+        collateral_factor: LiquidityFactor::from_nominal("1"),
+        borrow_factor: LiquidityFactor::from_nominal("1"),
         ..AssetInfo::minimal(asset, ETH)
     };
     let eth_holder = [0; 20];
@@ -110,7 +210,11 @@ fn test_extract_internal_sufficient_value() -> Result<(), Reason> {
             AccountNotices::iter().collect();
 
         assert_ok!(core::extract_internal::<Test>(
-            asset_info, holder, recipient, quantity
+            asset_info,
+            holder,
+            recipient,
+            quantity,
+            LiquidityCheck::Immediate
         ));
 
         let asset_balances_post = AssetBalances::get(asset, holder);
@@ -191,7 +295,8 @@ fn test_extract_internal_notice_ids() -> Result<(), Reason> {
     let eth_asset = [238; 20];
     let asset = ChainAsset::Eth(eth_asset);
     let asset_info = AssetInfo {
-        liquidity_factor: LiquidityFactor::from_nominal("1"),
+        collateral_factor: LiquidityFactor::from_nominal("1"),
+        borrow_factor: LiquidityFactor::from_nominal("1"),
         ..AssetInfo::minimal(asset, ETH)
     };
     let eth_holder = [0; 20];
@@ -218,7 +323,11 @@ fn test_extract_internal_notice_ids() -> Result<(), Reason> {
 
         assert_eq!(LatestNotice::get(ChainId::Eth), None);
         assert_ok!(core::extract_internal::<Test>(
-            asset_info, holder, recipient, quantity
+            asset_info,
+            holder,
+            recipient,
+            quantity,
+            LiquidityCheck::Immediate
         ));
 
         let notice_state_post: Vec<(ChainId, NoticeId, NoticeState)> =
@@ -253,7 +362,11 @@ fn test_extract_internal_notice_ids() -> Result<(), Reason> {
             Some((NoticeId(0, 1), expected_notice.hash()))
         );
         assert_ok!(core::extract_internal::<Test>(
-            asset_info, holder, recipient, quantity
+            asset_info,
+            holder,
+            recipient,
+            quantity,
+            LiquidityCheck::Immediate
         ));
 
         let notices_post_2: Vec<(NoticeId, Notice)> = Notices::iter_prefix(ChainId::Eth).collect();
@@ -306,3 +419,203 @@ fn test_extract_internal_notice_ids() -> Result<(), Reason> {
         Ok(())
     })
 }
+
+#[test]
+fn test_extract_cash_with_deferred_liquidity_checks() -> Result<(), Reason> {
+    let jared = ChainAccount::from_str("Eth:0x18c8F1222083997405F2E482338A4650ac02e1d6")?;
+    let max = ChainAccount::from_str("Eth:0x7f89077b122afaaf6ab50aa12e9cb46bb9a058c4")?;
+    let principal = CashPrincipalAmount::from_nominal("5");
+
+    new_test_ext().execute_with(|| {
+        assert_err!(
+            core::extract_cash_principal_internal::<Test>(
+                jared,
+                max,
+                principal,
+                LiquidityCheck::Immediate
+            ),
+            Reason::InsufficientLiquidity
+        );
+
+        assert_ok!(core::extract_cash_principal_internal::<Test>(
+            jared,
+            max,
+            principal,
+            LiquidityCheck::Deferred
+        ));
+        assert_eq!(
+            CashPrincipals::get(&jared),
+            CashPrincipal::from_nominal("-5")
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+fn test_batch_reverts_earlier_operations_when_later_one_fails() -> Result<(), Reason> {
+    let jared = ChainAccount::from_str("Eth:0x18c8F1222083997405F2E482338A4650ac02e1d6")?;
+    let max = ChainAccount::from_str("Eth:0x7f89077b122afaaf6ab50aa12e9cb46bb9a058c4")?;
+    let principal = CashPrincipalAmount::from_nominal("5");
+
+    new_test_ext().execute_with(|| {
+        Prices::insert(ETH.ticker, Price::from_nominal(ETH.ticker, "2000.19").value);
+        SupportedAssets::insert(&Eth, eth);
+        CashPrincipals::insert(&jared, CashPrincipal::from_nominal("10"));
+
+        assert_err!(
+            core::batch_internal::<Test>(jared, |liquidity_check| {
+                core::extract_cash_principal_internal::<Test>(
+                    jared,
+                    max,
+                    principal,
+                    liquidity_check,
+                )?;
+                assert_eq!(
+                    CashPrincipals::get(&jared),
+                    CashPrincipal::from_nominal("5")
+                );
+                core::extract_internal::<Test>(eth, jared, max, qty!("1", ETH), liquidity_check)
+            }),
+            Reason::InsufficientTotalFunds
+        );
+
+        assert_eq!(
+            CashPrincipals::get(&jared),
+            CashPrincipal::from_nominal("10")
+        );
+        assert_eq!(
+            ChainCashPrincipals::get(ChainId::Eth),
+            CashPrincipalAmount(0)
+        );
+        assert_eq!(LatestNotice::get(ChainId::Eth), None);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn test_batch_reverts_operations_when_final_liquidity_check_fails() -> Result<(), Reason> {
+    let jared = ChainAccount::from_str("Eth:0x18c8F1222083997405F2E482338A4650ac02e1d6")?;
+    let max = ChainAccount::from_str("Eth:0x7f89077b122afaaf6ab50aa12e9cb46bb9a058c4")?;
+
+    new_test_ext().execute_with(|| {
+        CashPrincipals::insert(&jared, CashPrincipal::from_nominal("10"));
+
+        assert_err!(
+            core::batch_internal::<Test>(jared, |liquidity_check| {
+                core::extract_cash_principal_internal::<Test>(
+                    jared,
+                    max,
+                    CashPrincipalAmount::from_nominal("8"),
+                    liquidity_check,
+                )?;
+                core::extract_cash_principal_internal::<Test>(
+                    jared,
+                    max,
+                    CashPrincipalAmount::from_nominal("8"),
+                    liquidity_check,
+                )
+            }),
+            Reason::InsufficientLiquidity
+        );
+
+        assert_eq!(
+            CashPrincipals::get(&jared),
+            CashPrincipal::from_nominal("10")
+        );
+        assert_eq!(
+            ChainCashPrincipals::get(ChainId::Eth),
+            CashPrincipalAmount(0)
+        );
+        assert_eq!(LatestNotice::get(ChainId::Eth), None);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn test_extract_cash_to_polkadot() -> Result<(), Reason> {
+    let alice = Polkadot::str_to_address("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5")?;
+    let holder = ChainAccount::from_str("Dot:15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5")?;
+    let principal = CashPrincipalAmount::from_nominal("5");
+
+    new_test_ext().execute_with(|| {
+        CashPrincipals::insert(&holder, CashPrincipal::from_nominal("10"));
+        assert_ok!(core::extract_cash_principal_internal::<Test>(
+            holder,
+            holder,
+            principal,
+            LiquidityCheck::Immediate
+        ));
+
+        let expected_notice_id = NoticeId(0, 1);
+        let expected_notice = Notice::CashExtractionNotice(CashExtractionNotice::Dot {
+            id: expected_notice_id,
+            parent: [0u8; 32],
+            account: alice,
+            principal: principal.0,
+        });
+
+        assert_eq!(
+            CashPrincipals::get(&holder),
+            CashPrincipal::from_nominal("5")
+        );
+        assert_eq!(ChainCashPrincipals::get(ChainId::Dot), principal);
+        assert_eq!(
+            Notices::get(ChainId::Dot, expected_notice_id),
+            Some(expected_notice.clone())
+        );
+        assert_eq!(
+            NoticeStates::get(ChainId::Dot, expected_notice_id),
+            NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Dot(vec![])
+            }
+        );
+        assert_eq!(
+            LatestNotice::get(ChainId::Dot),
+            Some((expected_notice_id, expected_notice.hash()))
+        );
+        assert_eq!(AccountNotices::get(holder), vec![expected_notice_id]);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn test_extract_to_gateway_account() -> Result<(), Reason> {
+    let alice = ChainAccount::from_str("Gate:5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")?;
+    let principal = CashPrincipalAmount::from_nominal("5");
+
+    new_test_ext().execute_with(|| {
+        CashPrincipals::insert(&alice, CashPrincipal::from_nominal("10"));
+        SupportedAssets::insert(&Eth, eth);
+
+        assert_err!(
+            core::extract_cash_principal_internal::<Test>(
+                alice,
+                alice,
+                principal,
+                LiquidityCheck::Immediate
+            ),
+            Reason::InvalidChain
+        );
+        assert_err!(
+            core::extract_internal::<Test>(
+                eth,
+                alice,
+                alice,
+                qty!("1", ETH),
+                LiquidityCheck::Immediate
+            ),
+            Reason::InvalidChain
+        );
+        assert_eq!(
+            CashPrincipals::get(&alice),
+            CashPrincipal::from_nominal("10")
+        );
+        assert_eq!(LatestNotice::get(ChainId::Gate), None);
+
+        Ok(())
+    })
+}
@@ -1,5 +1,6 @@
 use crate::{self as pallet_cash, *};
 use codec::alloc::sync::Arc;
+use frame_support::storage::StorageMap;
 use pallet_oracle;
 use parking_lot::RwLock;
 use sp_core::{
@@ -147,6 +148,7 @@ impl pallet_timestamp::Config for Test {
 impl pallet_oracle::Config for Test {
     type Call = Call;
     type Event = Event;
+    type TimeProvider = Timestamp;
 }
 
 impl frame_system::offchain::SigningTypes for Test {
@@ -214,6 +216,9 @@ pub fn new_test_ext_with_http_calls(
         }
     }
 
-    test_externalities.execute_with(|| System::set_block_number(1));
+    test_externalities.execute_with(|| {
+        System::set_block_number(1);
+        <frame_system::BlockHash<Test>>::insert(0, H256::repeat_byte(1));
+    });
     (test_externalities, pool_state, offchain_state)
 }
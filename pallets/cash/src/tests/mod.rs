@@ -17,6 +17,7 @@ pub use our_std::{iter::FromIterator, str::FromStr};
 pub use mock::*;
 pub mod mock;
 
+pub mod chains;
 pub mod extract;
 pub mod protocol;
 pub mod testdata;
@@ -26,7 +27,8 @@ pub const Eth: ChainAsset = ChainAsset::Eth(hex!("EeeeeEeeeEeEeeEeEeEeeEEEeeeeEe
 pub const eth: AssetInfo = AssetInfo {
     asset: Eth,
     decimals: ETH.decimals,
-    liquidity_factor: LiquidityFactor::from_nominal("0.8"),
+    collateral_factor: LiquidityFactor::from_nominal("0.8"),
+    borrow_factor: LiquidityFactor::from_nominal("0.8"),
     rate_model: InterestRateModel::Kink {
         zero_rate: APR(0),
         kink_rate: APR(200),
@@ -35,6 +37,7 @@ pub const eth: AssetInfo = AssetInfo {
     },
     miner_shares: Factor::from_nominal("0.05"),
     supply_cap: Quantity::from_nominal("1000", ETH).value,
+    borrow_cap: 0,
     symbol: Symbol(ETH.ticker.0),
     ticker: Ticker(ETH.ticker.0),
 };
@@ -44,7 +47,8 @@ pub const Uni: ChainAsset = ChainAsset::Eth(hex!("1f9840a85d5af5bf1d1762f925bdad
 pub const uni: AssetInfo = AssetInfo {
     asset: Uni,
     decimals: UNI.decimals,
-    liquidity_factor: LiquidityFactor::from_nominal("0.7"),
+    collateral_factor: LiquidityFactor::from_nominal("0.7"),
+    borrow_factor: LiquidityFactor::from_nominal("0.7"),
     rate_model: InterestRateModel::Kink {
         zero_rate: APR(0),
         kink_rate: APR(500),
@@ -53,6 +57,7 @@ pub const uni: AssetInfo = AssetInfo {
     },
     miner_shares: Factor::from_nominal("0.05"),
     supply_cap: Quantity::from_nominal("1000", UNI).value,
+    borrow_cap: 0,
     symbol: Symbol(UNI.ticker.0),
     ticker: Ticker(UNI.ticker.0),
 };
@@ -74,7 +79,8 @@ macro_rules! qty {
 pub fn initialize_storage() {
     CashModule::initialize_assets(vec![
         AssetInfo {
-            liquidity_factor: FromStr::from_str("7890").unwrap(),
+            collateral_factor: FromStr::from_str("7890").unwrap(),
+            borrow_factor: FromStr::from_str("7890").unwrap(),
             ..AssetInfo::minimal(
                 FromStr::from_str("eth:0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE").unwrap(),
                 FromStr::from_str("ETH/18").unwrap(),
@@ -82,7 +88,8 @@ pub fn initialize_storage() {
         },
         AssetInfo {
             ticker: FromStr::from_str("USD").unwrap(),
-            liquidity_factor: FromStr::from_str("7890").unwrap(),
+            collateral_factor: FromStr::from_str("7890").unwrap(),
+            borrow_factor: FromStr::from_str("7890").unwrap(),
             ..AssetInfo::minimal(
                 FromStr::from_str("eth:0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap(),
                 FromStr::from_str("USDC/6").unwrap(),
@@ -105,6 +112,8 @@ pub fn initialize_storage() {
                 "0x6a72a2f14577D9Cd0167801EFDd54a07B40d2b61",
             )
             .unwrap(), // pk: 50f05592dc31bfc65a77c4cc80f2764ba8f9a7cce29c94a51fe2d70cb5599374
+            dot_id: [0; 32],
+            sol_id: [0; 32],
         },
         ValidatorKeys {
             substrate_id: AccountId32::from_str("5FfBQ3kwXrbdyoqLPvcXRp7ikWydXawpNs2Ceu3WwFdhZ8W4")
@@ -113,6 +122,8 @@ pub fn initialize_storage() {
                 "0x8ad1b2918c34ee5d3e881a57c68574ea9dbecb81",
             )
             .unwrap(),
+            dot_id: [0; 32],
+            sol_id: [0; 32],
         },
     ]);
 }
@@ -398,3 +409,121 @@ fn offchain_worker_test() {
         }
     });
 }
+
+#[test]
+fn test_is_notice_ready_uses_signature_quorum() {
+    new_test_ext().execute_with(|| {
+        let chain_id = ChainId::Eth;
+        let notice_id = NoticeId(5, 6);
+        for i in 1..=3u8 {
+            let substrate_id = AccountId32::new([i; 32]);
+            Validators::insert(
+                substrate_id.clone(),
+                ValidatorKeys {
+                    substrate_id,
+                    eth_address: [i; 20],
+                    dot_id: [0; 32],
+                    sol_id: [0; 32],
+                },
+            );
+        }
+
+        assert_eq!(
+            CashModule::is_notice_ready(chain_id, notice_id),
+            Err(Reason::NoticeMissing(chain_id, notice_id))
+        );
+
+        NoticeStates::insert(
+            chain_id,
+            notice_id,
+            NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Eth(vec![
+                    ([1; 20], [0; 65]),
+                    ([2; 20], [0; 65]),
+                ]),
+            },
+        );
+        assert_eq!(CashModule::is_notice_ready(chain_id, notice_id), Ok(true));
+
+        assert_ok!(CashModule::set_signature_quorum(
+            Origin::root(),
+            Quorum::UNANIMOUS
+        ));
+        assert_eq!(CashModule::is_notice_ready(chain_id, notice_id), Ok(false));
+    });
+}
+
+#[test]
+fn test_is_notice_ready_checks_dot_and_sol_keys() {
+    new_test_ext().execute_with(|| {
+        for i in 1..=3u8 {
+            let substrate_id = AccountId32::new([i; 32]);
+            Validators::insert(
+                substrate_id.clone(),
+                ValidatorKeys {
+                    substrate_id,
+                    eth_address: [i; 20],
+                    dot_id: [i + 10; 32],
+                    sol_id: [i + 20; 32],
+                },
+            );
+        }
+
+        let dot_notice_id = NoticeId(5, 6);
+        NoticeStates::insert(
+            ChainId::Dot,
+            dot_notice_id,
+            NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Dot(vec![
+                    ([1; 32], [0; 64]),
+                    ([2; 32], [0; 64]),
+                ]),
+            },
+        );
+        assert_eq!(
+            CashModule::is_notice_ready(ChainId::Dot, dot_notice_id),
+            Ok(false)
+        );
+        NoticeStates::insert(
+            ChainId::Dot,
+            dot_notice_id,
+            NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Dot(vec![
+                    ([11; 32], [0; 64]),
+                    ([12; 32], [0; 64]),
+                ]),
+            },
+        );
+        assert_eq!(
+            CashModule::is_notice_ready(ChainId::Dot, dot_notice_id),
+            Ok(true)
+        );
+
+        let sol_notice_id = NoticeId(7, 8);
+        NoticeStates::insert(
+            ChainId::Sol,
+            sol_notice_id,
+            NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Sol(vec![([21; 32], [0; 64])]),
+            },
+        );
+        assert_eq!(
+            CashModule::is_notice_ready(ChainId::Sol, sol_notice_id),
+            Ok(false)
+        );
+        NoticeStates::insert(
+            ChainId::Sol,
+            sol_notice_id,
+            NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Sol(vec![
+                    ([21; 32], [0; 64]),
+                    ([23; 32], [0; 64]),
+                ]),
+            },
+        );
+        assert_eq!(
+            CashModule::is_notice_ready(ChainId::Sol, sol_notice_id),
+            Ok(true)
+        );
+    });
+}
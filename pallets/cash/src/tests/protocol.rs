@@ -20,14 +20,21 @@ fn upload_transfer_download() -> Result<(), Reason> {
         // Transfer
 
         assert_err!(
-            core::transfer_internal::<Test>(uni, jared, geoff, lock_amount),
+            core::transfer_internal::<Test>(
+                uni,
+                jared,
+                geoff,
+                lock_amount,
+                LiquidityCheck::Immediate
+            ),
             Reason::InsufficientLiquidity // transfer fee
         );
         assert_ok!(core::transfer_internal::<Test>(
             uni,
             jared,
             geoff,
-            qty!("998", UNI)
+            qty!("998", UNI),
+            LiquidityCheck::Immediate
         ));
         assert_eq!(
             CashPrincipals::get(&jared),
@@ -43,7 +50,8 @@ fn upload_transfer_download() -> Result<(), Reason> {
             uni,
             geoff,
             jared,
-            qty!("998", UNI)
+            qty!("998", UNI),
+            LiquidityCheck::Immediate
         ));
         assert_eq!(
             CashPrincipals::get(&jared),
@@ -54,7 +62,13 @@ fn upload_transfer_download() -> Result<(), Reason> {
         assert_eq!(AssetBalances::get(&Uni, &geoff), 0);
 
         assert_err!(
-            core::extract_internal::<Test>(uni, jared, geoff, qty!("2", UNI)),
+            core::extract_internal::<Test>(
+                uni,
+                jared,
+                geoff,
+                qty!("2", UNI),
+                LiquidityCheck::Immediate
+            ),
             Reason::InsufficientLiquidity
         );
 
@@ -62,7 +76,8 @@ fn upload_transfer_download() -> Result<(), Reason> {
             uni,
             jared,
             jared,
-            qty!("1.9", UNI)
+            qty!("1.9", UNI),
+            LiquidityCheck::Immediate
         ));
         assert_eq!(
             CashPrincipals::get(&jared),
@@ -73,10 +88,74 @@ fn upload_transfer_download() -> Result<(), Reason> {
         assert_eq!(AssetBalances::get(&Uni, &geoff), 0);
 
         assert_err!(
-            core::extract_internal::<Test>(uni, jared, jared, qty!("1", UNI)),
+            core::extract_internal::<Test>(
+                uni,
+                jared,
+                jared,
+                qty!("1", UNI),
+                LiquidityCheck::Immediate
+            ),
             Reason::MinTxValueNotMet
         );
 
         Ok(())
     })
 }
+
+#[test]
+fn upload_transfer_to_gateway_download() -> Result<(), Reason> {
+    let jared = ChainAccount::from_str("Eth:0x18c8F1222083997405F2E482338A4650ac02e1d6")?;
+    let alice = ChainAccount::from_str("Gate:5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")?;
+    let lock_amount = qty!("1000", UNI);
+    new_test_ext().execute_with(|| {
+        Prices::insert(UNI.ticker, Price::from_nominal(UNI.ticker, "0.99").value);
+        SupportedAssets::insert(&Uni, uni);
+
+        // Upload
+
+        assert_ok!(core::lock_internal::<Test>(uni, jared, jared, lock_amount));
+
+        // Transfer to a gateway account, which holds the collateral directly
+
+        assert_ok!(core::transfer_internal::<Test>(
+            uni,
+            jared,
+            alice,
+            qty!("998", UNI),
+            LiquidityCheck::Immediate
+        ));
+        assert_eq!(AssetBalances::get(&Uni, &jared), bal!("2", UNI).value);
+        assert_eq!(AssetBalances::get(&Uni, &alice), bal!("998", UNI).value);
+
+        // Borrow and send cash between gateway and ethereum accounts
+
+        assert_ok!(core::transfer_cash_principal_internal::<Test>(
+            alice,
+            jared,
+            CashPrincipalAmount::from_nominal("100"),
+            LiquidityCheck::Immediate
+        ));
+        assert_eq!(
+            CashPrincipals::get(&alice),
+            CashPrincipal::from_nominal("-100.01")
+        );
+        assert_eq!(
+            CashPrincipals::get(&jared),
+            CashPrincipal::from_nominal("99.99")
+        );
+
+        // Download from the gateway account to ethereum
+
+        assert_ok!(core::extract_internal::<Test>(
+            uni,
+            alice,
+            jared,
+            qty!("500", UNI),
+            LiquidityCheck::Immediate
+        ));
+        assert_eq!(AssetBalances::get(&Uni, &alice), bal!("498", UNI).value);
+        assert_eq!(AccountNotices::get(jared).len(), 1);
+
+        Ok(())
+    })
+}
@@ -65,4 +65,24 @@ pub mod json_responses {
         "id": 1,
         "result": "0xb27467"
     }"#;
+
+    pub const BLOCK_BY_NUMBER_RESPONSE: &[u8] = br#"{
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": {
+            "number": "0xb27449",
+            "hash": "0x5d4b4e3bb2d5b0c0ef1e1a2a3a0bdbd6db8d1f5a3a3c2d6e1d7a8c0c9e3f2b1a",
+            "parentHash": "0x0f6a1d7b9e0c3a5c2f8e4d1b7a9c6e3f0d2b4a8c1e5f7a9b3d6c0e2f4a8b1c5d"
+        }
+    }"#;
+
+    pub const REORGED_BLOCK_BY_NUMBER_RESPONSE: &[u8] = br#"{
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": {
+            "number": "0xb27449",
+            "hash": "0x8e2c1a6f4b3d9e0a7c5f2b8d1e4a6c3f9b0d7e2a5c8f1b4d6e9a3c0f2b5d8e1c",
+            "parentHash": "0x0f6a1d7b9e0c3a5c2f8e4d1b7a9c6e3f0d2b4a8c1e5f7a9b3d6c0e2f4a8b1c5d"
+        }
+    }"#;
 }
@@ -1,32 +1,114 @@
+use codec::alloc::string::String;
+use frame_support::storage::{IterableStorageMap, StorageMap};
+use our_std::vec::Vec;
 use trx_request;
 
 use crate::{
     chains::{ChainAccount, ChainAsset},
-    types::CashOrChainAsset,
+    reason::{MathError, Reason},
+    require,
+    types::{AssetAmount, CashOrChainAsset, Decimals},
+    Config, SupportedAssets, Tickers,
 };
 
 impl From<trx_request::Account> for ChainAccount {
     fn from(account: trx_request::Account) -> Self {
         match account {
+            trx_request::Account::Gate(gate_address) => ChainAccount::Gate(gate_address),
             trx_request::Account::Eth(eth_address) => ChainAccount::Eth(eth_address),
+            trx_request::Account::Dot(dot_address) => ChainAccount::Dot(dot_address),
+            trx_request::Account::Sol(sol_address) => ChainAccount::Sol(sol_address),
+            trx_request::Account::Tez(tez_address) => ChainAccount::Tez(tez_address),
+            trx_request::Account::Matic(matic_address) => ChainAccount::Matic(matic_address),
         }
     }
 }
 
-impl From<trx_request::Asset> for CashOrChainAsset {
-    fn from(account: trx_request::Asset) -> Self {
-        match account {
-            trx_request::Asset::Cash => CashOrChainAsset::Cash,
-            trx_request::Asset::Eth(eth_address) => {
-                CashOrChainAsset::ChainAsset(ChainAsset::Eth(eth_address))
+/// Resolve an asset from a trx request, looking up symbols by asset symbol and then by ticker.
+pub fn resolve_asset<T: Config>(asset: trx_request::Asset) -> Result<CashOrChainAsset, Reason> {
+    match asset {
+        trx_request::Asset::Cash => Ok(CashOrChainAsset::Cash),
+        trx_request::Asset::Gate(gate_address) => {
+            Ok(CashOrChainAsset::ChainAsset(ChainAsset::Gate(gate_address)))
+        }
+        trx_request::Asset::Eth(eth_address) => {
+            Ok(CashOrChainAsset::ChainAsset(ChainAsset::Eth(eth_address)))
+        }
+        trx_request::Asset::Dot(dot_address) => {
+            Ok(CashOrChainAsset::ChainAsset(ChainAsset::Dot(dot_address)))
+        }
+        trx_request::Asset::Sol(sol_address) => {
+            Ok(CashOrChainAsset::ChainAsset(ChainAsset::Sol(sol_address)))
+        }
+        trx_request::Asset::Tez(tez_address) => {
+            Ok(CashOrChainAsset::ChainAsset(ChainAsset::Tez(tez_address)))
+        }
+        trx_request::Asset::Matic(matic_address) => Ok(CashOrChainAsset::ChainAsset(
+            ChainAsset::Matic(matic_address),
+        )),
+        trx_request::Asset::Symbol(symbol) => {
+            let mut matches = SupportedAssets::iter_values()
+                .filter(|info| String::from(info.symbol) == symbol)
+                .map(|info| info.asset)
+                .collect::<Vec<ChainAsset>>();
+            if matches.is_empty() {
+                if let Some(ticker) = Tickers::get(&symbol) {
+                    matches = SupportedAssets::iter_values()
+                        .filter(|info| info.ticker == ticker)
+                        .map(|info| info.asset)
+                        .collect();
+                }
+            }
+
+            match &matches[..] {
+                [] => Err(Reason::BadSymbol),
+                [asset] => Ok(CashOrChainAsset::ChainAsset(*asset)),
+                _ => Err(Reason::AmbiguousSymbol),
             }
         }
     }
 }
 
+/// Resolve an amount from a trx request into base units of an asset with the given decimals.
+///
+/// Bare integer amounts are already in base units and are passed through unchanged,
+/// only decimal amounts like `1.5` are scaled by the decimals of the asset.
+/// Returns `None` for the max amount, and fails if a decimal amount is not exactly representable.
+pub fn resolve_amount(
+    max_amount: trx_request::MaxAmount,
+    decimals: Decimals,
+) -> Result<Option<AssetAmount>, Reason> {
+    match max_amount {
+        trx_request::MaxAmount::Max => Ok(None),
+        trx_request::MaxAmount::Amount(amount) => Ok(Some(amount)),
+        trx_request::MaxAmount::Decimal(decimal) if decimal.decimals > decimals => {
+            let excess = 10u128
+                .checked_pow((decimal.decimals - decimals).into())
+                .ok_or(Reason::InexactAmount)?;
+            require!(decimal.digits % excess == 0, Reason::InexactAmount);
+            Ok(Some(decimal.digits / excess))
+        }
+        trx_request::MaxAmount::Decimal(decimal) => {
+            let scale = 10u128
+                .checked_pow((decimals - decimal.decimals).into())
+                .ok_or(MathError::Overflow)?;
+            Ok(Some(
+                decimal
+                    .digits
+                    .checked_mul(scale)
+                    .ok_or(MathError::Overflow)?,
+            ))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        tests::{eth, new_test_ext, uni, Eth, Test, Uni},
+        types::AssetInfo,
+    };
     use trx_request;
 
     const ALAN: [u8; 20] = [1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1];
@@ -41,18 +123,104 @@ mod tests {
             ChainAccount::from(trx_request::Account::Eth(ALAN)),
             ChainAccount::Eth(ALAN)
         );
+        assert_eq!(
+            ChainAccount::from(trx_request::Account::Gate([2; 32])),
+            ChainAccount::Gate([2; 32])
+        );
+        assert_eq!(
+            ChainAccount::from(trx_request::Account::Dot([3; 32])),
+            ChainAccount::Dot([3; 32])
+        );
+        assert_eq!(
+            ChainAccount::from(trx_request::Account::Sol([4; 32])),
+            ChainAccount::Sol([4; 32])
+        );
+        assert_eq!(
+            ChainAccount::from(trx_request::Account::Tez([2; 21])),
+            ChainAccount::Tez([2; 21])
+        );
+        assert_eq!(
+            ChainAccount::from(trx_request::Account::Matic([5; 20])),
+            ChainAccount::Matic([5; 20])
+        );
     }
 
     #[test]
-    fn test_asset_to_chain_asset() {
+    fn test_resolve_asset() {
+        new_test_ext().execute_with(|| {
+            SupportedAssets::insert(&Eth, eth);
+            SupportedAssets::insert(&Uni, uni);
+
+            assert_eq!(
+                resolve_asset::<Test>(trx_request::Asset::Eth(ETH)),
+                Ok(CashOrChainAsset::ChainAsset(ChainAsset::Eth(ETH)))
+            );
+            assert_eq!(
+                resolve_asset::<Test>(trx_request::Asset::Cash),
+                Ok(CashOrChainAsset::Cash)
+            );
+            assert_eq!(
+                resolve_asset::<Test>(trx_request::Asset::Dot([3; 32])),
+                Ok(CashOrChainAsset::ChainAsset(ChainAsset::Dot([3; 32])))
+            );
+            assert_eq!(
+                resolve_asset::<Test>(trx_request::Asset::Tez(ETH)),
+                Ok(CashOrChainAsset::ChainAsset(ChainAsset::Tez(ETH)))
+            );
+            assert_eq!(
+                resolve_asset::<Test>(trx_request::Asset::Symbol(String::from("UNI"))),
+                Ok(CashOrChainAsset::ChainAsset(Uni))
+            );
+            assert_eq!(
+                resolve_asset::<Test>(trx_request::Asset::Symbol(String::from("WETH"))),
+                Err(Reason::BadSymbol)
+            );
+
+            Tickers::insert(String::from("WETH"), crate::tests::ETH.ticker);
+            assert_eq!(
+                resolve_asset::<Test>(trx_request::Asset::Symbol(String::from("WETH"))),
+                Ok(CashOrChainAsset::ChainAsset(Eth))
+            );
+
+            let other = ChainAsset::Eth([1; 20]);
+            SupportedAssets::insert(
+                &other,
+                AssetInfo {
+                    asset: other,
+                    ..eth
+                },
+            );
+            assert_eq!(
+                resolve_asset::<Test>(trx_request::Asset::Symbol(String::from("ETH"))),
+                Err(Reason::AmbiguousSymbol)
+            );
+        });
+    }
+
+    #[test]
+    fn test_resolve_amount() {
+        let decimal = |digits, decimals| {
+            trx_request::MaxAmount::Decimal(trx_request::Decimal { digits, decimals })
+        };
+
+        assert_eq!(resolve_amount(trx_request::MaxAmount::Max, 18), Ok(None));
         assert_eq!(
-            CashOrChainAsset::from(trx_request::Asset::Eth(ETH)),
-            CashOrChainAsset::ChainAsset(ChainAsset::Eth(ETH))
+            resolve_amount(trx_request::MaxAmount::Amount(3), 18),
+            Ok(Some(3))
         );
-
         assert_eq!(
-            CashOrChainAsset::from(trx_request::Asset::Cash),
-            CashOrChainAsset::Cash
+            resolve_amount(decimal(15, 1), 18),
+            Ok(Some(1_500_000_000_000_000_000))
+        );
+        assert_eq!(resolve_amount(decimal(150, 2), 1), Ok(Some(15)));
+        assert_eq!(
+            resolve_amount(decimal(151, 2), 1),
+            Err(Reason::InexactAmount)
+        );
+        assert_eq!(resolve_amount(decimal(1, 6), 0), Err(Reason::InexactAmount));
+        assert_eq!(
+            resolve_amount(decimal(u128::MAX, 0), 18),
+            Err(Reason::MathError(MathError::Overflow))
         );
     }
 }
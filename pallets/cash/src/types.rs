@@ -13,7 +13,7 @@ use pallet_oracle::{ticker::Ticker, types::Price};
 use types_derive::{type_alias, Types};
 
 pub use crate::{
-    chains::{Chain, ChainAsset, ChainId, Ethereum},
+    chains::{Chain, ChainAccount, ChainAsset, ChainId, Ethereum, Polkadot, Solana},
     factor::{BigInt, BigUint, Factor},
     notices::{Notice, NoticeId},
     rates::{InterestRateModel, APR},
@@ -117,6 +117,71 @@ pub type SignersSet = BTreeSet<ValidatorIdentity>;
 pub struct ValidatorKeys {
     pub substrate_id: SubstrateId,
     pub eth_address: <Ethereum as Chain>::Address,
+    pub dot_id: <Polkadot as Chain>::Address,
+    pub sol_id: <Solana as Chain>::Address,
+}
+
+impl ValidatorKeys {
+    /// The account the validator signs notices with on the given chain, if any.
+    pub fn chain_account(&self, chain_id: ChainId) -> Option<ChainAccount> {
+        match chain_id {
+            ChainId::Eth => Some(ChainAccount::Eth(self.eth_address)),
+            ChainId::Matic => Some(ChainAccount::Matic(self.eth_address)),
+            ChainId::Dot => Some(ChainAccount::Dot(self.dot_id)),
+            ChainId::Sol => Some(ChainAccount::Sol(self.sol_id)),
+            _ => None,
+        }
+    }
+}
+
+/// Type for the fraction of validators which must sign off on something before it is accepted.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct Quorum {
+    pub numerator: u32,
+    pub denominator: u32,
+}
+
+impl Quorum {
+    pub const SUPERMAJORITY: Quorum = Quorum::new(2, 3);
+    pub const UNANIMOUS: Quorum = Quorum::new(1, 1);
+
+    pub const fn new(numerator: u32, denominator: u32) -> Self {
+        Quorum {
+            numerator,
+            denominator,
+        }
+    }
+
+    /// Check the quorum is a strict majority, a fraction in (1/2, 1].
+    pub fn check(self) -> Result<(), Reason> {
+        let numerator = u64::from(self.numerator);
+        let denominator = u64::from(self.denominator);
+        if numerator > denominator || numerator * 2 <= denominator {
+            Err(Reason::InvalidQuorum)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Get the number of signers required out of the given number of validators.
+    /// Using ceil(validator_count * numerator / denominator), or every validator if that overflows.
+    pub fn required(self, validator_count: usize) -> usize {
+        let count = validator_count as u64;
+        let numerator = u64::from(self.numerator);
+        let denominator = u64::from(self.denominator);
+        count
+            .checked_mul(numerator)
+            .and_then(|product| product.checked_add(denominator.checked_sub(1)?))
+            .and_then(|product| product.checked_div(denominator))
+            .map(|required| required.min(count) as usize)
+            .unwrap_or(validator_count)
+    }
+}
+
+impl Default for Quorum {
+    fn default() -> Self {
+        Quorum::SUPERMAJORITY
+    }
 }
 
 /// Type for referring to either an asset or CASH.
@@ -132,10 +197,15 @@ pub enum CashOrChainAsset {
 pub struct AssetInfo {
     pub asset: ChainAsset,
     pub decimals: Decimals,
-    pub liquidity_factor: LiquidityFactor,
+    /// The fraction of the value of a supply position which counts towards liquidity.
+    pub collateral_factor: LiquidityFactor,
+    /// The fraction of the value of a borrow position which is covered by its liquidity requirement.
+    pub borrow_factor: LiquidityFactor,
     pub rate_model: InterestRateModel,
     pub miner_shares: MinerShares,
     pub supply_cap: AssetAmount,
+    /// The maximum total borrows of the asset, or zero for no limit.
+    pub borrow_cap: AssetAmount,
     pub symbol: Symbol,
     pub ticker: Ticker,
 }
@@ -145,10 +215,12 @@ impl AssetInfo {
         AssetInfo {
             asset,
             decimals: units.decimals,
-            liquidity_factor: LiquidityFactor::default(),
+            collateral_factor: LiquidityFactor::default(),
+            borrow_factor: LiquidityFactor::default(),
             rate_model: InterestRateModel::default(),
             miner_shares: MinerShares::default(),
             supply_cap: AssetAmount::default(),
+            borrow_cap: AssetAmount::default(),
             symbol: Symbol(units.ticker.0),
             ticker: units.ticker,
         }
@@ -964,6 +1036,33 @@ mod tests {
         assert_eq!(x, -12345);
     }
 
+    #[test]
+    fn test_quorum_required() {
+        assert_eq!(Quorum::SUPERMAJORITY.required(0), 0);
+        assert_eq!(Quorum::SUPERMAJORITY.required(1), 1);
+        assert_eq!(Quorum::SUPERMAJORITY.required(3), 2);
+        assert_eq!(Quorum::SUPERMAJORITY.required(4), 3);
+        assert_eq!(Quorum::UNANIMOUS.required(4), 4);
+        assert_eq!(Quorum::new(1, 2).required(4), 2);
+        assert_eq!(
+            Quorum::new(u32::MAX, u32::MAX).required(usize::MAX),
+            usize::MAX
+        );
+        assert_eq!(Quorum::new(1, 0).required(4), 4);
+    }
+
+    #[test]
+    fn test_quorum_check() {
+        assert_eq!(Quorum::SUPERMAJORITY.check(), Ok(()));
+        assert_eq!(Quorum::UNANIMOUS.check(), Ok(()));
+        assert_eq!(Quorum::new(0, 3).check(), Err(Reason::InvalidQuorum));
+        assert_eq!(Quorum::new(1, 0).check(), Err(Reason::InvalidQuorum));
+        assert_eq!(Quorum::new(4, 3).check(), Err(Reason::InvalidQuorum));
+        assert_eq!(Quorum::new(1, 2).check(), Err(Reason::InvalidQuorum));
+        assert_eq!(Quorum::new(1, 100).check(), Err(Reason::InvalidQuorum));
+        assert_eq!(Quorum::new(51, 100).check(), Ok(()));
+    }
+
     #[test]
     fn test_cash_principal_since() {
         let old_index = AssetIndex::from_nominal("1.0");
@@ -23,6 +23,10 @@ pub enum OracleError {
     NoPriceFeedURL,
     StalePrice,
     SubmitError,
+    ExpiredPrice,
+    ImplausiblePrice,
+    FuturePrice,
+    InvalidMinReporters,
 }
 
 impl From<CryptoError> for OracleError {
@@ -49,6 +53,10 @@ impl From<OracleError> for frame_support::dispatch::DispatchError {
             OracleError::NoPriceFeedURL => (12, 0, "NoPriceFeedURL"),
             OracleError::StalePrice => (13, 0, "StalePrice"),
             OracleError::SubmitError => (14, 0, "SubmitError"),
+            OracleError::ExpiredPrice => (15, 0, "ExpiredPrice"),
+            OracleError::ImplausiblePrice => (16, 0, "ImplausiblePrice"),
+            OracleError::FuturePrice => (17, 0, "FuturePrice"),
+            OracleError::InvalidMinReporters => (18, 0, "InvalidMinReporters"),
         };
         frame_support::dispatch::DispatchError::Module {
             index,
@@ -103,10 +103,13 @@ impl<T: Config> ProvideInherent for Module<T> {
 
         match inherent_data_res {
             Ok(InherentPriceData(pairs, _ts)) => {
-                if pairs
-                    .iter()
-                    .all(|(payload, _)| oracle::get_and_check_parsed_price::<T>(&payload).is_ok())
-                {
+                if pairs.iter().all(|(payload, signature)| {
+                    oracle::recover_reporter::<T>(&payload, &signature)
+                        .and_then(|reporter| {
+                            oracle::get_and_check_parsed_price::<T>(&payload, reporter)
+                        })
+                        .is_ok()
+                }) {
                     Some(Call::post_prices(pairs))
                 } else {
                     // Our data is stale, let's not post it
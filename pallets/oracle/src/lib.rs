@@ -3,16 +3,16 @@
 use crate::{
     error::OracleError,
     ticker::{Ticker, CASH_TICKER, USD_TICKER},
-    types::{AssetPrice, Price, ReporterSet, Timestamp},
+    types::{AssetPrice, Price, PriceLimit, Reporter, ReporterSet, Timestamp},
 };
 use codec::alloc::string::String;
 use frame_support::{
     decl_event, decl_module, decl_storage, dispatch,
-    traits::UnfilteredDispatchable,
+    traits::{UnfilteredDispatchable, UnixTime},
     weights::{DispatchClass, GetDispatchInfo, Pays},
     Parameter,
 };
-use frame_system::{ensure_none, offchain::CreateSignedTransaction};
+use frame_system::{ensure_none, ensure_root, offchain::CreateSignedTransaction};
 use our_std::log;
 use sp_runtime::transaction_validity::{
     InvalidTransaction, TransactionSource, TransactionValidity,
@@ -32,6 +32,12 @@ mod tests;
 /// Number of blocks between HTTP requests from offchain workers to open oracle price feed.
 pub const ORACLE_POLL_INTERVAL_BLOCKS: u32 = 10;
 
+/// Maximum age (milliseconds) of a report, relative to the chain time, to count towards the median.
+pub const MAX_REPORT_AGE: Timestamp = 60 * 60 * 1000;
+
+/// Maximum time (milliseconds) a report may be timestamped ahead of the chain time.
+pub const MAX_REPORT_CLOCK_SKEW: Timestamp = 60 * 1000;
+
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Config: frame_system::Config + CreateSignedTransaction<Call<Self>> {
     /// Because this pallet emits events, it depends on the runtime's definition of an event.
@@ -42,6 +48,9 @@ pub trait Config: frame_system::Config + CreateSignedTransaction<Call<Self>> {
         + Parameter
         + UnfilteredDispatchable<Origin = Self::Origin>
         + GetDispatchInfo;
+
+    /// The source of the chain time, which the freshness of reports is measured against.
+    type TimeProvider: UnixTime;
 }
 
 decl_storage! {
@@ -52,8 +61,24 @@ decl_storage! {
         /// Mapping of assets to the last time their price was updated.
         pub PriceTimes get(fn price_time): map hasher(blake2_128_concat) Ticker => Option<Timestamp>;
 
+        /// Mapping of the latest price and time reported for each price ticker, by reporter.
+        pub ReporterPrices get(fn reporter_price): double_map hasher(blake2_128_concat) Ticker, hasher(blake2_128_concat) Reporter => Option<(AssetPrice, Timestamp)>;
+
         /// Ethereum addresses of open oracle price reporters.
-        pub PriceReporters get(fn reporters): ReporterSet; // XXX if > 1, how are we combining?
+        pub PriceReporters get(fn reporters): ReporterSet;
+
+        /// The minimum number of fresh reports required before a price is accepted (at least 1).
+        pub MinReporters get(fn min_reporters) config(): u32 = 1;
+
+        /// Mapping of price tickers to the limits beyond which their price is unhealthy.
+        pub PriceLimits get(fn price_limit): map hasher(blake2_128_concat) Ticker => Option<PriceLimit>;
+
+        /// Mapping of price tickers to the reason their circuit breaker is tripped, if it is.
+        pub PriceBreakers get(fn price_breaker): map hasher(blake2_128_concat) Ticker => Option<OracleError>;
+
+        /// Mapping of price tickers to a price which jumped implausibly far from the last healthy price,
+        ///  the number of consecutive blocks whose prices have agreed on it so far, and when it was last confirmed.
+        pub PendingPrices get(fn pending_price): map hasher(blake2_128_concat) Ticker => Option<(AssetPrice, u32, Timestamp)>;
     }
     add_extra_genesis {
         config(reporters): ReporterSet;
@@ -69,6 +94,18 @@ decl_event!(
     pub enum Event {
         /// Failed to process a given extrinsic. [reason]
         Failure(OracleError),
+
+        /// The limits on a ticker's price were changed. [ticker, limit]
+        SetPriceLimit(Ticker, Option<PriceLimit>),
+
+        /// The price for a ticker became unhealthy. [ticker, reason]
+        BreakerTripped(Ticker, OracleError),
+
+        /// The price for a ticker became healthy again. [ticker]
+        BreakerReset(Ticker),
+
+        /// The minimum number of fresh reports for a price was changed. [min_reporters]
+        SetMinReporters(u32),
     }
 );
 
@@ -112,6 +149,20 @@ decl_module! {
             })?)
         }
 
+        /// Set or clear the limits on a ticker's price. [Root]
+        #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
+        pub fn set_price_limit(origin, ticker: Ticker, limit: Option<PriceLimit>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(oracle::set_price_limit::<T>(ticker, limit))?)
+        }
+
+        /// Set the minimum number of fresh reports required before a price is accepted. [Root]
+        #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
+        pub fn set_min_reporters(origin, min_reporters: u32) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(oracle::set_min_reporters::<T>(min_reporters))?)
+        }
+
         /// Offchain Worker entry point.
         fn offchain_worker(block_number: T::BlockNumber) {
             if let Err(e) = oracle::process_prices::<T>(block_number) {
@@ -122,7 +173,16 @@ decl_module! {
 }
 
 /// Return the USD price associated with the given units.
+/// Fails if the circuit breaker for the ticker is tripped.
 pub fn get_price_by_ticker<T: Config>(ticker: Ticker) -> Result<Price, OracleError> {
+    if let Some(reason) = PriceBreakers::get(ticker) {
+        return Err(reason);
+    }
+    get_last_price_by_ticker::<T>(ticker)
+}
+
+/// Return the last USD price associated with the given units, healthy or not.
+pub fn get_last_price_by_ticker<T: Config>(ticker: Ticker) -> Result<Price, OracleError> {
     match ticker {
         t if t == USD_TICKER => Ok(Price::from_nominal(USD_TICKER, "1.0")),
         t if t == CASH_TICKER => Ok(Price::from_nominal(CASH_TICKER, "1.0")),
@@ -1,19 +1,26 @@
-use frame_support::storage::{StorageMap, StorageValue};
+use frame_support::{
+    storage::{
+        IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
+    },
+    traits::UnixTime,
+};
 use serde::Deserialize;
 use sp_runtime::offchain::{
-    http,
     storage::StorageValueRef,
     storage_lock::{StorageLock, Time},
-    Duration,
 };
 
 use crate::{
     error::OracleError,
     ticker::Ticker,
-    types::{AssetPrice, Timestamp},
+    types::{AssetPrice, PriceLimit, Reporter, Timestamp},
+};
+use crate::{
+    Config, Event, MinReporters, Module, PendingPrices, PriceBreakers, PriceLimits, PriceReporters,
+    PriceTimes, Prices, ReporterPrices, MAX_REPORT_AGE, MAX_REPORT_CLOCK_SKEW,
+    ORACLE_POLL_INTERVAL_BLOCKS,
 };
-use crate::{Config, PriceReporters, PriceTimes, Prices, ORACLE_POLL_INTERVAL_BLOCKS};
-use our_std::{collections::btree_map::BTreeMap, str::FromStr, vec::Vec, RuntimeDebug};
+use our_std::{collections::btree_map::BTreeMap, log, str::FromStr, vec::Vec, RuntimeDebug};
 
 /// A single decoded message from the price oracle
 #[derive(PartialEq, Eq, RuntimeDebug)]
@@ -121,31 +128,28 @@ fn parse_open_price_feed_api_response(
     serde_json::from_slice(response).map_err(|_| OracleError::JsonParseError)
 }
 
-/// Make the open price feed API request to an unauthenticated http endpoint
-pub fn open_price_feed_request(url: &str) -> Result<OpenPriceFeedApiResponse, OracleError> {
-    let response = open_price_feed_request_unchecked(url)?;
+/// Make the open price feed API request to an http endpoint, with the validator's HTTP options for it
+pub fn open_price_feed_request(
+    url: &str,
+    http_config: &runtime_interfaces::HttpConfig,
+) -> Result<OpenPriceFeedApiResponse, OracleError> {
+    let response = open_price_feed_request_unchecked(url, http_config)?;
 
     Ok(response)
 }
 
-/// Make the open price feed HTTP API request to an unauthenticated endpoint using HTTP GET.
-fn open_price_feed_request_unchecked(url: &str) -> Result<OpenPriceFeedApiResponse, OracleError> {
-    let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(2_000));
-    let request = http::Request::get(url);
-    let pending = request
-        .deadline(deadline)
-        .send()
-        .map_err(|_| OracleError::HttpError)?;
-    let response = pending
-        .try_wait(deadline)
-        .map_err(|_| OracleError::HttpError)?
-        .map_err(|_| OracleError::HttpError)?;
-
-    if response.code != 200 {
-        return Err(OracleError::HttpError);
-    }
-
-    let body = response.body().collect::<Vec<u8>>();
+/// Make the open price feed HTTP API request using HTTP GET.
+fn open_price_feed_request_unchecked(
+    url: &str,
+    http_config: &runtime_interfaces::HttpConfig,
+) -> Result<OpenPriceFeedApiResponse, OracleError> {
+    let options = ethereum_client::HttpOptions::new(
+        http_config.get_timeout_ms(),
+        http_config.get_retries(),
+        http_config.get_headers(url),
+    );
+    let body =
+        ethereum_client::send_http(url, None, &options).map_err(|_| OracleError::HttpError)?;
     let parsed = parse_open_price_feed_api_response(&body);
 
     parsed
@@ -175,51 +179,226 @@ impl OpenPriceFeedApiResponse {
 const OCW_LATEST_BLOCK_NUMBER: &[u8; 41] = b"cash::latest_price_feed_poll_block_number";
 const OCW_STORAGE_LOCK: &[u8; 34] = b"cash::storage_lock_open_price_feed";
 
-pub fn check_signature<T: Config>(
-    payload: &Vec<u8>,
-    signature: &Vec<u8>,
-) -> Result<bool, OracleError> {
+/// Recover the address which signed the given payload.
+fn recover_signer(payload: &Vec<u8>, signature: &Vec<u8>) -> Result<Reporter, OracleError> {
     // check signature
     let parsed_sig: [u8; 65] = gateway_crypto::eth_signature_from_bytes(&signature)?;
 
     // note that this is actually a double-hash situation but that is expected behavior
     // the hashed message is hashed again in the eth convention inside eth_recover
     let hashed = gateway_crypto::keccak(&payload);
-    let recovered =
-        runtime_interfaces::keyring_interface::eth_recover(hashed.into(), parsed_sig, true)?;
+    Ok(runtime_interfaces::keyring_interface::eth_recover(
+        hashed.into(),
+        parsed_sig,
+        true,
+    )?)
+}
 
-    Ok(PriceReporters::get().contains(recovered))
+pub fn check_signature<T: Config>(
+    payload: &Vec<u8>,
+    signature: &Vec<u8>,
+) -> Result<bool, OracleError> {
+    Ok(PriceReporters::get().contains(recover_signer(payload, signature)?))
+}
+
+/// Recover the reporter which signed the given payload, failing if it is not a known reporter.
+pub fn recover_reporter<T: Config>(
+    payload: &Vec<u8>,
+    signature: &Vec<u8>,
+) -> Result<Reporter, OracleError> {
+    let recovered = recover_signer(payload, signature)?;
+    if !PriceReporters::get().contains(recovered) {
+        Err(OracleError::InvalidReporter)?;
+    }
+    Ok(recovered)
+}
+
+/// Get the current chain time, in milliseconds.
+pub fn now<T: Config>() -> Timestamp {
+    T::TimeProvider::now().as_millis() as Timestamp
 }
 
 pub fn get_and_check_parsed_price<T: Config>(
     payload: &Vec<u8>,
+    reporter: Reporter,
 ) -> Result<(Message, Ticker), OracleError> {
     // parse message and check it
     let parsed = parse_message(payload)?;
     let ticker = Ticker::from_str(&parsed.key)?;
 
-    if let Some(last_updated) = PriceTimes::get(&ticker) {
-        if parsed.timestamp <= last_updated {
+    // Reporters choose their own timestamps, so a report from the future could outlive every other
+    if parsed.timestamp > now::<T>().saturating_add(MAX_REPORT_CLOCK_SKEW) {
+        Err(OracleError::FuturePrice)?;
+    }
+
+    if let Some((_, last_reported)) = ReporterPrices::get(&ticker, reporter) {
+        if parsed.timestamp <= last_reported {
             Err(OracleError::StalePrice)?;
         }
     }
 
     Ok((parsed, ticker))
 }
-pub fn post_price<T: Config>(payload: Vec<u8>, signature: Vec<u8>) -> Result<(), OracleError> {
-    if !check_signature::<T>(&payload, &signature)? {
-        Err(OracleError::InvalidReporter)?;
+
+/// Get the median of the given prices, or None if there are none.
+pub fn median(mut prices: Vec<AssetPrice>) -> Option<AssetPrice> {
+    prices.sort();
+    let mid = prices.len() / 2;
+    match prices.len() {
+        0 => None,
+        // average the middle two without overflowing
+        n if n % 2 == 0 => Some(
+            prices[mid - 1] / 2 + prices[mid] / 2 + (prices[mid - 1] % 2 + prices[mid] % 2) / 2,
+        ),
+        _ => Some(prices[mid]),
     }
+}
 
-    let (parsed, ticker) = get_and_check_parsed_price::<T>(&payload)?;
+/// Get the median of the fresh reports from current reporters for a ticker,
+///  as of the given chain time, as long as there are enough of them.
+pub fn get_median_price<T: Config>(ticker: Ticker, as_of: Timestamp) -> Option<AssetPrice> {
+    let reporters = PriceReporters::get();
+    let fresh_prices: Vec<AssetPrice> = ReporterPrices::iter_prefix(&ticker)
+        .filter(|(reporter, (_, timestamp))| {
+            reporters.contains(*reporter) && timestamp.saturating_add(MAX_REPORT_AGE) >= as_of
+        })
+        .map(|(_, (price, _))| price)
+        .collect();
+
+    if fresh_prices.len() < MinReporters::get().max(1) as usize {
+        return None;
+    }
+
+    median(fresh_prices)
+}
+
+pub fn post_price<T: Config>(payload: Vec<u8>, signature: Vec<u8>) -> Result<(), OracleError> {
+    let reporter = recover_reporter::<T>(&payload, &signature)?;
+
+    let (parsed, ticker) = get_and_check_parsed_price::<T>(&payload, reporter)?;
 
     // * WARNING begin storage - all checks must happen above * //
 
-    Prices::insert(&ticker, parsed.value as AssetPrice);
-    PriceTimes::insert(&ticker, parsed.timestamp as Timestamp);
+    ReporterPrices::insert(
+        &ticker,
+        reporter,
+        (parsed.value as AssetPrice, parsed.timestamp as Timestamp),
+    );
+
+    // Only publish a price once enough reporters agree on a recent one
+    let now = now::<T>();
+    if let Some(price) = get_median_price::<T>(ticker, now) {
+        publish_price::<T>(ticker, price, now);
+    }
+    Ok(())
+}
+
+/// Publish a new median price for a ticker, unless it jumps beyond the ticker's limit.
+///
+/// A jump beyond the limit trips the breaker and leaves the last healthy price in place,
+///  as the reference for following prices, until enough consecutive blocks confirm the jump.
+pub fn publish_price<T: Config>(ticker: Ticker, price: AssetPrice, now: Timestamp) {
+    if let (Some(limit), Some(last_price)) = (PriceLimits::get(ticker), Prices::get(ticker)) {
+        if !limit.is_plausible_jump(last_price, price)
+            && !confirm_jump::<T>(ticker, limit, price, now)
+        {
+            return;
+        }
+        PendingPrices::remove(ticker);
+        if PriceBreakers::contains_key(ticker) {
+            reset_breaker::<T>(ticker);
+        }
+    }
+
+    Prices::insert(&ticker, price);
+    PriceTimes::insert(&ticker, now);
+}
+
+/// Count another price which jumped beyond the limit from the last healthy price,
+///  returning whether enough consecutive blocks have agreed on the jump for it to be accepted.
+///
+/// Prices published at the same time, i.e. within the same block, only count once.
+fn confirm_jump<T: Config>(
+    ticker: Ticker,
+    limit: PriceLimit,
+    price: AssetPrice,
+    now: Timestamp,
+) -> bool {
+    let confirmations = match PendingPrices::get(ticker) {
+        Some((pending, confirmations, confirmed_at)) if limit.is_plausible_jump(pending, price) => {
+            if now > confirmed_at {
+                confirmations.saturating_add(1)
+            } else {
+                confirmations
+            }
+        }
+        _ => 1,
+    };
+    if confirmations >= limit.min_confirmations.max(2) {
+        return true;
+    }
+
+    PendingPrices::insert(ticker, (price, confirmations, now));
+    if PriceBreakers::get(ticker) != Some(OracleError::ImplausiblePrice) {
+        trip_breaker::<T>(ticker, OracleError::ImplausiblePrice);
+    }
+    false
+}
+
+pub fn set_price_limit<T: Config>(
+    ticker: Ticker,
+    limit: Option<PriceLimit>,
+) -> Result<(), OracleError> {
+    match limit {
+        Some(limit) => PriceLimits::insert(ticker, limit),
+        None => {
+            PriceLimits::remove(ticker);
+            PendingPrices::remove(ticker);
+            if PriceBreakers::contains_key(ticker) {
+                reset_breaker::<T>(ticker);
+            }
+        }
+    }
+    <Module<T>>::deposit_event(Event::SetPriceLimit(ticker, limit));
     Ok(())
 }
 
+pub fn set_min_reporters<T: Config>(min_reporters: u32) -> Result<(), OracleError> {
+    if min_reporters == 0 {
+        Err(OracleError::InvalidMinReporters)?;
+    }
+    MinReporters::put(min_reporters);
+    <Module<T>>::deposit_event(Event::SetMinReporters(min_reporters));
+    Ok(())
+}
+
+pub fn trip_breaker<T: Config>(ticker: Ticker, reason: OracleError) {
+    log!("Price breaker tripped for {:?}: {:?}", ticker, reason);
+    PriceBreakers::insert(ticker, reason);
+    <Module<T>>::deposit_event(Event::BreakerTripped(ticker, reason));
+}
+
+pub fn reset_breaker<T: Config>(ticker: Ticker) {
+    log!("Price breaker reset for {:?}", ticker);
+    PriceBreakers::remove(ticker);
+    <Module<T>>::deposit_event(Event::BreakerReset(ticker));
+}
+
+/// Trip or reset the circuit breakers on prices which have expired or been refreshed, as of now.
+pub fn update_price_breakers<T: Config>(now: Timestamp) {
+    for (ticker, limit) in PriceLimits::iter() {
+        let expired = match PriceTimes::get(ticker) {
+            Some(updated_at) => limit.is_expired(updated_at, now),
+            None => true,
+        };
+        match (PriceBreakers::get(ticker), expired) {
+            (None, true) => trip_breaker::<T>(ticker, OracleError::ExpiredPrice),
+            (Some(OracleError::ExpiredPrice), false) => reset_breaker::<T>(ticker),
+            _ => (),
+        }
+    }
+}
+
 /// Procedure for offchain worker to processes messages coming out of the open price feed
 pub fn process_prices<T: Config>(block_number: T::BlockNumber) -> Result<(), OracleError> {
     let mut lock = StorageLock::<Time>::new(OCW_STORAGE_LOCK);
@@ -247,10 +426,11 @@ pub fn process_prices<T: Config>(block_number: T::BlockNumber) -> Result<(), Ora
         }
     }
     let url = String::from_utf8(url).map_err(|_| OracleError::InvalidApiEndpoint)?;
+    let http_config = runtime_interfaces::validator_config_interface::get_opf_http_config();
 
     // poll
     let (messages_and_signatures, timestamp) =
-        open_price_feed_request(&url)?.to_message_signature_pairs()?;
+        open_price_feed_request(&url, &http_config)?.to_message_signature_pairs()?;
 
     let curr_ts = runtime_interfaces::price_feed_interface::get_price_data_ts();
     if curr_ts.map(|v| v < timestamp).unwrap_or(true) {
@@ -341,6 +521,16 @@ pub mod tests {
         assert_eq!(actual.timestamp, "1609340760");
     }
 
+    #[test]
+    fn test_median() {
+        assert_eq!(median(vec![]), None);
+        assert_eq!(median(vec![5]), Some(5));
+        assert_eq!(median(vec![7, 1, 5]), Some(5));
+        assert_eq!(median(vec![7, 1, 5, 3]), Some(4));
+        assert_eq!(median(vec![2, 3]), Some(2));
+        assert_eq!(median(vec![u128::MAX, u128::MAX]), Some(u128::MAX));
+    }
+
     #[test]
     fn test_recover() {
         let msg = hex_literal::hex!("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005fec975800000000000000000000000000000000000000000000000000000000000000c00000000000000000000000000000000000000000000000000000000688e4cda00000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034254430000000000000000000000000000000000000000000000000000000000");
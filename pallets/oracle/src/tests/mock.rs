@@ -45,6 +45,7 @@ frame_support::construct_runtime!(
     UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Module, Call, Config, Storage, Event<T>},
+        Timestamp: pallet_timestamp::{Module, Call, Storage, Inherent},
         Oracle: pallet_oracle::{Module, Call, Config, Storage, Event, Inherent},
     }
 );
@@ -52,6 +53,7 @@ frame_support::construct_runtime!(
 frame_support::parameter_types! {
     pub const BlockHashCount: u64 = 250;
     pub const SS58Prefix: u8 = 42;
+    pub const MinimumPeriod: u64 = 3000;
 }
 
 impl frame_system::Config for Test {
@@ -84,9 +86,23 @@ impl frame_system::offchain::SigningTypes for Test {
     type Signature = Signature;
 }
 
+impl pallet_timestamp::Config for Test {
+    /// A timestamp: milliseconds since the unix epoch.
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
 impl Config for Test {
     type Event = Event;
     type Call = Call;
+    type TimeProvider = Timestamp;
+}
+
+/// Set the chain time, in milliseconds.
+pub fn set_now(now: u64) {
+    Timestamp::set_timestamp(now);
 }
 
 impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
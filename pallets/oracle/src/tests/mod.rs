@@ -1,8 +1,12 @@
-use crate::{error::OracleError, ticker::Ticker, *};
+use crate::{error::OracleError, ticker::Ticker, types::PriceLimit, *};
 
 use sp_core::offchain::testing;
 
-pub use frame_support::{assert_err, assert_ok, dispatch::DispatchError};
+pub use frame_support::{
+    assert_err, assert_ok,
+    dispatch::DispatchError,
+    storage::{StorageMap, StorageValue},
+};
 pub use our_std::{convert::TryInto, str::FromStr};
 
 pub mod mock;
@@ -23,6 +27,12 @@ pub fn initialize_storage() {
 
 const TEST_OPF_URL: &str = "http://localhost/";
 
+/// The time the test ETH price message was reported at.
+const REPORT_TIME: u64 = 1609340760000;
+
+/// The chain time the test ETH price message is posted at.
+const POST_TIME: u64 = REPORT_TIME + 1000;
+
 #[test]
 fn test_process_prices_happy_path_makes_required_http_call() {
     std::env::set_var("OPF_URL", TEST_OPF_URL);
@@ -56,11 +66,218 @@ fn test_post_price_happy_path() {
     let test_signature = hex::decode("41a3f89a526dee766049f3699e9e975bfbabda4db677c9f5c41fbcc0730fccb84d08b2208c4ffae0b87bb162e2791cc305ee4e9a1d936f9e6154356154e9a8e9000000000000000000000000000000000000000000000000000000000000001c").unwrap();
     new_test_ext().execute_with(|| {
         initialize_storage(); // sets up ETH
+        set_now(POST_TIME);
         OracleModule::post_price(Origin::none(), test_payload, test_signature).unwrap();
         let eth_price = OracleModule::price(ETH_TICKER);
         let eth_price_time = OracleModule::price_time(ETH_TICKER);
         assert_eq!(eth_price, Some(732580000));
-        assert_eq!(eth_price_time, Some(1609340760000));
+        assert_eq!(eth_price_time, Some(POST_TIME));
+    });
+}
+
+#[test]
+fn test_post_price_waits_for_min_reporters() {
+    // an eth price message
+    let test_payload = hex::decode("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005fec975800000000000000000000000000000000000000000000000000000000000000c0000000000000000000000000000000000000000000000000000000002baa48a00000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034554480000000000000000000000000000000000000000000000000000000000").unwrap();
+    let test_signature = hex::decode("41a3f89a526dee766049f3699e9e975bfbabda4db677c9f5c41fbcc0730fccb84d08b2208c4ffae0b87bb162e2791cc305ee4e9a1d936f9e6154356154e9a8e9000000000000000000000000000000000000000000000000000000000000001c").unwrap();
+    let reporter = [
+        133, 97, 91, 7, 102, 21, 49, 124, 128, 241, 76, 186, 214, 80, 30, 236, 3, 28, 213, 28,
+    ];
+    new_test_ext().execute_with(|| {
+        initialize_storage(); // sets up ETH
+        set_now(POST_TIME);
+        MinReporters::put(2);
+        OracleModule::post_price(Origin::none(), test_payload, test_signature).unwrap();
+        assert_eq!(
+            OracleModule::reporter_price(ETH_TICKER, reporter),
+            Some((732580000, 1609340760000))
+        );
+        assert_eq!(OracleModule::price(ETH_TICKER), None);
+        assert_eq!(OracleModule::price_time(ETH_TICKER), None);
+    });
+}
+
+#[test]
+fn test_post_price_trips_breaker_on_jump() {
+    // an eth price message
+    let test_payload = hex::decode("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005fec975800000000000000000000000000000000000000000000000000000000000000c0000000000000000000000000000000000000000000000000000000002baa48a00000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034554480000000000000000000000000000000000000000000000000000000000").unwrap();
+    let test_signature = hex::decode("41a3f89a526dee766049f3699e9e975bfbabda4db677c9f5c41fbcc0730fccb84d08b2208c4ffae0b87bb162e2791cc305ee4e9a1d936f9e6154356154e9a8e9000000000000000000000000000000000000000000000000000000000000001c").unwrap();
+    new_test_ext().execute_with(|| {
+        initialize_storage(); // sets up ETH
+        set_now(POST_TIME);
+        let limit = PriceLimit {
+            max_age: 60 * 60 * 1000,
+            max_jump_bps: 1000,
+            min_confirmations: 2,
+        };
+        assert_ok!(OracleModule::set_price_limit(
+            Origin::root(),
+            ETH_TICKER,
+            Some(limit)
+        ));
+        Prices::insert(ETH_TICKER, 500000000);
+
+        OracleModule::post_price(Origin::none(), test_payload, test_signature).unwrap();
+        assert_eq!(OracleModule::price(ETH_TICKER), Some(500000000));
+        assert_eq!(
+            OracleModule::pending_price(ETH_TICKER),
+            Some((732580000, 1, POST_TIME))
+        );
+        assert_eq!(
+            OracleModule::price_breaker(ETH_TICKER),
+            Some(OracleError::ImplausiblePrice)
+        );
+        assert_eq!(
+            get_price_by_ticker::<Test>(ETH_TICKER),
+            Err(OracleError::ImplausiblePrice)
+        );
+
+        let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+        assert_eq!(
+            events,
+            vec![
+                mock::Event::pallet_oracle(crate::Event::SetPriceLimit(ETH_TICKER, Some(limit))),
+                mock::Event::pallet_oracle(crate::Event::BreakerTripped(
+                    ETH_TICKER,
+                    OracleError::ImplausiblePrice
+                )),
+            ]
+        );
+
+        // removing the limit resets the breaker
+        assert_ok!(OracleModule::set_price_limit(
+            Origin::root(),
+            ETH_TICKER,
+            None
+        ));
+        assert_eq!(OracleModule::price_breaker(ETH_TICKER), None);
+        assert_eq!(OracleModule::pending_price(ETH_TICKER), None);
+        assert_eq!(
+            get_price_by_ticker::<Test>(ETH_TICKER).map(|p| p.value),
+            Ok(500000000)
+        );
+    });
+}
+
+#[test]
+fn test_publish_price_requires_consecutive_confirmations() {
+    new_test_ext().execute_with(|| {
+        PriceLimits::insert(
+            ETH_TICKER,
+            PriceLimit {
+                max_age: 60 * 60 * 1000,
+                max_jump_bps: 1000,
+                min_confirmations: 3,
+            },
+        );
+        Prices::insert(ETH_TICKER, 500000000);
+
+        // a lone outlier leaves the healthy price as the reference
+        oracle::publish_price::<Test>(ETH_TICKER, 900000000, 1000);
+        assert_eq!(OracleModule::price(ETH_TICKER), Some(500000000));
+        assert_eq!(
+            OracleModule::price_breaker(ETH_TICKER),
+            Some(OracleError::ImplausiblePrice)
+        );
+        oracle::publish_price::<Test>(ETH_TICKER, 510000000, 2000);
+        assert_eq!(OracleModule::price(ETH_TICKER), Some(510000000));
+        assert_eq!(OracleModule::price_breaker(ETH_TICKER), None);
+        assert_eq!(OracleModule::pending_price(ETH_TICKER), None);
+
+        // a jump is only accepted once enough consecutive prices agree on it
+        oracle::publish_price::<Test>(ETH_TICKER, 730000000, 3000);
+        oracle::publish_price::<Test>(ETH_TICKER, 735000000, 4000);
+        assert_eq!(OracleModule::price(ETH_TICKER), Some(510000000));
+        assert_eq!(OracleModule::price_time(ETH_TICKER), Some(2000));
+        assert_eq!(
+            OracleModule::pending_price(ETH_TICKER),
+            Some((735000000, 2, 4000))
+        );
+        oracle::publish_price::<Test>(ETH_TICKER, 732000000, 5000);
+        assert_eq!(OracleModule::price(ETH_TICKER), Some(732000000));
+        assert_eq!(OracleModule::price_time(ETH_TICKER), Some(5000));
+        assert_eq!(OracleModule::price_breaker(ETH_TICKER), None);
+        assert_eq!(OracleModule::pending_price(ETH_TICKER), None);
+    });
+}
+
+#[test]
+fn test_publish_price_counts_confirmations_once_per_block() {
+    new_test_ext().execute_with(|| {
+        PriceLimits::insert(
+            ETH_TICKER,
+            PriceLimit {
+                max_age: 60 * 60 * 1000,
+                max_jump_bps: 1000,
+                min_confirmations: 2,
+            },
+        );
+        Prices::insert(ETH_TICKER, 500000000);
+        PriceTimes::insert(ETH_TICKER, 1000);
+
+        // repeated posts within the same block do not confirm a jump
+        for _ in 0..3 {
+            oracle::publish_price::<Test>(ETH_TICKER, 730000000, 2000);
+        }
+        assert_eq!(OracleModule::price(ETH_TICKER), Some(500000000));
+        assert_eq!(
+            OracleModule::pending_price(ETH_TICKER),
+            Some((730000000, 1, 2000))
+        );
+        assert_eq!(
+            OracleModule::price_breaker(ETH_TICKER),
+            Some(OracleError::ImplausiblePrice)
+        );
+
+        // but the same price in the next block does
+        oracle::publish_price::<Test>(ETH_TICKER, 730000000, 3000);
+        assert_eq!(OracleModule::price(ETH_TICKER), Some(730000000));
+        assert_eq!(OracleModule::price_time(ETH_TICKER), Some(3000));
+        assert_eq!(OracleModule::price_breaker(ETH_TICKER), None);
+        assert_eq!(OracleModule::pending_price(ETH_TICKER), None);
+    });
+}
+
+#[test]
+fn test_update_price_breakers_expires_and_resets() {
+    new_test_ext().execute_with(|| {
+        let limit = PriceLimit {
+            max_age: 1000,
+            max_jump_bps: 1000,
+            min_confirmations: 2,
+        };
+        PriceLimits::insert(ETH_TICKER, limit);
+        Prices::insert(ETH_TICKER, 732580000);
+        PriceTimes::insert(ETH_TICKER, 10000);
+
+        oracle::update_price_breakers::<Test>(11000);
+        assert_eq!(OracleModule::price_breaker(ETH_TICKER), None);
+
+        oracle::update_price_breakers::<Test>(11001);
+        assert_eq!(
+            OracleModule::price_breaker(ETH_TICKER),
+            Some(OracleError::ExpiredPrice)
+        );
+        assert_eq!(
+            get_price_by_ticker::<Test>(ETH_TICKER),
+            Err(OracleError::ExpiredPrice)
+        );
+
+        PriceTimes::insert(ETH_TICKER, 11001);
+        oracle::update_price_breakers::<Test>(11002);
+        assert_eq!(OracleModule::price_breaker(ETH_TICKER), None);
+
+        let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+        assert_eq!(
+            events,
+            vec![
+                mock::Event::pallet_oracle(crate::Event::BreakerTripped(
+                    ETH_TICKER,
+                    OracleError::ExpiredPrice
+                )),
+                mock::Event::pallet_oracle(crate::Event::BreakerReset(ETH_TICKER)),
+            ]
+        );
     });
 }
 
@@ -98,19 +315,72 @@ fn test_post_price_stale_price() {
     let test_signature = hex::decode("41a3f89a526dee766049f3699e9e975bfbabda4db677c9f5c41fbcc0730fccb84d08b2208c4ffae0b87bb162e2791cc305ee4e9a1d936f9e6154356154e9a8e9000000000000000000000000000000000000000000000000000000000000001c").unwrap();
     new_test_ext().execute_with(|| {
         initialize_storage(); // sets up ETH
-                              // post once
+        set_now(POST_TIME);
+        // post once
         OracleModule::post_price(Origin::none(), test_payload.clone(), test_signature.clone())
             .unwrap();
         let eth_price = OracleModule::price(ETH_TICKER);
         let eth_price_time = OracleModule::price_time(ETH_TICKER);
         assert_eq!(eth_price, Some(732580000));
-        assert_eq!(eth_price_time, Some(1609340760000));
+        assert_eq!(eth_price_time, Some(POST_TIME));
         // try to post the same thing again
         let result = OracleModule::post_price(Origin::none(), test_payload, test_signature);
         assert_err!(result, OracleError::StalePrice);
     });
 }
 
+#[test]
+fn test_post_price_from_the_future() {
+    // an eth price message
+    let test_payload = hex::decode("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005fec975800000000000000000000000000000000000000000000000000000000000000c0000000000000000000000000000000000000000000000000000000002baa48a00000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034554480000000000000000000000000000000000000000000000000000000000").unwrap();
+    let test_signature = hex::decode("41a3f89a526dee766049f3699e9e975bfbabda4db677c9f5c41fbcc0730fccb84d08b2208c4ffae0b87bb162e2791cc305ee4e9a1d936f9e6154356154e9a8e9000000000000000000000000000000000000000000000000000000000000001c").unwrap();
+    new_test_ext().execute_with(|| {
+        initialize_storage(); // sets up ETH
+        set_now(REPORT_TIME - MAX_REPORT_CLOCK_SKEW - 1);
+        let result = OracleModule::post_price(Origin::none(), test_payload, test_signature);
+        assert_err!(result, OracleError::FuturePrice);
+        assert_eq!(OracleModule::price(ETH_TICKER), None);
+    });
+}
+
+#[test]
+fn test_post_price_ignores_reports_stale_by_chain_time() {
+    // an eth price message
+    let test_payload = hex::decode("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005fec975800000000000000000000000000000000000000000000000000000000000000c0000000000000000000000000000000000000000000000000000000002baa48a00000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034554480000000000000000000000000000000000000000000000000000000000").unwrap();
+    let test_signature = hex::decode("41a3f89a526dee766049f3699e9e975bfbabda4db677c9f5c41fbcc0730fccb84d08b2208c4ffae0b87bb162e2791cc305ee4e9a1d936f9e6154356154e9a8e9000000000000000000000000000000000000000000000000000000000000001c").unwrap();
+    new_test_ext().execute_with(|| {
+        initialize_storage(); // sets up ETH
+        set_now(REPORT_TIME + MAX_REPORT_AGE + 1);
+        OracleModule::post_price(Origin::none(), test_payload, test_signature).unwrap();
+        assert_eq!(OracleModule::price(ETH_TICKER), None);
+        assert_eq!(OracleModule::price_time(ETH_TICKER), None);
+    });
+}
+
+#[test]
+fn test_set_min_reporters() {
+    new_test_ext().execute_with(|| {
+        assert_err!(
+            OracleModule::set_min_reporters(Origin::signed(Default::default()), 2),
+            DispatchError::BadOrigin
+        );
+        assert_err!(
+            OracleModule::set_min_reporters(Origin::root(), 0),
+            OracleError::InvalidMinReporters
+        );
+        assert_ok!(OracleModule::set_min_reporters(Origin::root(), 2));
+        assert_eq!(OracleModule::min_reporters(), 2);
+
+        let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+        assert_eq!(
+            events.last(),
+            Some(&mock::Event::pallet_oracle(crate::Event::SetMinReporters(
+                2
+            )))
+        );
+    });
+}
+
 #[test]
 fn offchain_worker_test() {
     use frame_support::traits::OffchainWorker;
@@ -42,6 +42,37 @@ impl Price {
     }
 }
 
+/// Type for the limits on a ticker's price, beyond which the price is considered unhealthy.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct PriceLimit {
+    /// The maximum time (milliseconds) since the last price update.
+    pub max_age: Timestamp,
+    /// The maximum change from one price update to the next, in basis points.
+    pub max_jump_bps: u32,
+    /// The number of consecutive blocks whose prices must agree on a larger jump before it's accepted (at least 2).
+    pub min_confirmations: u32,
+}
+
+impl PriceLimit {
+    /// Whether the change from the old price to the new price is within the limit.
+    pub fn is_plausible_jump(&self, old: AssetPrice, new: AssetPrice) -> bool {
+        let delta = if new > old { new - old } else { old - new };
+        // delta / old <= max_jump_bps / 10000, without dividing
+        match (
+            delta.checked_mul(10_000),
+            old.checked_mul(self.max_jump_bps as AssetPrice),
+        ) {
+            (Some(lhs), Some(rhs)) => lhs <= rhs,
+            _ => false,
+        }
+    }
+
+    /// Whether a price updated at the given time is too old as of now.
+    pub fn is_expired(&self, updated_at: Timestamp, now: Timestamp) -> bool {
+        now.saturating_sub(updated_at) > self.max_age
+    }
+}
+
 /// Type for a set of open price feed reporters.
 #[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, Types)]
 pub struct ReporterSet(pub Vec<Reporter>);
@@ -67,3 +98,25 @@ impl<'a> TryFrom<Vec<&'a str>> for ReporterSet {
         Ok(ReporterSet(reporters))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_limit() {
+        let limit = PriceLimit {
+            max_age: 1000,
+            max_jump_bps: 2500,
+            min_confirmations: 2,
+        };
+        assert!(limit.is_plausible_jump(100, 125));
+        assert!(limit.is_plausible_jump(100, 75));
+        assert!(!limit.is_plausible_jump(100, 126));
+        assert!(!limit.is_plausible_jump(100, 74));
+        assert!(!limit.is_plausible_jump(0, 1));
+        assert!(!limit.is_expired(5000, 6000));
+        assert!(limit.is_expired(5000, 6001));
+        assert!(!limit.is_expired(5000, 4000));
+    }
+}
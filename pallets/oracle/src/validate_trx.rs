@@ -34,7 +34,9 @@ pub fn validate_unsigned<T: Config>(
                             .propagate(false)
                             .build())
                     }
-                    _ => match oracle::get_and_check_parsed_price::<T>(payload) {
+                    _ => match oracle::recover_reporter::<T>(payload, signature).and_then(
+                        |reporter| oracle::get_and_check_parsed_price::<T>(payload, reporter),
+                    ) {
                         Ok(_) => Ok(ValidTransaction::with_tag_prefix("Gateway::post_price")
                             .priority(UNSIGNED_TXS_PRIORITY)
                             .longevity(UNSIGNED_TXS_LONGEVITY)
@@ -83,7 +85,10 @@ pub fn validate_unsigned<T: Config>(
                                 TransactionSource::Local | TransactionSource::InBlock => {
                                     Ok(validation)
                                 }
-                                _ => match oracle::get_and_check_parsed_price::<T>(payload) {
+                                _ => match oracle::recover_reporter::<T>(payload, signature)
+                                    .and_then(|reporter| {
+                                        oracle::get_and_check_parsed_price::<T>(payload, reporter)
+                                    }) {
                                     Ok(_) => Ok(validation),
                                     Err(err) => Err(ValidationError::InvalidPrice(err)),
                                 },
@@ -101,8 +106,10 @@ pub fn validate_unsigned<T: Config>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{tests::*, ticker::Ticker, types::ReporterSet, Call, PriceReporters, PriceTimes};
-    use frame_support::storage::{StorageMap, StorageValue};
+    use crate::{
+        tests::*, ticker::Ticker, types::ReporterSet, Call, PriceReporters, ReporterPrices,
+    };
+    use frame_support::storage::{StorageDoubleMap, StorageValue};
 
     #[test]
     fn test_post_price_invalid_signature() {
@@ -122,7 +129,8 @@ mod tests {
         new_test_ext().execute_with(|| {
             PriceReporters::put(ReporterSet(vec![[133, 97, 91, 7, 102, 21, 49, 124, 128, 241, 76, 186, 214, 80, 30, 236, 3, 28, 213, 28]]));
             let ticker = Ticker::new("BTC");
-            PriceTimes::insert(ticker, 999999999999999);
+            let reporter = [133, 97, 91, 7, 102, 21, 49, 124, 128, 241, 76, 186, 214, 80, 30, 236, 3, 28, 213, 28];
+            ReporterPrices::insert(ticker, reporter, (0, 999999999999999));
             let msg = hex_literal::hex!("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005fec975800000000000000000000000000000000000000000000000000000000000000c00000000000000000000000000000000000000000000000000000000688e4cda00000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034254430000000000000000000000000000000000000000000000000000000000");
             let sig = hex_literal::hex!("69538bfa1a2097ea206780654d7baac3a17ee57547ee3eeb5d8bcb58a2fcdf401ff8834f4a003193f24224437881276fe76c8e1c0a361081de854457d41d0690000000000000000000000000000000000000000000000000000000000000001c");
 
@@ -200,7 +208,8 @@ mod tests {
         new_test_ext().execute_with(|| {
             PriceReporters::put(ReporterSet(vec![[133, 97, 91, 7, 102, 21, 49, 124, 128, 241, 76, 186, 214, 80, 30, 236, 3, 28, 213, 28]]));
             let ticker = Ticker::new("BTC");
-            PriceTimes::insert(ticker, 999999999999999);
+            let reporter = [133, 97, 91, 7, 102, 21, 49, 124, 128, 241, 76, 186, 214, 80, 30, 236, 3, 28, 213, 28];
+            ReporterPrices::insert(ticker, reporter, (0, 999999999999999));
             let msg = hex_literal::hex!("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005fec975800000000000000000000000000000000000000000000000000000000000000c00000000000000000000000000000000000000000000000000000000688e4cda00000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034254430000000000000000000000000000000000000000000000000000000000");
             let sig = hex_literal::hex!("69538bfa1a2097ea206780654d7baac3a17ee57547ee3eeb5d8bcb58a2fcdf401ff8834f4a003193f24224437881276fe76c8e1c0a361081de854457d41d0690000000000000000000000000000000000000000000000000000000000000001c");
 
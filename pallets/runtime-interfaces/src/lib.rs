@@ -8,59 +8,354 @@ use std::sync::Mutex;
 
 #[derive(Clone, Decode, Encode, PassByCodec)]
 pub struct Config {
-    eth_starport_address: Vec<u8>,
+    /// The starport on each EVM chain which has one.
+    starports: Vec<StarportDeployment>,
+}
+
+/// Where the starport on an EVM chain is deployed.
+#[derive(Clone, Decode, Encode)]
+pub struct StarportDeployment {
+    /// The name of the chain, e.g. `eth`.
+    pub chain: String,
+    pub address: Vec<u8>,
+    /// The block the starport was deployed in, which has no events before it.
+    pub block: u64,
+}
+
+impl StarportDeployment {
+    pub fn new(chain: &str, address: &str, block: u64) -> StarportDeployment {
+        StarportDeployment {
+            chain: chain.into(),
+            address: address.into(),
+            block,
+        }
+    }
+}
+
+/// The options for the HTTP requests a validator makes to one of its endpoints.
+/// These may hold credentials, so they're only kept in the validator's memory, never on chain.
+#[derive(Clone, Default, Decode, Encode, PartialEq, PassByCodec)]
+pub struct HttpConfig {
+    timeout_ms: Option<u64>,
+    retries: Option<u32>,
+    headers: Vec<HttpHeader>,
+}
+
+/// A header sent with the requests to a single URL, or to every URL if it isn't scoped to one.
+#[derive(Clone, Decode, Encode, PartialEq)]
+struct HttpHeader {
+    url: Option<String>,
+    name: String,
+    value: String,
+}
+
+impl HttpConfig {
+    /// Build the config from headers given as `Name: value`, to send them with requests to every URL,
+    ///  or as `url=<URL> Name: value`, to only send them with requests to that URL.
+    pub fn parse(
+        timeout_ms: Option<u64>,
+        retries: Option<u32>,
+        headers: &[String],
+    ) -> Result<HttpConfig, String> {
+        Ok(HttpConfig {
+            timeout_ms,
+            retries,
+            headers: headers
+                .iter()
+                .map(|header| parse_http_header(header))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn get_timeout_ms(&self) -> Option<u64> {
+        self.timeout_ms
+    }
+
+    pub fn get_retries(&self) -> Option<u32> {
+        self.retries
+    }
+
+    /// Get the headers to send with requests to the URL.
+    pub fn get_headers(&self, url: &str) -> Vec<(String, String)> {
+        self.headers
+            .iter()
+            .filter(|header| header.url.as_ref().map_or(true, |scope| scope == url))
+            .map(|header| (header.name.clone(), header.value.clone()))
+            .collect()
+    }
+}
+
+fn parse_http_header(header: &str) -> Result<HttpHeader, String> {
+    let invalid = || {
+        format!(
+            "Invalid HTTP header, expected `Name: value` or `url=<URL> Name: value`: {}",
+            header
+        )
+    };
+    let (url, name_value) = match header.trim_start().strip_prefix("url=") {
+        Some(scoped) => match scoped.find(char::is_whitespace) {
+            Some(space) if space > 0 => (Some(scoped[..space].to_owned()), &scoped[space..]),
+            _ => return Err(invalid()),
+        },
+        None => (None, header),
+    };
+    match name_value.find(':') {
+        Some(colon) if !name_value[..colon].trim().is_empty() => Ok(HttpHeader {
+            url,
+            name: name_value[..colon].trim().to_owned(),
+            value: name_value[colon + 1..].trim().to_owned(),
+        }),
+        _ => Err(invalid()),
+    }
+}
+
+/// How a validator reaches the RPC nodes of an EVM chain.
+#[derive(Clone, Default)]
+pub struct EvmRpcConfig {
+    /// The RPC URL, or several comma separated URLs to fail over between.
+    url: Option<String>,
+    /// The number of RPC URLs which must return identical logs for an event to be signed.
+    quorum: Option<u32>,
+    /// The most blocks a single `eth_getLogs` call may span.
+    logs_block_span: Option<u64>,
+    /// The most `eth_getLogs` calls sent together in a single batch request.
+    logs_batch_size: Option<u32>,
+    http: HttpConfig,
+}
+
+impl EvmRpcConfig {
+    pub fn new(
+        url: Option<String>,
+        quorum: Option<u32>,
+        logs_block_span: Option<u64>,
+        logs_batch_size: Option<u32>,
+    ) -> EvmRpcConfig {
+        EvmRpcConfig {
+            url,
+            quorum,
+            logs_block_span,
+            logs_batch_size,
+            http: HttpConfig::default(),
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct ValidatorConfig {
     eth_key_id: String,
-    eth_rpc_url: String,
+    /// The RPC config for each EVM chain, by the name of the chain.
+    evm_rpc: Vec<(String, EvmRpcConfig)>,
+    liquidator_account: Option<String>,
     miner: String,
     opf_url: String,
+    opf_http: HttpConfig,
+}
+
+impl ValidatorConfig {
+    fn evm_rpc(&self, chain: &str) -> Option<&EvmRpcConfig> {
+        self.evm_rpc
+            .iter()
+            .find(|(name, _)| name == chain)
+            .map(|(_, rpc)| rpc)
+    }
 }
 
 /// XXX Possible sanity checks for config fields here
 impl Config {
     pub fn update(&mut self, new: Config) {
-        self.eth_starport_address = new.eth_starport_address;
+        self.starports = new.starports;
+    }
+
+    fn starport(&self, chain: &str) -> Option<&StarportDeployment> {
+        self.starports
+            .iter()
+            .find(|starport| starport.chain == chain)
+    }
+
+    /// Get the starport address on the named EVM chain, which is empty if it has no starport.
+    pub fn get_starport_address(&self, chain: &str) -> Vec<u8> {
+        self.starport(chain)
+            .map(|starport| starport.address.clone())
+            .unwrap_or_default()
     }
 
-    pub fn get_eth_starport_address(&self) -> Vec<u8> {
-        self.eth_starport_address.clone()
+    /// Get the block the starport on the named EVM chain was deployed in, or 0 if unknown.
+    pub fn get_starport_deploy_block(&self, chain: &str) -> u64 {
+        self.starport(chain)
+            .map(|starport| starport.block)
+            .unwrap_or_default()
     }
 }
 
-pub fn new_config(eth_starport_address: Vec<u8>) -> Config {
-    return Config {
-        eth_starport_address,
-    };
+pub fn new_config(starports: Vec<StarportDeployment>) -> Config {
+    return Config { starports };
 }
 
 type PriceFeedData = (Vec<(Vec<u8>, Vec<u8>)>, u64);
 
 lazy_static! {
-    static ref CONFIG: Mutex<Config> = Mutex::new(new_config("".into()));
+    static ref CONFIG: Mutex<Config> = Mutex::new(new_config(vec![]));
     static ref VALIDATOR_CONFIG: Mutex<Option<ValidatorConfig>> = Mutex::new(None);
     static ref PRICE_FEED_DATA: Mutex<Option<PriceFeedData>> = Mutex::new(None);
 }
 
+/// Initialize the validator config, with the RPC options of each EVM chain given by the name of the chain.
+///
+/// RPC options given by env vars override the given ones, e.g. `ETH_RPC_QUORUM`,
+///  and fail to initialize if they're invalid, just like invalid command line options.
 pub fn initialize_validator_config(
     eth_key_id: Option<String>,
-    eth_rpc_url: Option<String>,
+    evm_rpc: Vec<(String, EvmRpcConfig)>,
+    liquidator_account: Option<String>,
     miner: Option<String>,
     opf_url: Option<String>,
-) {
+) -> Result<(), String> {
+    let evm_rpc = evm_rpc
+        .into_iter()
+        .map(|(chain, rpc)| {
+            let rpc = evm_rpc_config_with_env(&evm_rpc_env_prefix(&chain), rpc)?;
+            Ok((chain, rpc))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
     match VALIDATOR_CONFIG.lock() {
         Ok(mut data_ref) => {
             *data_ref = Some(ValidatorConfig {
                 eth_key_id: eth_key_id.unwrap_or(ETH_KEY_ID_DEFAULT.to_owned()),
-                eth_rpc_url: eth_rpc_url.unwrap_or(ETH_RPC_URL_DEFAULT.to_owned()),
+                evm_rpc,
+                liquidator_account,
                 miner: miner.unwrap_or(MINER_DEFAULT.to_owned()),
                 opf_url: opf_url.unwrap_or(OPF_URL_DEFAULT.to_owned()),
+                opf_http: HttpConfig::default(),
             });
         }
         _ => (), // XXX todo: log?
     };
+    Ok(())
+}
+
+/// Apply any overrides from the `<prefix>_QUORUM`, `<prefix>_LOGS_BLOCK_SPAN` and `<prefix>_LOGS_BATCH_SIZE`
+///  env vars to the RPC options of an EVM chain.
+fn evm_rpc_config_with_env(
+    env_prefix: &str,
+    mut rpc_config: EvmRpcConfig,
+) -> Result<EvmRpcConfig, String> {
+    let env_var = format!("{}_QUORUM", env_prefix);
+    if let Some(quorum) = parse_positive_env_var(&env_var, "a count of URLs")? {
+        rpc_config.quorum = Some(quorum);
+    }
+    let env_var = format!("{}_LOGS_BLOCK_SPAN", env_prefix);
+    if let Some(span) = parse_positive_env_var(&env_var, "a number of blocks")? {
+        rpc_config.logs_block_span = Some(span);
+    }
+    let env_var = format!("{}_LOGS_BATCH_SIZE", env_prefix);
+    if let Some(size) = parse_positive_env_var(&env_var, "a number of calls")? {
+        rpc_config.logs_batch_size = Some(size);
+    }
+    Ok(rpc_config)
+}
+
+/// Parse the env var as a number greater than zero, if it's set.
+fn parse_positive_env_var<T: std::str::FromStr + Default + PartialEq>(
+    env_var: &str,
+    expected: &str,
+) -> Result<Option<T>, String> {
+    match std::env::var(env_var) {
+        Ok(value) => match value.parse() {
+            Ok(parsed) if parsed != T::default() => Ok(Some(parsed)),
+            _ => Err(format!(
+                "Invalid {}, expected {}: {}",
+                env_var, expected, value
+            )),
+        },
+        Err(_) => Ok(None),
+    }
+}
+
+/// Set the HTTP options for each endpoint, once the validator config is initialized.
+/// The RPC options of each EVM chain are given by the name of the chain.
+///
+/// Options given by env vars override the given ones, e.g. `ETH_RPC_TIMEOUT_MS` or `OPF_HEADERS`,
+///  and fail to initialize if they're invalid, just like invalid command line options.
+pub fn initialize_validator_http_config(
+    evm_rpc_http: Vec<(String, HttpConfig)>,
+    opf_http: HttpConfig,
+) -> Result<(), String> {
+    let evm_rpc_http = evm_rpc_http
+        .into_iter()
+        .map(|(chain, http)| {
+            let http = http_config_with_env(&evm_rpc_env_prefix(&chain), http)?;
+            Ok((chain, http))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let opf_http = http_config_with_env(OPF_HTTP_ENV_PREFIX, opf_http)?;
+    match VALIDATOR_CONFIG.lock() {
+        Ok(mut data_ref) => {
+            if let Some(inner) = data_ref.as_mut() {
+                for (chain, http) in evm_rpc_http {
+                    match inner.evm_rpc.iter_mut().find(|(name, _)| *name == chain) {
+                        Some((_, rpc)) => rpc.http = http,
+                        None => inner.evm_rpc.push((
+                            chain,
+                            EvmRpcConfig {
+                                http,
+                                ..EvmRpcConfig::default()
+                            },
+                        )),
+                    }
+                }
+                inner.opf_http = opf_http;
+            }
+        }
+        _ => (), // XXX todo: log?
+    };
+    Ok(())
+}
+
+/// Apply any overrides from the `<prefix>_TIMEOUT_MS`, `<prefix>_RETRIES` and `<prefix>_HEADERS`
+///  env vars to the HTTP options for an endpoint.
+/// Headers in the env var are separated by `;`, which keeps credentials off the command line.
+fn http_config_with_env(
+    env_prefix: &str,
+    mut http_config: HttpConfig,
+) -> Result<HttpConfig, String> {
+    let env_var = format!("{}_TIMEOUT_MS", env_prefix);
+    if let Ok(timeout_ms) = std::env::var(&env_var) {
+        let timeout_ms = timeout_ms
+            .parse()
+            .map_err(|_| format!("Invalid {}, expected milliseconds: {}", env_var, timeout_ms))?;
+        http_config.timeout_ms = Some(timeout_ms);
+    }
+    let env_var = format!("{}_RETRIES", env_prefix);
+    if let Ok(retries) = std::env::var(&env_var) {
+        let retries = retries
+            .parse()
+            .map_err(|_| format!("Invalid {}, expected a count: {}", env_var, retries))?;
+        http_config.retries = Some(retries);
+    }
+    let env_var = format!("{}_HEADERS", env_prefix);
+    if let Ok(headers) = std::env::var(&env_var) {
+        let headers: Vec<String> = headers
+            .split(';')
+            .filter(|header| !header.trim().is_empty())
+            .map(String::from)
+            .collect();
+        let env_config = HttpConfig::parse(None, None, &headers)
+            .map_err(|err| format!("Invalid {}: {}", env_var, err))?;
+        http_config.headers = env_config.headers;
+    }
+    Ok(http_config)
+}
+
+/// Get the HTTP options for an endpoint from the validator config.
+fn get_http_config(configured: impl Fn(&ValidatorConfig) -> Option<&HttpConfig>) -> HttpConfig {
+    match VALIDATOR_CONFIG.lock() {
+        Ok(config) => config
+            .as_ref()
+            .and_then(configured)
+            .cloned()
+            .unwrap_or_default(),
+        _ => HttpConfig::default(),
+    }
 }
 
 /// The configuration interface for offchain workers. This is designed to manage configuration
@@ -86,15 +381,23 @@ pub trait ConfigInterface {
 }
 
 const ETH_KEY_ID_ENV_VAR: &str = "ETH_KEY_ID";
-const ETH_RPC_URL_ENV_VAR: &str = "ETH_RPC_URL";
+const LIQUIDATOR_ACCOUNT_ENV_VAR: &str = "LIQUIDATOR_ACCOUNT";
 const MINER_ENV_VAR: &str = "MINER";
 const OPF_URL_ENV_VAR: &str = "OPF_URL";
+const OPF_HTTP_ENV_PREFIX: &str = "OPF";
 
 const ETH_KEY_ID_DEFAULT: &str = gateway_crypto::ETH_KEY_ID_ENV_VAR_DEV_DEFAULT;
 const MINER_DEFAULT: &str = "Eth:0x0000000000000000000000000000000000000000";
-const ETH_RPC_URL_DEFAULT: &str = "https://ropsten-eth.compound.finance";
 const OPF_URL_DEFAULT: &str = "https://prices.compound.finance/coinbase";
 
+/// The RPC URLs used for EVM chains when the validator doesn't configure any, by the name of the chain.
+const EVM_RPC_URL_DEFAULTS: &[(&str, &str)] = &[("eth", "https://ropsten-eth.compound.finance")];
+
+/// The prefix of the env vars which configure the RPC of the named EVM chain, e.g. `ETH_RPC`.
+fn evm_rpc_env_prefix(chain: &str) -> String {
+    format!("{}_RPC", chain.to_uppercase())
+}
+
 /// The ValidatorConfigInterface is designed to be modified as needed by the validators. This means
 /// that each validator should be modifying the values here. For example, the ETH_KEY_ID is set
 /// by each validator separately corresponding to their HSM configuration and key ID that they
@@ -128,22 +431,43 @@ pub trait ValidatorConfigInterface {
         return Some(ETH_KEY_ID_DEFAULT.into());
     }
 
-    /// Get the Ethereum node RPC URL
-    fn get_eth_rpc_url() -> Option<Vec<u8>> {
+    /// Get the RPC URL of the named EVM chain, or several comma separated URLs to fail over between,
+    ///  if the validator follows the starport on that chain.
+    fn get_evm_rpc_url(chain: &str) -> Option<Vec<u8>> {
         // check env override
-        if let Ok(eth_rpc_url) = std::env::var(ETH_RPC_URL_ENV_VAR) {
-            if eth_rpc_url.len() > 0 {
-                return Some(eth_rpc_url.into());
+        if let Ok(rpc_url) = std::env::var(format!("{}_URL", evm_rpc_env_prefix(chain))) {
+            if rpc_url.len() > 0 {
+                return Some(rpc_url.into());
             }
         }
         // check config
         if let Ok(config) = VALIDATOR_CONFIG.lock() {
-            if let Some(inner) = config.as_ref() {
-                return Some(inner.eth_rpc_url.clone().into());
+            if let Some(url) = config
+                .as_ref()
+                .and_then(|inner| inner.evm_rpc(chain))
+                .and_then(|rpc| rpc.url.clone())
+            {
+                return Some(url.into());
             }
         }
-        // not set
-        return Some(ETH_RPC_URL_DEFAULT.into());
+        // not set, use the default if the chain has one
+        EVM_RPC_URL_DEFAULTS
+            .iter()
+            .find(|(name, _)| *name == chain)
+            .map(|(_, url)| (*url).into())
+    }
+
+    /// Get the number of RPC URLs of the named EVM chain which must return identical logs
+    ///  for an event to be signed, if the validator requires them to agree.
+    fn get_evm_rpc_quorum(chain: &str) -> Option<u32> {
+        // check config, which has any env override, if not set any single URL is trusted
+        match VALIDATOR_CONFIG.lock() {
+            Ok(config) => config
+                .as_ref()
+                .and_then(|inner| inner.evm_rpc(chain))
+                .and_then(|rpc| rpc.quorum),
+            _ => None,
+        }
     }
 
     /// Get the open price feed URLs
@@ -164,6 +488,58 @@ pub trait ValidatorConfigInterface {
         return Some(OPF_URL_DEFAULT.into());
     }
 
+    /// Get the most blocks a single `eth_getLogs` call to the named EVM chain may span, if configured.
+    fn get_evm_rpc_logs_block_span(chain: &str) -> Option<u64> {
+        // check config, which has any env override
+        match VALIDATOR_CONFIG.lock() {
+            Ok(config) => config
+                .as_ref()
+                .and_then(|inner| inner.evm_rpc(chain))
+                .and_then(|rpc| rpc.logs_block_span),
+            _ => None,
+        }
+    }
+
+    /// Get the most `eth_getLogs` calls to the named EVM chain sent in a single batch request, if configured.
+    fn get_evm_rpc_logs_batch_size(chain: &str) -> Option<u32> {
+        // check config, which has any env override
+        match VALIDATOR_CONFIG.lock() {
+            Ok(config) => config
+                .as_ref()
+                .and_then(|inner| inner.evm_rpc(chain))
+                .and_then(|rpc| rpc.logs_batch_size),
+            _ => None,
+        }
+    }
+
+    /// Get the HTTP options for requests to the RPC URLs of the named EVM chain
+    fn get_evm_rpc_http_config(chain: &str) -> HttpConfig {
+        get_http_config(|config| config.evm_rpc(chain).map(|rpc| &rpc.http))
+    }
+
+    /// Get the HTTP options for requests to the open price feed
+    fn get_opf_http_config() -> HttpConfig {
+        get_http_config(|config| Some(&config.opf_http))
+    }
+
+    /// Get the Key ID for the liquidator account, if the liquidation keeper is enabled.
+    fn get_liquidator_account() -> Option<Vec<u8>> {
+        // check env override
+        if let Ok(liquidator_account) = std::env::var(LIQUIDATOR_ACCOUNT_ENV_VAR) {
+            if liquidator_account.len() > 0 {
+                return Some(liquidator_account.into());
+            }
+        }
+        // check config
+        if let Ok(config) = VALIDATOR_CONFIG.lock() {
+            if let Some(inner) = config.as_ref() {
+                return inner.liquidator_account.clone().map(Into::into);
+            }
+        }
+        // not set, keeper disabled
+        return None;
+    }
+
     /// Get the Miner address
     fn get_miner_address() -> Option<Vec<u8>> {
         // check env override
@@ -261,12 +637,154 @@ mod tests {
         let expected_eth_starport_address: Vec<u8> =
             "0xbbde1662bC3ED16aA8C618c9833c801F3543B587".into();
 
-        let config = new_config(given_eth_starport_address.clone());
+        let given_matic_starport_address: Vec<u8> =
+            "0x77e6d8d7b3bcd3ce6ad28f9f4de6ca5a0a11a4d7".into();
+
+        let config = new_config(vec![
+            StarportDeployment {
+                chain: String::from("eth"),
+                address: given_eth_starport_address.clone(),
+                block: 9_000_000,
+            },
+            StarportDeployment {
+                chain: String::from("matic"),
+                address: given_matic_starport_address.clone(),
+                block: 0,
+            },
+        ]);
         // set in node
         config_interface::set(config);
         // ...later... in offchain worker context, get the configuration
         let actual_config = config_interface::get();
-        let actual_eth_starport_address = actual_config.eth_starport_address;
+        let actual_eth_starport_address = actual_config.get_starport_address("eth");
         assert_eq!(expected_eth_starport_address, actual_eth_starport_address);
+        assert_eq!(
+            given_matic_starport_address,
+            actual_config.get_starport_address("matic")
+        );
+        assert_eq!(actual_config.get_starport_address("sol"), Vec::<u8>::new());
+        assert_eq!(actual_config.get_starport_deploy_block("eth"), 9_000_000);
+        assert_eq!(actual_config.get_starport_deploy_block("sol"), 0);
+    }
+
+    #[test]
+    fn test_parse_http_config() {
+        let http_config = HttpConfig::parse(
+            Some(10_000),
+            None,
+            &[
+                String::from("Authorization: Bearer abc:def"),
+                String::from(" X-Api-Key :123 "),
+                String::from("url=https://a.example.com X-Secret: a=b"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(http_config.get_timeout_ms(), Some(10_000));
+        assert_eq!(http_config.get_retries(), None);
+        assert_eq!(
+            http_config.get_headers("https://b.example.com"),
+            vec![
+                (
+                    String::from("Authorization"),
+                    String::from("Bearer abc:def")
+                ),
+                (String::from("X-Api-Key"), String::from("123")),
+            ]
+        );
+        assert_eq!(
+            http_config.get_headers("https://a.example.com"),
+            vec![
+                (
+                    String::from("Authorization"),
+                    String::from("Bearer abc:def")
+                ),
+                (String::from("X-Api-Key"), String::from("123")),
+                (String::from("X-Secret"), String::from("a=b")),
+            ]
+        );
+
+        assert!(HttpConfig::parse(None, None, &[String::from("Bearer abc")]).is_err());
+        assert!(HttpConfig::parse(None, None, &[String::from(": abc")]).is_err());
+        assert!(
+            HttpConfig::parse(None, None, &[String::from("url=https://a.example.com")]).is_err()
+        );
+        assert!(HttpConfig::parse(None, None, &[String::from("url= X-Id: 1")]).is_err());
+    }
+
+    #[test]
+    fn test_http_config_env_overrides() {
+        std::env::set_var("TEST_HTTP_TIMEOUT_MS", "5000");
+        std::env::set_var(
+            "TEST_HTTP_HEADERS",
+            "Authorization: Basic dXNlcjpwYXNz; url=https://a.example.com X-Id: 1;",
+        );
+        let http_config = http_config_with_env(
+            "TEST_HTTP",
+            HttpConfig::parse(Some(1_000), Some(3), &[String::from("X-Old: 0")]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(http_config.get_timeout_ms(), Some(5_000));
+        assert_eq!(http_config.get_retries(), Some(3));
+        assert_eq!(
+            http_config.get_headers("https://a.example.com"),
+            vec![
+                (
+                    String::from("Authorization"),
+                    String::from("Basic dXNlcjpwYXNz")
+                ),
+                (String::from("X-Id"), String::from("1")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_http_config_invalid_env() {
+        std::env::set_var("TEST_BAD_RETRIES_HTTP_RETRIES", "two");
+        assert_eq!(
+            http_config_with_env("TEST_BAD_RETRIES_HTTP", HttpConfig::default()).err(),
+            Some(String::from(
+                "Invalid TEST_BAD_RETRIES_HTTP_RETRIES, expected a count: two"
+            ))
+        );
+
+        std::env::set_var("TEST_BAD_TIMEOUT_HTTP_TIMEOUT_MS", "2s");
+        assert!(http_config_with_env("TEST_BAD_TIMEOUT_HTTP", HttpConfig::default()).is_err());
+
+        std::env::set_var("TEST_BAD_HEADERS_HTTP_HEADERS", "Authorization Bearer abc");
+        assert!(http_config_with_env("TEST_BAD_HEADERS_HTTP", HttpConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_evm_rpc_config_env_overrides() {
+        std::env::set_var("TEST_RPC_QUORUM", "2");
+        std::env::set_var("TEST_RPC_LOGS_BATCH_SIZE", "10");
+        let rpc_config = evm_rpc_config_with_env(
+            "TEST_RPC",
+            EvmRpcConfig::new(None, Some(1), Some(1_000), Some(5)),
+        )
+        .unwrap();
+        assert_eq!(rpc_config.quorum, Some(2));
+        assert_eq!(rpc_config.logs_block_span, Some(1_000));
+        assert_eq!(rpc_config.logs_batch_size, Some(10));
+    }
+
+    #[test]
+    fn test_evm_rpc_config_invalid_env() {
+        std::env::set_var("TEST_BAD_QUORUM_RPC_QUORUM", "two");
+        assert_eq!(
+            evm_rpc_config_with_env("TEST_BAD_QUORUM_RPC", EvmRpcConfig::default()).err(),
+            Some(String::from(
+                "Invalid TEST_BAD_QUORUM_RPC_QUORUM, expected a count of URLs: two"
+            ))
+        );
+
+        std::env::set_var("TEST_ZERO_QUORUM_RPC_QUORUM", "0");
+        assert!(evm_rpc_config_with_env("TEST_ZERO_QUORUM_RPC", EvmRpcConfig::default()).is_err());
+
+        std::env::set_var("TEST_BAD_SPAN_RPC_LOGS_BLOCK_SPAN", "-1");
+        assert!(evm_rpc_config_with_env("TEST_BAD_SPAN_RPC", EvmRpcConfig::default()).is_err());
+
+        std::env::set_var("TEST_BAD_BATCH_RPC_LOGS_BATCH_SIZE", "1e3");
+        assert!(evm_rpc_config_with_env("TEST_BAD_BATCH_RPC", EvmRpcConfig::default()).is_err());
     }
 }
@@ -44,7 +44,8 @@ pub use pallet_grandpa::{AuthorityId as GrandpaId, AuthorityList as GrandpaAutho
 pub use pallet_timestamp::Call as TimestampCall;
 
 use pallet_cash::{
-    chains::{ChainAccount, ChainAsset},
+    chains::{ChainAccount, ChainAsset, ChainId},
+    notices::NoticeId,
     rates::APR,
     reason::Reason,
     types::{AssetAmount, AssetBalance, AssetInfo},
@@ -121,7 +122,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("gateway"),
     impl_name: create_runtime_str!("gateway"),
     authoring_version: 1,
-    spec_version: 8,
+    spec_version: 9,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -291,6 +292,7 @@ impl pallet_session::Config for Runtime {
 impl pallet_oracle::Config for Runtime {
     type Call = Call;
     type Event = Event;
+    type TimeProvider = Timestamp;
 }
 
 /// Configure the CASH pallet in pallets/cash.
@@ -572,6 +574,10 @@ impl_runtime_apis! {
         fn get_rates(asset: ChainAsset) -> Result<(APR, APR), Reason> {
             Cash::get_rates(asset)
         }
+
+        fn is_notice_ready(chain_id: ChainId, notice_id: NoticeId) -> Result<bool, Reason> {
+            Cash::is_notice_ready(chain_id, notice_id)
+        }
     }
 
     #[cfg(feature = "runtime-benchmarks")]
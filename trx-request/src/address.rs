@@ -0,0 +1,210 @@
+use sha2::{Digest, Sha256};
+use std::convert::TryInto;
+
+/// The SS58 network prefix used by Gateway accounts.
+pub const GATE_SS58_PREFIX: u8 = 42;
+
+/// The SS58 network prefix used by Polkadot accounts.
+pub const DOT_SS58_PREFIX: u8 = 0;
+
+/// The base58check prefix of a Tezos ed25519 implicit account (`tz1`).
+pub const TEZ_TZ1_PREFIX: [u8; 3] = [6, 161, 159];
+
+/// The base58check prefix of a Tezos secp256k1 implicit account (`tz2`).
+pub const TEZ_TZ2_PREFIX: [u8; 3] = [6, 161, 161];
+
+/// The base58check prefix of a Tezos p256 implicit account (`tz3`).
+pub const TEZ_TZ3_PREFIX: [u8; 3] = [6, 161, 164];
+
+/// The base58check prefixes of Tezos implicit accounts, indexed by the tag of their curve.
+const TEZ_IMPLICIT_PREFIXES: [[u8; 3]; 3] = [TEZ_TZ1_PREFIX, TEZ_TZ2_PREFIX, TEZ_TZ3_PREFIX];
+
+/// The base58check prefix of a Tezos originated contract (`KT1`).
+pub const TEZ_KT1_PREFIX: [u8; 3] = [2, 90, 121];
+
+const SS58_CHECKSUM_PREFIX: &[u8] = b"SS58PRE";
+
+fn ss58_checksum(data: &[u8]) -> [u8; 2] {
+    let hash = blake2_rfc::blake2b::blake2b(64, &[], &[SS58_CHECKSUM_PREFIX, data].concat());
+    [hash.as_bytes()[0], hash.as_bytes()[1]]
+}
+
+fn base58check_checksum(data: &[u8]) -> [u8; 4] {
+    let hash = Sha256::digest(&Sha256::digest(data));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Decode an SS58 address with a single byte network prefix into its public key.
+pub fn decode_ss58(address: &str, prefix: u8) -> Option<[u8; 32]> {
+    let bytes = bs58::decode(address).into_vec().ok()?;
+    match &bytes[..] {
+        [network, key @ .., c0, c1] if *network == prefix && key.len() == 32 => {
+            if ss58_checksum(&bytes[..33]) == [*c0, *c1] {
+                key.try_into().ok()
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Encode a public key as an SS58 address with a single byte network prefix.
+pub fn encode_ss58(key: &[u8; 32], prefix: u8) -> String {
+    let data = [&[prefix][..], &key[..]].concat();
+    let checksum = ss58_checksum(&data);
+    bs58::encode([&data[..], &checksum[..]].concat()).into_string()
+}
+
+/// Decode a plain base58 public key, as used by Solana.
+pub fn decode_base58(address: &str) -> Option<[u8; 32]> {
+    bs58::decode(address).into_vec().ok()?.try_into().ok()
+}
+
+/// Encode a public key as plain base58, as used by Solana.
+pub fn encode_base58(key: &[u8; 32]) -> String {
+    bs58::encode(key).into_string()
+}
+
+/// Decode a Tezos base58check address with the given prefix into its key hash.
+pub fn decode_tezos(address: &str, prefix: [u8; 3]) -> Option<[u8; 20]> {
+    let bytes = bs58::decode(address).into_vec().ok()?;
+    if bytes.len() != 27 || bytes[..3] != prefix[..] {
+        return None;
+    }
+    if base58check_checksum(&bytes[..23]) != bytes[23..] {
+        return None;
+    }
+    bytes[3..23].try_into().ok()
+}
+
+/// Encode a Tezos key hash as a base58check address with the given prefix.
+pub fn encode_tezos(hash: &[u8; 20], prefix: [u8; 3]) -> String {
+    let data = [&prefix[..], &hash[..]].concat();
+    let checksum = base58check_checksum(&data);
+    bs58::encode([&data[..], &checksum[..]].concat()).into_string()
+}
+
+/// Decode a Tezos implicit account (`tz1`, `tz2` or `tz3`) into the tag of its curve followed by its key hash,
+///  the same as Tezos' own binary encoding of a public key hash.
+pub fn decode_tezos_account(address: &str) -> Option<[u8; 21]> {
+    TEZ_IMPLICIT_PREFIXES
+        .iter()
+        .enumerate()
+        .find_map(|(tag, prefix)| {
+            let hash = decode_tezos(address, *prefix)?;
+            let mut account = [tag as u8; 21];
+            account[1..].copy_from_slice(&hash);
+            Some(account)
+        })
+}
+
+/// Encode a Tezos implicit account, given as the tag of its curve followed by its key hash,
+///  unless the tag isn't one of a known curve.
+pub fn encode_tezos_account(account: &[u8; 21]) -> Option<String> {
+    let prefix = TEZ_IMPLICIT_PREFIXES.get(account[0] as usize)?;
+    let hash: [u8; 20] = account[1..].try_into().ok()?;
+    Some(encode_tezos(&hash, *prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: [u8; 32] = [
+        212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88,
+        133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125,
+    ];
+
+    #[test]
+    fn test_ss58() {
+        let gate = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let dot = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5";
+
+        assert_eq!(decode_ss58(gate, GATE_SS58_PREFIX), Some(ALICE));
+        assert_eq!(decode_ss58(dot, DOT_SS58_PREFIX), Some(ALICE));
+        assert_eq!(encode_ss58(&ALICE, GATE_SS58_PREFIX), gate);
+        assert_eq!(encode_ss58(&ALICE, DOT_SS58_PREFIX), dot);
+
+        // wrong network
+        assert_eq!(decode_ss58(gate, DOT_SS58_PREFIX), None);
+        // bad checksum
+        assert_eq!(
+            decode_ss58(
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ",
+                GATE_SS58_PREFIX
+            ),
+            None
+        );
+        // not base58
+        assert_eq!(decode_ss58("0x01", GATE_SS58_PREFIX), None);
+    }
+
+    #[test]
+    fn test_base58() {
+        let token_program = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+        let key = decode_base58(token_program).unwrap();
+
+        assert_eq!(
+            hex::encode(key),
+            "06ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a9"
+        );
+        assert_eq!(encode_base58(&key), token_program);
+        assert_eq!(
+            decode_base58("11111111111111111111111111111111"),
+            Some([0; 32])
+        );
+        assert_eq!(decode_base58("TokenkegQfeZyiNwAJbNbGKPFXCWu"), None);
+    }
+
+    #[test]
+    fn test_tezos() {
+        assert_eq!(
+            decode_tezos("tz1KjLa4hxghcRgtK6i8BgPTXathEV66JaSk", TEZ_TZ1_PREFIX),
+            Some([1; 20])
+        );
+        assert_eq!(
+            decode_tezos("KT1WN8coMkJHbdjqUeSQM5FEerxRgu1st3tf", TEZ_KT1_PREFIX),
+            Some([238; 20])
+        );
+        assert_eq!(
+            encode_tezos(&[1; 20], TEZ_TZ1_PREFIX),
+            "tz1KjLa4hxghcRgtK6i8BgPTXathEV66JaSk"
+        );
+
+        // wrong prefix
+        assert_eq!(
+            decode_tezos("tz1KjLa4hxghcRgtK6i8BgPTXathEV66JaSk", TEZ_KT1_PREFIX),
+            None
+        );
+        // bad checksum
+        assert_eq!(
+            decode_tezos("tz1KjLa4hxghcRgtK6i8BgPTXathEV66JaSm", TEZ_TZ1_PREFIX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tezos_accounts() {
+        let mut account = [1; 21];
+        for (tag, address) in [
+            "tz1KjLa4hxghcRgtK6i8BgPTXathEV66JaSk",
+            "tz28QYYHJY74YBKkbGkxrKMi5A9hkNZzdwGm",
+            "tz3LRMVcCQEbvon41XqDrGp5tX3DXCiw6Ge3",
+        ]
+        .iter()
+        .enumerate()
+        {
+            account[0] = tag as u8;
+            assert_eq!(decode_tezos_account(address), Some(account));
+            assert_eq!(encode_tezos_account(&account).as_deref(), Some(*address));
+        }
+
+        assert_eq!(
+            decode_tezos_account("KT1WN8coMkJHbdjqUeSQM5FEerxRgu1st3tf"),
+            None
+        );
+        account[0] = 3;
+        assert_eq!(encode_tezos_account(&account), None);
+    }
+}
@@ -1,4 +1,5 @@
 use logos::{Lexer, Logos};
+use std::convert::TryInto;
 
 #[derive(Logos, Debug, PartialEq, Eq, Clone)]
 pub enum Token<'a> {
@@ -14,7 +15,10 @@ pub enum Token<'a> {
     #[regex(r"[0-9]+", parse_int)]
     Integer(Option<u128>),
 
-    #[regex(r"[a-zA-Z-]+")]
+    #[regex(r"[0-9]+\.[0-9]+", parse_decimal)]
+    Decimal(Option<(u128, u8)>),
+
+    #[regex(r"[a-zA-Z][a-zA-Z0-9-]*")]
     Identifier(&'a str),
 
     #[regex(r"[a-zA-Z0-9]+:[a-zA-Z0-9]+", split_pair)]
@@ -39,6 +43,13 @@ fn parse_int<'a>(lex: &mut Lexer<'a, Token<'a>>) -> Option<u128> {
     u128::from_str_radix(&lex.slice()[..], 10).ok()
 }
 
+fn parse_decimal<'a>(lex: &mut Lexer<'a, Token<'a>>) -> Option<(u128, u8)> {
+    let (whole, fractional) = lex.slice().split_once('.')?;
+    let digits = [whole, fractional].concat();
+    let decimals = fractional.len().try_into().ok()?;
+    Some((u128::from_str_radix(&digits[..], 10).ok()?, decimals))
+}
+
 pub fn lex<'source>(text: &'source str) -> Lexer<'source, Token> {
     Token::lexer(text)
 }
@@ -85,5 +96,23 @@ mod tests {
           Token::Pair(Some(("eth", "0x20"))),
           Token::RightDelim
         ],
+      identifier_with_digits:
+        "(Extract 1 USDC0 C3PO-2)" => vec![
+          Token::LeftDelim,
+          Token::Identifier("Extract"),
+          Token::Integer(Some(1)),
+          Token::Identifier("USDC0"),
+          Token::Identifier("C3PO-2"),
+          Token::RightDelim
+        ],
+      decimal:
+        "(1.50 0.000001 1.)" => vec![
+          Token::LeftDelim,
+          Token::Decimal(Some((150, 2))),
+          Token::Decimal(Some((1, 6))),
+          Token::Integer(Some(1)),
+          Token::Error,
+          Token::RightDelim
+        ],
     }
 }
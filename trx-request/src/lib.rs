@@ -3,34 +3,61 @@
 #![allow(incomplete_features)]
 #![feature(unsized_locals)]
 
+pub mod address;
 mod hex_util;
 mod lex;
 use lex::{lex, Token};
 use logos::Lexer;
-use std::convert::TryInto;
+use std::{convert::TryInto, fmt, ops::Range};
 
 pub type Amount = u128;
 
+/// An amount in whole units of an asset, e.g. `1.5`, which must be resolved against the decimals of the asset.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Decimal {
+    pub digits: Amount,
+    pub decimals: u8,
+}
+
 #[derive(PartialEq, Eq, Debug)]
 pub enum MaxAmount {
+    /// An amount in base units of an asset, e.g. wei, used as is.
     Amount(Amount),
+    Decimal(Decimal),
     Max,
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum Chain {
+    Gate,
     Eth,
+    Dot,
+    Sol,
+    Tez,
+    Matic,
 }
 
 #[derive(PartialEq, Eq, Debug)]
 pub enum Asset {
     Cash,
+    Gate([u8; 32]),
     Eth([u8; 20]),
+    Dot([u8; 32]),
+    Sol([u8; 32]),
+    Tez([u8; 20]),
+    Matic([u8; 20]),
+    Symbol(String),
 }
 
 #[derive(PartialEq, Eq, Debug)]
 pub enum Account {
+    Gate([u8; 32]),
     Eth([u8; 20]),
+    Dot([u8; 32]),
+    Sol([u8; 32]),
+    /// The tag of the account's curve, followed by its key hash.
+    Tez([u8; 21]),
+    Matic([u8; 20]),
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -38,10 +65,15 @@ pub enum TrxRequest {
     Extract(MaxAmount, Asset, Account),
     Transfer(MaxAmount, Asset, Account),
     Liquidate(MaxAmount, Asset, Asset, Account),
+    Batch(Vec<TrxRequest>),
 }
 
+pub type Span = Range<usize>;
+
+type SpannedToken<'a> = (Token<'a>, Span);
+
 #[derive(PartialEq, Eq, Debug)]
-pub enum ParseError<'a> {
+pub enum ParseErrorKind<'a> {
     NotImplemented,
     LexError(&'a str),
     InvalidAmount,
@@ -51,78 +83,242 @@ pub enum ParseError<'a> {
     InvalidExpression,
     InvalidChain(&'a str),
     InvalidChainAccount(Chain),
+    EmptyBatch,
+    NestedBatch,
 }
 
-fn parse_amount<'a>(t: &Token) -> Result<Amount, ParseError<'a>> {
-    match t {
-        Token::Integer(Some(v)) => Ok(*v),
-        Token::Hex(Some(v)) => Ok(hex_util::hex_to_u128(v).ok_or(ParseError::InvalidAmount)?),
-        _ => Err(ParseError::InvalidAmount), // TODO: Debug here?
+/// An error parsing a trx request, with the span of the input at fault and what was expected there.
+#[derive(PartialEq, Eq, Debug)]
+pub struct ParseError<'a> {
+    pub kind: ParseErrorKind<'a>,
+    pub span: Span,
+    pub expected: &'static str,
+}
+
+impl<'a> ParseErrorKind<'a> {
+    fn at(self, span: &Span, expected: &'static str) -> ParseError<'a> {
+        ParseError {
+            kind: self,
+            span: span.clone(),
+            expected,
+        }
     }
 }
 
-fn parse_max_amount<'a>(t: &Token) -> Result<MaxAmount, ParseError<'a>> {
-    match t {
-        Token::Identifier("Max") | Token::Identifier("MAX") => Ok(MaxAmount::Max),
-        els => Ok(MaxAmount::Amount(parse_amount(els)?)),
+const EXPECTED_TOKEN: &str = "token";
+const EXPECTED_REQUEST: &str = "request";
+const EXPECTED_FUNCTION: &str = "Extract, Transfer, Liquidate or Batch";
+const EXPECTED_AMOUNT: &str = "amount";
+const EXPECTED_ASSET: &str = "asset";
+const EXPECTED_ACCOUNT: &str = "account";
+const EXPECTED_OPEN: &str = "(";
+const EXPECTED_CLOSE: &str = ")";
+
+/// Without any decimals, the digits are written as an integer, as the amount is given in base units
+///  of an asset which has no decimals either.
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.decimals == 0 {
+            return write!(f, "{}", self.digits);
+        }
+        let decimals = self.decimals as usize;
+        let digits = format!("{:0>width$}", self.digits, width = decimals + 1);
+        let (whole, fractional) = digits.split_at(digits.len() - decimals);
+        write!(f, "{}.{}", whole, fractional)
     }
 }
 
-fn parse_chain<'a>(chain: &'a str) -> Result<Chain, ParseError<'a>> {
-    match chain {
-        "Eth" => Ok(Chain::Eth),
-        _ => Err(ParseError::InvalidChain(chain)),
+impl fmt::Display for MaxAmount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MaxAmount::Amount(amount) => write!(f, "{}", amount),
+            MaxAmount::Decimal(decimal) => write!(f, "{}", decimal),
+            MaxAmount::Max => write!(f, "Max"),
+        }
     }
 }
 
-fn parse_eth_address<'a>(account: &'a str) -> Result<[u8; 20], ParseError<'a>> {
-    if &account[0..2] != "0x" {
-        Err(ParseError::InvalidChainAccount(Chain::Eth))?;
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Chain::Gate => write!(f, "Gate"),
+            Chain::Eth => write!(f, "Eth"),
+            Chain::Dot => write!(f, "Dot"),
+            Chain::Sol => write!(f, "Sol"),
+            Chain::Tez => write!(f, "Tez"),
+            Chain::Matic => write!(f, "Matic"),
+        }
     }
+}
 
-    let account_vec: Vec<u8> =
-        hex::decode(&account[2..]).map_err(|_| ParseError::InvalidChainAccount(Chain::Eth))?;
-    let chain_account: [u8; 20] = account_vec
-        .try_into()
-        .map_err(|_| ParseError::InvalidChainAccount(Chain::Eth))?;
+impl fmt::Display for Asset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Asset::Cash => write!(f, "Cash"),
+            Asset::Gate(key) => write!(
+                f,
+                "{}:{}",
+                Chain::Gate,
+                address::encode_ss58(key, address::GATE_SS58_PREFIX)
+            ),
+            Asset::Eth(address) => write!(f, "{}:0x{}", Chain::Eth, hex::encode(address)),
+            Asset::Dot(key) => write!(
+                f,
+                "{}:{}",
+                Chain::Dot,
+                address::encode_ss58(key, address::DOT_SS58_PREFIX)
+            ),
+            Asset::Sol(key) => write!(f, "{}:{}", Chain::Sol, address::encode_base58(key)),
+            Asset::Tez(hash) => write!(
+                f,
+                "{}:{}",
+                Chain::Tez,
+                address::encode_tezos(hash, address::TEZ_KT1_PREFIX)
+            ),
+            Asset::Matic(address) => write!(f, "{}:0x{}", Chain::Matic, hex::encode(address)),
+            Asset::Symbol(symbol) => write!(f, "{}", symbol),
+        }
+    }
+}
 
-    Ok(chain_account)
+impl fmt::Display for Account {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Account::Gate(key) => write!(
+                f,
+                "{}:{}",
+                Chain::Gate,
+                address::encode_ss58(key, address::GATE_SS58_PREFIX)
+            ),
+            Account::Eth(address) => write!(f, "{}:0x{}", Chain::Eth, hex::encode(address)),
+            Account::Dot(key) => write!(
+                f,
+                "{}:{}",
+                Chain::Dot,
+                address::encode_ss58(key, address::DOT_SS58_PREFIX)
+            ),
+            Account::Sol(key) => write!(f, "{}:{}", Chain::Sol, address::encode_base58(key)),
+            Account::Tez(account) => match address::encode_tezos_account(account) {
+                Some(address) => write!(f, "{}:{}", Chain::Tez, address),
+                None => write!(f, "{}:0x{}", Chain::Tez, hex::encode(account)),
+            },
+            Account::Matic(address) => write!(f, "{}:0x{}", Chain::Matic, hex::encode(address)),
+        }
+    }
 }
 
-fn parse_chain_account<'a>(chain: Chain, address: &'a str) -> Result<Account, ParseError<'a>> {
-    match chain {
-        Chain::Eth => Ok(Account::Eth(parse_eth_address(address)?)),
+/// The canonical form of a request, which parses back to the same request.
+impl fmt::Display for TrxRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TrxRequest::Extract(amount, asset, account) => {
+                write!(f, "(Extract {} {} {})", amount, asset, account)
+            }
+            TrxRequest::Transfer(amount, asset, account) => {
+                write!(f, "(Transfer {} {} {})", amount, asset, account)
+            }
+            TrxRequest::Liquidate(amount, borrowed_asset, collateral_asset, borrower) => write!(
+                f,
+                "(Liquidate {} {} {} {})",
+                amount, borrowed_asset, collateral_asset, borrower
+            ),
+            TrxRequest::Batch(requests) => {
+                write!(f, "(Batch")?;
+                for request in requests {
+                    write!(f, " {}", request)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+fn parse_amount<'a>(t: &Token) -> Result<Amount, ParseErrorKind<'a>> {
+    match t {
+        Token::Integer(Some(v)) => Ok(*v),
+        Token::Hex(Some(v)) => Ok(hex_util::hex_to_u128(v).ok_or(ParseErrorKind::InvalidAmount)?),
+        _ => Err(ParseErrorKind::InvalidAmount), // TODO: Debug here?
+    }
+}
+
+fn parse_max_amount<'a>((t, span): &SpannedToken<'a>) -> Result<MaxAmount, ParseError<'a>> {
+    match t {
+        Token::Identifier("Max") | Token::Identifier("MAX") => Ok(MaxAmount::Max),
+        Token::Decimal(Some((digits, decimals))) => Ok(MaxAmount::Decimal(Decimal {
+            digits: *digits,
+            decimals: *decimals,
+        })),
+        Token::Decimal(None) => Err(ParseErrorKind::InvalidAmount.at(span, EXPECTED_AMOUNT)),
+        els => Ok(MaxAmount::Amount(
+            parse_amount(els).map_err(|err| err.at(span, EXPECTED_AMOUNT))?,
+        )),
     }
 }
 
-fn parse_chain_asset<'a>(chain: Chain, address: &'a str) -> Result<Asset, ParseError<'a>> {
+fn parse_chain<'a>(chain: &'a str) -> Result<Chain, ParseErrorKind<'a>> {
     match chain {
-        Chain::Eth => Ok(Asset::Eth(parse_eth_address(address)?)),
+        "Gate" => Ok(Chain::Gate),
+        "Eth" => Ok(Chain::Eth),
+        "Dot" => Ok(Chain::Dot),
+        "Sol" => Ok(Chain::Sol),
+        "Tez" => Ok(Chain::Tez),
+        "Matic" => Ok(Chain::Matic),
+        _ => Err(ParseErrorKind::InvalidChain(chain)),
     }
 }
 
-fn parse_account<'a>(t: &Token<'a>) -> Result<Account, ParseError<'a>> {
+/// Parse a hex address, as used by Ethereum and the other EVM chains.
+fn parse_eth_address(account: &str) -> Option<[u8; 20]> {
+    let hex_account = account.strip_prefix("0x")?;
+    let account_vec: Vec<u8> = hex::decode(hex_account).ok()?;
+    account_vec.try_into().ok()
+}
+
+fn parse_chain_account<'a>(chain: Chain, addr: &'a str) -> Result<Account, ParseErrorKind<'a>> {
+    let account = match chain {
+        Chain::Gate => address::decode_ss58(addr, address::GATE_SS58_PREFIX).map(Account::Gate),
+        Chain::Eth => parse_eth_address(addr).map(Account::Eth),
+        Chain::Dot => address::decode_ss58(addr, address::DOT_SS58_PREFIX).map(Account::Dot),
+        Chain::Sol => address::decode_base58(addr).map(Account::Sol),
+        Chain::Tez => address::decode_tezos_account(addr).map(Account::Tez),
+        Chain::Matic => parse_eth_address(addr).map(Account::Matic),
+    };
+    account.ok_or(ParseErrorKind::InvalidChainAccount(chain))
+}
+
+fn parse_chain_asset<'a>(chain: Chain, addr: &'a str) -> Result<Asset, ParseErrorKind<'a>> {
+    let asset = match chain {
+        Chain::Gate => address::decode_ss58(addr, address::GATE_SS58_PREFIX).map(Asset::Gate),
+        Chain::Eth => parse_eth_address(addr).map(Asset::Eth),
+        Chain::Dot => address::decode_ss58(addr, address::DOT_SS58_PREFIX).map(Asset::Dot),
+        Chain::Sol => address::decode_base58(addr).map(Asset::Sol),
+        Chain::Tez => address::decode_tezos(addr, address::TEZ_KT1_PREFIX).map(Asset::Tez),
+        Chain::Matic => parse_eth_address(addr).map(Asset::Matic),
+    };
+    asset.ok_or(ParseErrorKind::InvalidChainAccount(chain))
+}
+
+fn parse_account<'a>((t, span): &SpannedToken<'a>) -> Result<Account, ParseError<'a>> {
     match t {
-        Token::Pair(Some((chain_str, account_str))) => {
-            let chain = parse_chain(chain_str)?;
-            Ok(parse_chain_account(chain, account_str)?)
-        }
-        _ => Err(ParseError::InvalidAddress),
+        Token::Pair(Some((chain_str, account_str))) => parse_chain(chain_str)
+            .and_then(|chain| parse_chain_account(chain, account_str))
+            .map_err(|err| err.at(span, EXPECTED_ACCOUNT)),
+        _ => Err(ParseErrorKind::InvalidAddress.at(span, EXPECTED_ACCOUNT)),
     }
 }
 
-fn parse_asset<'a>(t: &Token<'a>) -> Result<Asset, ParseError<'a>> {
+fn parse_asset<'a>((t, span): &SpannedToken<'a>) -> Result<Asset, ParseError<'a>> {
     match t {
         Token::Identifier("Cash") | Token::Identifier("CASH") => Ok(Asset::Cash),
-        Token::Pair(Some((chain_str, account_str))) => {
-            let chain = parse_chain(chain_str)?;
-            Ok(parse_chain_asset(chain, account_str)?)
-        }
-        _ => Err(ParseError::InvalidAddress),
+        Token::Pair(Some((chain_str, account_str))) => parse_chain(chain_str)
+            .and_then(|chain| parse_chain_asset(chain, account_str))
+            .map_err(|err| err.at(span, EXPECTED_ASSET)),
+        Token::Identifier(symbol) => Ok(Asset::Symbol(symbol.to_string())),
+        _ => Err(ParseErrorKind::InvalidAddress.at(span, EXPECTED_ASSET)),
     }
 }
 
-fn parse_extract<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+fn parse_extract<'a>(args: &[SpannedToken<'a>], span: &Span) -> Result<TrxRequest, ParseError<'a>> {
     match args {
         [amount_token, asset_token, account_token] => {
             let max_amount = parse_max_amount(amount_token)?;
@@ -131,11 +327,15 @@ fn parse_extract<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
 
             Ok(TrxRequest::Extract(max_amount, asset, account))
         }
-        _ => Err(ParseError::InvalidArgs("Extract", 3, args.len())),
+        _ => Err(ParseErrorKind::InvalidArgs("Extract", 3, args.len())
+            .at(span, "(Extract amount asset account)")),
     }
 }
 
-fn parse_transfer<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+fn parse_transfer<'a>(
+    args: &[SpannedToken<'a>],
+    span: &Span,
+) -> Result<TrxRequest, ParseError<'a>> {
     match args {
         [amount_token, asset_token, account_token] => {
             let max_amount = parse_max_amount(amount_token)?;
@@ -144,11 +344,15 @@ fn parse_transfer<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>>
 
             Ok(TrxRequest::Transfer(max_amount, asset, account))
         }
-        _ => Err(ParseError::InvalidArgs("Transfer", 3, args.len())),
+        _ => Err(ParseErrorKind::InvalidArgs("Transfer", 3, args.len())
+            .at(span, "(Transfer amount asset account)")),
     }
 }
 
-fn parse_liquidate<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+fn parse_liquidate<'a>(
+    args: &[SpannedToken<'a>],
+    span: &Span,
+) -> Result<TrxRequest, ParseError<'a>> {
     match args {
         [amount_token, borrowed_asset_token, collateral_asset_token, account_token] => {
             let max_amount = parse_max_amount(amount_token)?;
@@ -163,39 +367,92 @@ fn parse_liquidate<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>>
                 account,
             ))
         }
-        _ => Err(ParseError::InvalidArgs("Liquidate", 4, args.len())),
+        _ => Err(ParseErrorKind::InvalidArgs("Liquidate", 4, args.len())
+            .at(span, "(Liquidate amount asset asset account)")),
     }
 }
 
 fn parse<'a>(tokens: Lexer<'a, Token<'a>>) -> Result<TrxRequest, ParseError<'a>> {
-    // TODO: I don't love having to clone here at all
-    tokens
-        .clone()
-        .spanned()
-        .fold(Ok(()) as Result<(), ParseError<'a>>, |acc, el| {
-            match (acc, el) {
-                (Err(err), _) => Err(err),
-                (_, (Token::Error, span)) => Err(ParseError::LexError(&tokens.source()[span])),
-                (_, _) => Ok(()),
-            }
-        })?;
+    let source = tokens.source();
+    let token_vec = tokens.spanned().collect::<Vec<SpannedToken<'a>>>();
 
-    let token_vec = tokens.collect::<Vec<Token<'a>>>();
+    if let Some((_, span)) = token_vec.iter().find(|(token, _)| *token == Token::Error) {
+        return Err(ParseErrorKind::LexError(&source[span.clone()]).at(span, EXPECTED_TOKEN));
+    }
 
-    match &token_vec[..] {
-        [Token::LeftDelim, Token::Identifier("Extract"), args @ .., Token::RightDelim] => {
-            parse_extract(args)
+    parse_expression(&token_vec[..])
+}
+
+/// The span of the input covered by the tokens.
+fn span_of(tokens: &[SpannedToken]) -> Span {
+    match tokens {
+        [] => 0..0,
+        [(_, span)] => span.clone(),
+        [(_, first), .., (_, last)] => first.start..last.end,
+    }
+}
+
+fn parse_expression<'a>(tokens: &[SpannedToken<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    let span = span_of(tokens);
+    match tokens {
+        [(Token::LeftDelim, _), (Token::Identifier("Extract"), _), args @ .., (Token::RightDelim, _)] => {
+            parse_extract(args, &span)
         }
-        [Token::LeftDelim, Token::Identifier("Transfer"), args @ .., Token::RightDelim] => {
-            parse_transfer(args)
+        [(Token::LeftDelim, _), (Token::Identifier("Transfer"), _), args @ .., (Token::RightDelim, _)] => {
+            parse_transfer(args, &span)
         }
-        [Token::LeftDelim, Token::Identifier("Liquidate"), args @ .., Token::RightDelim] => {
-            parse_liquidate(args)
+        [(Token::LeftDelim, _), (Token::Identifier("Liquidate"), _), args @ .., (Token::RightDelim, _)] => {
+            parse_liquidate(args, &span)
         }
-        [Token::LeftDelim, Token::Identifier(fun), .., Token::RightDelim] => {
-            Err(ParseError::UnknownFunction(fun))
+        [(Token::LeftDelim, _), (Token::Identifier("Batch"), _), args @ .., (Token::RightDelim, _)] => {
+            parse_batch(args, &span)
+        }
+        [(Token::LeftDelim, _), (Token::Identifier(fun), fun_span), .., (Token::RightDelim, _)] => {
+            Err(ParseErrorKind::UnknownFunction(fun).at(fun_span, EXPECTED_FUNCTION))
+        }
+        _ => Err(ParseErrorKind::InvalidExpression.at(&span, EXPECTED_REQUEST)),
+    }
+}
+
+/// Splits the first parenthesized expression off the front of the tokens.
+fn split_expression<'a, 'b>(
+    tokens: &'b [SpannedToken<'a>],
+) -> Result<(&'b [SpannedToken<'a>], &'b [SpannedToken<'a>]), ParseError<'a>> {
+    let mut depth = 0;
+    for (i, (token, span)) in tokens.iter().enumerate() {
+        match token {
+            Token::LeftDelim => depth += 1,
+            Token::RightDelim if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(tokens.split_at(i + 1));
+                }
+            }
+            _ if depth > 0 => (),
+            _ => return Err(ParseErrorKind::InvalidExpression.at(span, EXPECTED_OPEN)),
+        }
+    }
+    Err(ParseErrorKind::InvalidExpression.at(&span_of(tokens), EXPECTED_CLOSE))
+}
+
+fn parse_batch<'a>(args: &[SpannedToken<'a>], span: &Span) -> Result<TrxRequest, ParseError<'a>> {
+    let mut requests = Vec::new();
+    let mut rest = args;
+    while !rest.is_empty() {
+        let (expression, tail) = split_expression(rest)?;
+        match parse_expression(expression)? {
+            TrxRequest::Batch(_) => {
+                return Err(ParseErrorKind::NestedBatch.at(&span_of(expression), EXPECTED_REQUEST))
+            }
+            request => requests.push(request),
         }
-        _ => Err(ParseError::InvalidExpression),
+        rest = tail;
+    }
+
+    if requests.is_empty() {
+        Err(ParseErrorKind::EmptyBatch.at(span, EXPECTED_REQUEST))
+    } else {
+        Ok(TrxRequest::Batch(requests))
     }
 }
 
@@ -212,6 +469,14 @@ mod tests {
         238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238, 238,
         238, 238,
     ];
+    const ALICE: [u8; 32] = [
+        212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88,
+        133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125,
+    ];
+    const TOKEN_PROGRAM: [u8; 32] = [
+        6, 221, 246, 225, 215, 101, 161, 147, 217, 203, 225, 70, 206, 235, 121, 172, 28, 180, 133,
+        237, 95, 91, 55, 145, 58, 140, 245, 133, 126, 255, 0, 169,
+    ];
 
     macro_rules! parse_tests {
     ($($name:ident: $input:expr => $exp:expr,)*) => {
@@ -229,11 +494,11 @@ mod tests {
 
     parse_tests! {
       parse_fail_lex_error:
-        "(fricassée)" => Err(ParseError::LexError("é")),
+        "(fricassée)" => Err(ParseErrorKind::LexError("é").at(&(8..10), EXPECTED_TOKEN)),
       parse_fail_invalid_expression:
-        "hello" => Err(ParseError::InvalidExpression),
+        "hello" => Err(ParseErrorKind::InvalidExpression.at(&(0..5), EXPECTED_REQUEST)),
       parse_fail_unknown_function:
-        "(MyFun 3 Eth:0x55)" => Err(ParseError::UnknownFunction("MyFun")),
+        "(MyFun 3 Eth:0x55)" => Err(ParseErrorKind::UnknownFunction("MyFun").at(&(1..6), EXPECTED_FUNCTION)),
       parse_extract:
         "(Extract 3 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::Extract(
           MaxAmount::Amount(3),
@@ -296,18 +561,155 @@ mod tests {
           Asset::Eth(ETH),
           Account::Eth(ALAN)
         )),
+      parse_batch:
+        "(Batch (Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) (Transfer Max Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101))" => Ok(TrxRequest::Batch(vec![
+          TrxRequest::Extract(MaxAmount::Amount(3), Asset::Cash, Account::Eth(ALAN)),
+          TrxRequest::Transfer(MaxAmount::Max, Asset::Eth(ETH), Account::Eth(ALAN)),
+        ])),
+      parse_fail_empty_batch:
+        "(Batch)" => Err(ParseErrorKind::EmptyBatch.at(&(0..7), EXPECTED_REQUEST)),
+      parse_fail_nested_batch:
+        "(Batch (Batch (Extract 3 Cash Eth:0x0101010101010101010101010101010101010101)))" => Err(ParseErrorKind::NestedBatch.at(&(7..78), EXPECTED_REQUEST)),
+      parse_fail_batch_bare_args:
+        "(Batch Extract 3 Cash Eth:0x0101010101010101010101010101010101010101)" => Err(ParseErrorKind::InvalidExpression.at(&(7..14), EXPECTED_OPEN)),
+      parse_fail_batch_unbalanced:
+        "(Batch (Extract 3 Cash Eth:0x0101010101010101010101010101010101010101)))" => Err(ParseErrorKind::InvalidExpression.at(&(70..71), EXPECTED_OPEN)),
+      parse_fail_batch_invalid_request:
+        "(Batch (Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) (Transfer 3 Cash))" => Err(ParseErrorKind::InvalidArgs("Transfer", 3, 2).at(&(71..88), "(Transfer amount asset account)")),
+      parse_extract_decimal_symbol:
+        "(Extract 1.5 ETH Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::Extract(
+          MaxAmount::Decimal(Decimal { digits: 15, decimals: 1 }),
+          Asset::Symbol(String::from("ETH")),
+          Account::Eth(ALAN)
+        )),
+      parse_transfer_decimal_address:
+        "(Transfer 0.25 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::Transfer(
+          MaxAmount::Decimal(Decimal { digits: 25, decimals: 2 }),
+          Asset::Eth(ETH),
+          Account::Eth(ALAN)
+        )),
+      parse_liquidate_symbols:
+        "(Liquidate 100.0 USDC Cash Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::Liquidate(
+          MaxAmount::Decimal(Decimal { digits: 1000, decimals: 1 }),
+          Asset::Symbol(String::from("USDC")),
+          Asset::Cash,
+          Account::Eth(ALAN)
+        )),
+      parse_extract_symbol_with_digits:
+        "(Extract 2.5 USDC2 Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::Extract(
+          MaxAmount::Decimal(Decimal { digits: 25, decimals: 1 }),
+          Asset::Symbol(String::from("USDC2")),
+          Account::Eth(ALAN)
+        )),
+      parse_fail_invalid_amount_too_large_decimal:
+        "(Extract 340282366920938463463374607431768211.456 ETH Eth:0x0101010101010101010101010101010101010101)" => Err(ParseErrorKind::InvalidAmount.at(&(9..49), EXPECTED_AMOUNT)),
+      parse_fail_symbol_account:
+        "(Extract 1.5 ETH ALAN)" => Err(ParseErrorKind::InvalidAddress.at(&(17..21), EXPECTED_ACCOUNT)),
+      parse_transfer_gate:
+        "(Transfer 3 Cash Gate:5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY)" => Ok(TrxRequest::Transfer(
+          MaxAmount::Amount(3),
+          Asset::Cash,
+          Account::Gate(ALICE)
+        )),
+      parse_extract_dot:
+        "(Extract 3 Dot:15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5 Dot:15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5)" => Ok(TrxRequest::Extract(
+          MaxAmount::Amount(3),
+          Asset::Dot(ALICE),
+          Account::Dot(ALICE)
+        )),
+      parse_extract_sol:
+        "(Extract 3 Sol:TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA Sol:11111111111111111111111111111111)" => Ok(TrxRequest::Extract(
+          MaxAmount::Amount(3),
+          Asset::Sol(TOKEN_PROGRAM),
+          Account::Sol([0; 32])
+        )),
+      parse_extract_tez:
+        "(Extract 3 Tez:KT1WN8coMkJHbdjqUeSQM5FEerxRgu1st3tf Tez:tz1KjLa4hxghcRgtK6i8BgPTXathEV66JaSk)" => Ok(TrxRequest::Extract(
+          MaxAmount::Amount(3),
+          Asset::Tez(ETH),
+          Account::Tez([0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1])
+        )),
+      parse_transfer_tez_tz2:
+        "(Transfer 3 Cash Tez:tz28QYYHJY74YBKkbGkxrKMi5A9hkNZzdwGm)" => Ok(TrxRequest::Transfer(
+          MaxAmount::Amount(3),
+          Asset::Cash,
+          Account::Tez([1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1])
+        )),
+      parse_transfer_tez_tz3:
+        "(Transfer 3 Cash Tez:tz3LRMVcCQEbvon41XqDrGp5tX3DXCiw6Ge3)" => Ok(TrxRequest::Transfer(
+          MaxAmount::Amount(3),
+          Asset::Cash,
+          Account::Tez([2, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1])
+        )),
+      parse_extract_matic:
+        "(Extract 3 Matic:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Matic:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::Extract(
+          MaxAmount::Amount(3),
+          Asset::Matic([238; 20]),
+          Account::Matic([1; 20])
+        )),
+      parse_fail_short_matic_account:
+        "(Transfer 3 Cash Matic:0x0101)" => Err(ParseErrorKind::InvalidChainAccount(Chain::Matic).at(&(17..29), EXPECTED_ACCOUNT)),
+      parse_fail_dot_account_wrong_network:
+        "(Transfer 3 Cash Dot:5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY)" => Err(ParseErrorKind::InvalidChainAccount(Chain::Dot).at(&(17..69), EXPECTED_ACCOUNT)),
+      parse_fail_tez_account_contract:
+        "(Transfer 3 Cash Tez:KT1WN8coMkJHbdjqUeSQM5FEerxRgu1st3tf)" => Err(ParseErrorKind::InvalidChainAccount(Chain::Tez).at(&(17..57), EXPECTED_ACCOUNT)),
+      parse_fail_unknown_chain:
+        "(Transfer 3 Cash Btc:0x0101)" => Err(ParseErrorKind::InvalidChain("Btc").at(&(17..27), EXPECTED_ACCOUNT)),
       // TODO: Should we prohibit non-Cash from being Maxable?
       parse_fail_no_zero_ex:
-        "(Extract 3 Eth:xxeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidChainAccount(Chain::Eth)),
+        "(Extract 3 Eth:xxeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Err(ParseErrorKind::InvalidChainAccount(Chain::Eth).at(&(11..57), EXPECTED_ASSET)),
+      parse_fail_short_eth_account:
+        "(Transfer 3 Cash Eth:x)" => Err(ParseErrorKind::InvalidChainAccount(Chain::Eth).at(&(17..22), EXPECTED_ACCOUNT)),
       parse_fail_invalid_amount_invalid:
-        "(Extract hi Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidAmount),
+        "(Extract hi Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Err(ParseErrorKind::InvalidAmount.at(&(9..11), EXPECTED_AMOUNT)),
       parse_fail_invalid_amount_too_large_int:
-        "(Extract 340282366920938463463374607431768211456 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidAmount),
+        "(Extract 340282366920938463463374607431768211456 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Err(ParseErrorKind::InvalidAmount.at(&(9..48), EXPECTED_AMOUNT)),
       parse_fail_invalid_amount_too_large_hex:
-        "(Extract 0xffffffffffffffffffffffffffffffff00 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidAmount),
+        "(Extract 0xffffffffffffffffffffffffffffffff00 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Err(ParseErrorKind::InvalidAmount.at(&(9..45), EXPECTED_AMOUNT)),
       parse_fail_invalid_asset:
-        "(Extract 5 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeff Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidChainAccount(Chain::Eth)),
+        "(Extract 5 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeff Eth:0x0101010101010101010101010101010101010101)" => Err(ParseErrorKind::InvalidChainAccount(Chain::Eth).at(&(11..59), EXPECTED_ASSET)),
       parse_fail_invalid_recipient:
-        "(Extract 5 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101ff)" => Err(ParseError::InvalidChainAccount(Chain::Eth)),
+        "(Extract 5 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101ff)" => Err(ParseErrorKind::InvalidChainAccount(Chain::Eth).at(&(58..106), EXPECTED_ACCOUNT)),
+    }
+    #[test]
+    fn test_display_round_trip() {
+        let requests = [
+            "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101)",
+            "(Transfer Max Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)",
+            "(Liquidate 0.050 USDC Cash Eth:0x0101010101010101010101010101010101010101)",
+            "(Batch (Extract 1.5 ETH Eth:0x0101010101010101010101010101010101010101) (Transfer 2 Cash Eth:0x0101010101010101010101010101010101010101))",
+            "(Transfer 3 Cash Gate:5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY)",
+            "(Extract 3 Dot:15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5 Dot:15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5)",
+            "(Extract 3 Sol:TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA Sol:11111111111111111111111111111111)",
+            "(Extract 3 Tez:KT1WN8coMkJHbdjqUeSQM5FEerxRgu1st3tf Tez:tz1KjLa4hxghcRgtK6i8BgPTXathEV66JaSk)",
+            "(Transfer 3 Cash Tez:tz28QYYHJY74YBKkbGkxrKMi5A9hkNZzdwGm)",
+            "(Transfer 3 Cash Tez:tz3LRMVcCQEbvon41XqDrGp5tX3DXCiw6Ge3)",
+            "(Transfer 3 Matic:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Matic:0x0101010101010101010101010101010101010101)",
+        ];
+        for request in requests.iter() {
+            let parsed = parse_request(request).unwrap();
+            assert_eq!(&parsed.to_string(), request);
+            assert_eq!(parse_request(&parsed.to_string()), Ok(parsed));
+        }
+    }
+
+    #[test]
+    fn test_display_canonical() {
+        let parsed = parse_request(
+            "(Extract  0x0100 CASH\nEth:0x0101010101010101010101010101010101010101 )",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.to_string(),
+            "(Extract 256 Cash Eth:0x0101010101010101010101010101010101010101)"
+        );
+    }
+
+    #[test]
+    fn test_display_decimal() {
+        let decimal = |digits, decimals| Decimal { digits, decimals }.to_string();
+        assert_eq!(decimal(1, 6), "0.000001");
+        assert_eq!(decimal(1500, 3), "1.500");
+        assert_eq!(decimal(15, 0), "15");
     }
 }